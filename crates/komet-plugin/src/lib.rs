@@ -1,6 +1,5 @@
 use ciborium_io::Write as _;
-use ciborium_ll::{Decoder, Encoder, Header};
-use rustfft::{FftDirection, FftPlanner};
+use ciborium_ll::{simple, Decoder, Encoder, Header};
 use wasm_minimal_protocol::*;
 initiate_protocol!();
 
@@ -70,10 +69,2073 @@ pub fn contour(input: &[u8]) -> Result<Vec<u8>, String> {
 pub fn histogram(input: &[u8]) -> Result<Vec<u8>, String> {
     let mut decoder = Decoder::from(input);
 
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 3 {
+                return Err(String::from("Expected array of 3 elements"));
+            }
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let edges = read::read_edges(&mut decoder, &values)?;
+
+            let fold = read::read_bool(&mut decoder)?;
+
+            let histogram = komet::histogram(&values, &edges, fold);
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+            // Write the structure
+            encoder.push(Header::Map(Some(5))).unwrap();
+
+            encoder.text("counts", None).unwrap();
+            encoder
+                .push(Header::Array(Some(histogram.counts.len())))
+                .unwrap();
+            for count in histogram.counts {
+                encoder.push(Header::Positive(count)).unwrap();
+            }
+
+            encoder.text("edges", None).unwrap();
+            encoder.push(Header::Array(Some(edges.len()))).unwrap();
+            for edge in edges {
+                encoder.push(Header::Float(edge)).unwrap();
+            }
+
+            encoder.text("underflow", None).unwrap();
+            encoder.push(Header::Positive(histogram.underflow)).unwrap();
+
+            encoder.text("overflow", None).unwrap();
+            encoder.push(Header::Positive(histogram.overflow)).unwrap();
+
+            encoder.text("nan", None).unwrap();
+            encoder.push(Header::Positive(histogram.nan)).unwrap();
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+/// Returns the bin indices as packed 32-bit big-endian integers.
+#[wasm_func]
+pub fn digitize(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 3 {
+                return Err(String::from("Expected array of 3 elements"));
+            }
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let edges = read::read_edges(&mut decoder, &values)?;
+            let right = read::read_bool(&mut decoder)?;
+
+            let indices = komet::digitize(&values, &edges, right);
+
+            Ok(indices
+                .iter()
+                .flat_map(|&index| (index as i32).to_be_bytes())
+                .collect())
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+fn read_statistic<R: std::io::Read>(decoder: &mut Decoder<R>) -> Result<komet::Statistic, String> {
+    use komet::Statistic;
+
+    match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => match read::read_string(decoder, len)?.as_str() {
+            "count" => Ok(Statistic::Count),
+            "sum" => Ok(Statistic::Sum),
+            "mean" => Ok(Statistic::Mean),
+            "median" => Ok(Statistic::Median),
+            "std" => Ok(Statistic::Std),
+            "min" => Ok(Statistic::Min),
+            "max" => Ok(Statistic::Max),
+            name => Err(format!("Unknown statistic \"{name}\"")),
+        },
+        Header::Float(q) => Ok(Statistic::Percentile(q)),
+        _ => Err(String::from("Bad input")),
+    }
+}
+
+#[wasm_func]
+pub fn binned_statistic(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 4 {
+                return Err(String::from("Expected array of 4 elements"));
+            }
+
+            let x = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            if x.len() != values.len() {
+                return Err(String::from("x and values need to have the same length"));
+            }
+
+            let edges = read::read_edges(&mut decoder, &x)?;
+            let statistic = read_statistic(&mut decoder)?;
+
+            let result = komet::binned_statistic(&x, &values, &edges, statistic);
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+            // Write the structure
+            encoder.push(Header::Map(Some(2))).unwrap();
+
+            encoder.text("statistic", None).unwrap();
+            encoder.push(Header::Array(Some(result.len()))).unwrap();
+            for value in result {
+                encoder.push(Header::Float(value)).unwrap();
+            }
+
+            encoder.text("edges", None).unwrap();
+            encoder.push(Header::Array(Some(edges.len()))).unwrap();
+            for edge in edges {
+                encoder.push(Header::Float(edge)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+#[wasm_func]
+pub fn binned_statistic_2d(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 6 {
+                return Err(String::from("Expected array of 6 elements"));
+            }
+
+            let x = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let y = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            if x.len() != values.len() || y.len() != values.len() {
+                return Err(String::from(
+                    "x, y, and values need to have the same length",
+                ));
+            }
+
+            let x_edges = read::read_edges(&mut decoder, &x)?;
+            let y_edges = read::read_edges(&mut decoder, &y)?;
+            let statistic = read_statistic(&mut decoder)?;
+
+            let result = komet::binned_statistic_2d(&x, &y, &values, &x_edges, &y_edges, statistic);
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+            // Write the structure
+            encoder.push(Header::Map(Some(3))).unwrap();
+
+            encoder.text("statistic", None).unwrap();
+            encoder.push(Header::Array(Some(result.len()))).unwrap();
+            for value in result {
+                encoder.push(Header::Float(value)).unwrap();
+            }
+
+            encoder.text("x-edges", None).unwrap();
+            encoder.push(Header::Array(Some(x_edges.len()))).unwrap();
+            for edge in x_edges {
+                encoder.push(Header::Float(edge)).unwrap();
+            }
+
+            encoder.text("y-edges", None).unwrap();
+            encoder.push(Header::Array(Some(y_edges.len()))).unwrap();
+            for edge in y_edges {
+                encoder.push(Header::Float(edge)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+/// Checks that weights are non-negative and finite with at least one
+/// positive weight.
+fn check_weights(weights: &[f64]) -> Result<(), String> {
+    if weights.iter().any(|w| !(w.is_finite() && *w >= 0.)) {
+        return Err(String::from("weights must be non-negative and finite"));
+    }
+    if weights.iter().all(|&w| w == 0.) {
+        return Err(String::from("at least one weight must be positive"));
+    }
+    Ok(())
+}
+
+fn read_kernel<R: std::io::Read>(decoder: &mut Decoder<R>) -> Result<komet::Kernel, String> {
+    use komet::Kernel;
+
+    match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => match read::read_string(decoder, len)?.as_str() {
+            "gaussian" => Ok(Kernel::Gaussian),
+            "epanechnikov" => Ok(Kernel::Epanechnikov),
+            "uniform" => Ok(Kernel::Uniform),
+            "triangular" => Ok(Kernel::Triangular),
+            "biweight" => Ok(Kernel::Biweight),
+            "triweight" => Ok(Kernel::Triweight),
+            "cosine" => Ok(Kernel::Cosine),
+            name => Err(format!("Unknown kernel \"{name}\"")),
+        },
+        _ => Err(String::from("Bad input")),
+    }
+}
+
+fn read_bandwidth<R: std::io::Read>(decoder: &mut Decoder<R>) -> Result<komet::Bandwidth, String> {
+    use komet::Bandwidth;
+
+    match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => match read::read_string(decoder, len)?.as_str() {
+            "scott" => Ok(Bandwidth::Scott),
+            "silverman" => Ok(Bandwidth::Silverman),
+            "isj" => Ok(Bandwidth::Isj),
+            name => Err(format!("Unknown bandwidth rule \"{name}\"")),
+        },
+        Header::Float(h) if h > 0. && h.is_finite() => Ok(Bandwidth::Fixed(h)),
+        Header::Float(_) => Err(String::from("bandwidth must be positive")),
+        _ => Err(String::from("Bad input")),
+    }
+}
+
+#[wasm_func]
+pub fn kde(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 7 {
+                return Err(String::from("Expected array of 7 elements"));
+            }
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let weights = read::read_optional_float_array(&mut decoder)?;
+            if weights.as_ref().is_some_and(|w| w.len() != values.len()) {
+                return Err(String::from(
+                    "values and weights need to have the same length",
+                ));
+            }
+
+            // NaN values are ignored together with their weights
+            let (values, weights) = match weights {
+                Some(weights) => {
+                    let (values, weights) = values
+                        .into_iter()
+                        .zip(weights)
+                        .filter(|(x, _)| !x.is_nan())
+                        .unzip::<f64, f64, Vec<_>, Vec<_>>();
+                    (values, Some(weights))
+                }
+                None => (values.into_iter().filter(|x| !x.is_nan()).collect(), None),
+            };
+            if values.is_empty() {
+                return Err(String::from("values must contain at least one number"));
+            }
+            if let Some(weights) = &weights {
+                check_weights(weights)?;
+            }
+
+            // Either an explicit grid or at least two grid points
+            let (grid, num_points) = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => (read::read_float_array(&mut decoder, len)?, None),
+                Header::Positive(num_points) if num_points >= 2 => (Vec::new(), Some(num_points)),
+                Header::Positive(_) => return Err(String::from("Expected at least 2 grid points")),
+                _ => return Err(String::from("Bad input")),
+            };
+            let kernel = read_kernel(&mut decoder)?;
+            let bandwidth = read_bandwidth(&mut decoder)?;
+            let lower_bound = read::read_optional_float(&mut decoder)?;
+            let upper_bound = read::read_optional_float(&mut decoder)?;
+
+            let bandwidth = komet::bandwidth(&values, weights.as_deref(), bandwidth);
+
+            let grid = match num_points {
+                Some(num_points) => {
+                    // Extend the grid by three bandwidths beyond the data
+                    let cut = 3. * bandwidth;
+                    let min = values.iter().fold(f64::INFINITY, |a, &b| a.min(b)) - cut;
+                    let max = values.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b)) + cut;
+                    let min = lower_bound.map_or(min, |a| a.max(min));
+                    let max = upper_bound.map_or(max, |b| b.min(max));
+
+                    let step = (max - min) / (num_points as f64 - 1.);
+                    (0..num_points).map(|x| min + (x as f64) * step).collect()
+                }
+                None => grid,
+            };
+
+            let density = komet::kde(
+                &values,
+                &grid,
+                &komet::KdeOptions {
+                    kernel,
+                    bandwidth: komet::Bandwidth::Fixed(bandwidth),
+                    weights: weights.as_deref(),
+                    lower_bound,
+                    upper_bound,
+                },
+            );
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+            // Write the structure
+            encoder.push(Header::Map(Some(3))).unwrap();
+
+            encoder.text("x", None).unwrap();
+            encoder.push(Header::Array(Some(grid.len()))).unwrap();
+            for x in grid {
+                encoder.push(Header::Float(x)).unwrap();
+            }
+
+            encoder.text("density", None).unwrap();
+            encoder.push(Header::Array(Some(density.len()))).unwrap();
+            for value in density {
+                encoder.push(Header::Float(value)).unwrap();
+            }
+
+            encoder.text("bandwidth", None).unwrap();
+            encoder.push(Header::Float(bandwidth)).unwrap();
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+fn read_bandwidth_matrix<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<komet::BandwidthMatrix, String> {
+    use komet::BandwidthMatrix;
+
+    match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => match read::read_string(decoder, len)?.as_str() {
+            "scott" => Ok(BandwidthMatrix::Scott),
+            "silverman" => Ok(BandwidthMatrix::Silverman),
+            name => Err(format!("Unknown bandwidth rule \"{name}\"")),
+        },
+        Header::Float(h) => Ok(BandwidthMatrix::Fixed([[h * h, 0.], [0., h * h]])),
+        Header::Array(Some(2)) => match read::read_float_array_2d(decoder, 2)?.as_slice() {
+            [row1, row2] if row1.len() == 2 && row2.len() == 2 => Ok(BandwidthMatrix::Fixed([
+                [row1[0], row1[1]],
+                [row2[0], row2[1]],
+            ])),
+            _ => Err(String::from("Expected a 2x2 bandwidth matrix")),
+        },
+        _ => Err(String::from("Bad input")),
+    }
+}
+
+/// Checks whether grid points are ascending with a constant step, up to
+/// rounding errors.
+fn is_even_grid(grid: &[f64]) -> bool {
+    let n = grid.len();
+    let step = (grid[n - 1] - grid[0]) / (n - 1) as f64;
+    step > 0.
+        && step.is_finite()
+        && grid
+            .iter()
+            .enumerate()
+            .all(|(i, &x)| (x - (grid[0] + i as f64 * step)).abs() <= 1e-6 * step)
+}
+
+#[wasm_func]
+pub fn kde_2d(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 6 {
+                return Err(String::from("Expected array of 6 elements"));
+            }
+
+            let x = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let y = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            if x.len() != y.len() {
+                return Err(String::from("x and y need to have the same length"));
+            }
+
+            let weights = read::read_optional_float_array(&mut decoder)?;
+            if weights.as_ref().is_some_and(|w| w.len() != x.len()) {
+                return Err(String::from(
+                    "x, y, and weights need to have the same length",
+                ));
+            }
+            if let Some(weights) = &weights {
+                check_weights(weights)?;
+            }
+
+            // Either explicit grids or numbers of grid points
+            let x_grid = decoder.pull().unwrap();
+            let x_grid = match x_grid {
+                Header::Array(Some(len)) => Ok(read::read_float_array(&mut decoder, len)?),
+                Header::Positive(num_points) => Err(num_points),
+                _ => return Err(String::from("Bad input")),
+            };
+            let y_grid = decoder.pull().unwrap();
+            let y_grid = match y_grid {
+                Header::Array(Some(len)) => Ok(read::read_float_array(&mut decoder, len)?),
+                Header::Positive(num_points) => Err(num_points),
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let bandwidth = read_bandwidth_matrix(&mut decoder)?;
+            let Some(bandwidth) = komet::bandwidth_matrix(&x, &y, weights.as_deref(), bandwidth)
+            else {
+                return Err(String::from(
+                    "bandwidth matrix is singular, e.g., because the points lie on a line",
+                ));
+            };
+
+            // Extend generated grids by three standard deviations of the kernel
+            let make_grid = |values: &[f64], variance: f64, num_points: u64| -> Vec<f64> {
+                let cut = 3. * variance.sqrt();
+                let min = values.iter().fold(f64::INFINITY, |a, &b| a.min(b)) - cut;
+                let max = values.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b)) + cut;
+
+                let step = (max - min) / (num_points as f64 - 1.);
+                (0..num_points).map(|x| min + (x as f64) * step).collect()
+            };
+            let x_grid = x_grid.unwrap_or_else(|n| make_grid(&x, bandwidth[0][0], n));
+            let y_grid = y_grid.unwrap_or_else(|n| make_grid(&y, bandwidth[1][1], n));
+            if x_grid.len() < 2 || y_grid.len() < 2 {
+                return Err(String::from("The grids need to have at least two points"));
+            }
+            if !is_even_grid(&x_grid) || !is_even_grid(&y_grid) {
+                return Err(String::from("grids must be ascending and evenly spaced"));
+            }
+
+            let z = komet::kde_2d(
+                &x,
+                &y,
+                weights.as_deref(),
+                &x_grid,
+                &y_grid,
+                komet::BandwidthMatrix::Fixed(bandwidth),
+            )
+            .unwrap();
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+            // Write the structure
+            encoder.push(Header::Map(Some(4))).unwrap();
+
+            encoder.text("x", None).unwrap();
+            encoder.push(Header::Array(Some(x_grid.len()))).unwrap();
+            for value in x_grid {
+                encoder.push(Header::Float(value)).unwrap();
+            }
+
+            encoder.text("y", None).unwrap();
+            encoder.push(Header::Array(Some(y_grid.len()))).unwrap();
+            for value in y_grid {
+                encoder.push(Header::Float(value)).unwrap();
+            }
+
+            encoder.text("z", None).unwrap();
+            encoder.push(Header::Array(Some(z.len()))).unwrap();
+            for value in z {
+                encoder.push(Header::Float(value)).unwrap();
+            }
+
+            encoder.text("bandwidth", None).unwrap();
+            encoder.push(Header::Array(Some(2))).unwrap();
+            for row in bandwidth {
+                encoder.push(Header::Array(Some(2))).unwrap();
+                for value in row {
+                    encoder.push(Header::Float(value)).unwrap();
+                }
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+#[wasm_func]
+pub fn density_levels(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 2 {
+                return Err(String::from("Expected array of 2 elements"));
+            }
+
+            let z = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+            if z.is_empty() {
+                return Err(String::from("z must not be empty"));
+            }
+
+            let fractions = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let levels = komet::density_levels(&z, &fractions);
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            encoder.push(Header::Array(Some(levels.len()))).unwrap();
+            for level in levels {
+                encoder.push(Header::Float(level)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+fn read_quantile_method<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<komet::QuantileMethod, String> {
+    use komet::QuantileMethod;
+
+    match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => {
+            match read::read_string(decoder, len)?.replace('-', "_").as_str() {
+                "inverted_cdf" => Ok(QuantileMethod::InvertedCdf),
+                "averaged_inverted_cdf" => Ok(QuantileMethod::AveragedInvertedCdf),
+                "closest_observation" => Ok(QuantileMethod::ClosestObservation),
+                "interpolated_inverted_cdf" => Ok(QuantileMethod::InterpolatedInvertedCdf),
+                "hazen" => Ok(QuantileMethod::Hazen),
+                "weibull" => Ok(QuantileMethod::Weibull),
+                "linear" => Ok(QuantileMethod::Linear),
+                "median_unbiased" => Ok(QuantileMethod::MedianUnbiased),
+                "normal_unbiased" => Ok(QuantileMethod::NormalUnbiased),
+                "lower" => Ok(QuantileMethod::Lower),
+                "higher" => Ok(QuantileMethod::Higher),
+                "nearest" => Ok(QuantileMethod::Nearest),
+                "midpoint" => Ok(QuantileMethod::Midpoint),
+                name => Err(format!("Unknown quantile method \"{name}\"")),
+            }
+        }
+        // Hyndman–Fan types 1 to 9
+        Header::Positive(1) => Ok(QuantileMethod::InvertedCdf),
+        Header::Positive(2) => Ok(QuantileMethod::AveragedInvertedCdf),
+        Header::Positive(3) => Ok(QuantileMethod::ClosestObservation),
+        Header::Positive(4) => Ok(QuantileMethod::InterpolatedInvertedCdf),
+        Header::Positive(5) => Ok(QuantileMethod::Hazen),
+        Header::Positive(6) => Ok(QuantileMethod::Weibull),
+        Header::Positive(7) => Ok(QuantileMethod::Linear),
+        Header::Positive(8) => Ok(QuantileMethod::MedianUnbiased),
+        Header::Positive(9) => Ok(QuantileMethod::NormalUnbiased),
+        _ => Err(String::from("Bad input")),
+    }
+}
+
+fn read_notch<R: std::io::Read>(decoder: &mut Decoder<R>) -> Result<komet::Notch, String> {
+    match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => match read::read_string(decoder, len)?.as_str() {
+            "mcgill" => Ok(komet::Notch::McGill),
+            name => Err(format!("Unknown notch method \"{name}\"")),
+        },
+        // Bootstrap parameters (resamples, confidence, seed)
+        Header::Array(Some(3)) => {
+            let resamples = match decoder.pull().unwrap() {
+                Header::Positive(resamples) if resamples > 0 => resamples as usize,
+                Header::Positive(_) => return Err(String::from("resamples must be positive")),
+                _ => return Err(String::from("Bad input")),
+            };
+            let confidence = match decoder.pull().unwrap() {
+                Header::Float(confidence) if confidence > 0. && confidence < 1. => confidence,
+                Header::Float(_) => {
+                    return Err(String::from("confidence must lie between 0 and 1"))
+                }
+                _ => return Err(String::from("Bad input")),
+            };
+            let seed = match decoder.pull().unwrap() {
+                Header::Positive(seed) => seed,
+                _ => return Err(String::from("Bad input")),
+            };
+            Ok(komet::Notch::Bootstrap {
+                resamples,
+                confidence,
+                seed,
+            })
+        }
+        _ => Err(String::from("Bad input")),
+    }
+}
+
+#[wasm_func]
+pub fn describe(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 3 {
+                return Err(String::from("Expected array of 3 elements"));
+            }
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let ddof = match decoder.pull().unwrap() {
+                Header::Float(ddof) => ddof,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let nan_policy = match decoder.pull().unwrap() {
+                Header::Text(Some(len)) => match read::read_string(&mut decoder, len)?.as_str() {
+                    "propagate" => komet::NanPolicy::Propagate,
+                    "omit" => komet::NanPolicy::Omit,
+                    "raise" => {
+                        if values.iter().any(|x| x.is_nan()) {
+                            return Err(String::from("values contain NaN"));
+                        }
+                        komet::NanPolicy::Propagate
+                    }
+                    name => return Err(format!("Unknown NaN policy \"{name}\"")),
+                },
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let d = komet::describe(&values, &komet::DescribeOptions { ddof, nan_policy });
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            // Write the structure
+            encoder.push(Header::Map(Some(15))).unwrap();
+
+            encoder.text("count", None).unwrap();
+            encoder.push(Header::Positive(d.count as u64)).unwrap();
+
+            for (key, value) in [
+                ("sum", d.sum),
+                ("mean", d.mean),
+                ("variance", d.variance),
+                ("std", d.std),
+                ("sem", d.sem),
+                ("skewness", d.skewness),
+                ("kurtosis", d.kurtosis),
+                ("min", d.min),
+                ("max", d.max),
+                ("median", d.median),
+                ("mad", d.mad),
+                ("geometric-mean", d.geometric_mean),
+                ("harmonic-mean", d.harmonic_mean),
+                ("mode", d.mode),
+            ] {
+                encoder.text(key, None).unwrap();
+                encoder.push(Header::Float(value)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+#[wasm_func]
+pub fn ecdf(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 3 {
+                return Err(String::from("Expected array of 3 elements"));
+            }
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let weights = read::read_optional_float_array(&mut decoder)?;
+            if weights.as_ref().is_some_and(|w| w.len() != values.len()) {
+                return Err(String::from(
+                    "values and weights need to have the same length",
+                ));
+            }
+            if let Some(weights) = &weights {
+                check_weights(weights)?;
+            }
+
+            let complementary = read::read_bool(&mut decoder)?;
+
+            let ecdf = komet::ecdf(&values, weights.as_deref(), complementary);
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            // Write the structure
+            encoder.push(Header::Map(Some(2))).unwrap();
+
+            for (key, values) in [("x", ecdf.x), ("y", ecdf.y)] {
+                encoder.text(key, None).unwrap();
+                encoder.push(Header::Array(Some(values.len()))).unwrap();
+                for value in values {
+                    encoder.push(Header::Float(value)).unwrap();
+                }
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+fn read_distribution<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<komet::Distribution, String> {
+    let name = match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => read::read_string(decoder, len)?,
+        _ => return Err(String::from("Bad input")),
+    };
+    let shape = match decoder.pull().unwrap() {
+        Header::Float(shape) => shape,
+        _ => return Err(String::from("Bad input")),
+    };
+
+    Ok(match name.as_str() {
+        "normal" => komet::Distribution::Normal,
+        "exponential" => komet::Distribution::Exponential,
+        "uniform" => komet::Distribution::Uniform,
+        "lognormal" => komet::Distribution::LogNormal(shape),
+        "logistic" => komet::Distribution::Logistic,
+        "laplace" => komet::Distribution::Laplace,
+        "gumbel" => komet::Distribution::Gumbel,
+        "cauchy" => komet::Distribution::Cauchy,
+        "weibull" => komet::Distribution::Weibull(shape),
+        _ => return Err(format!("Unknown distribution \"{name}\"")),
+    })
+}
+
+#[wasm_func]
+pub fn qq(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 5 {
+                return Err(String::from("Expected array of 5 elements"));
+            }
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            if values.iter().all(|x| x.is_nan()) {
+                return Err(String::from("values must contain at least one number"));
+            }
+
+            let distribution = read_distribution(&mut decoder)?;
+
+            let plotting_position = match decoder.pull().unwrap() {
+                Header::Float(plotting_position) if (0. ..1.).contains(&plotting_position) => {
+                    plotting_position
+                }
+                Header::Float(_) => {
+                    return Err(String::from("plotting position must lie in [0, 1)"))
+                }
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let line = match decoder.pull().unwrap() {
+                Header::Text(Some(len)) => match read::read_string(&mut decoder, len)?.as_str() {
+                    "least-squares" => komet::ReferenceLine::LeastSquares,
+                    "quartiles" => komet::ReferenceLine::Quartiles,
+                    name => return Err(format!("Unknown reference line \"{name}\"")),
+                },
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let qq = komet::qq(
+                &values,
+                &komet::QqOptions {
+                    distribution,
+                    plotting_position,
+                    line,
+                },
+            );
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            // Write the structure
+            encoder.push(Header::Map(Some(5))).unwrap();
+
+            for (key, values) in [("theoretical", qq.theoretical), ("sample", qq.sample)] {
+                encoder.text(key, None).unwrap();
+                encoder.push(Header::Array(Some(values.len()))).unwrap();
+                for value in values {
+                    encoder.push(Header::Float(value)).unwrap();
+                }
+            }
+
+            for (key, value) in [
+                ("slope", qq.slope),
+                ("intercept", qq.intercept),
+                ("r", qq.r),
+            ] {
+                encoder.text(key, None).unwrap();
+                encoder.push(Header::Float(value)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+#[wasm_func]
+pub fn quantiles(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 3 {
+                return Err(String::from("Expected array of 3 elements"));
+            }
+
+            let mut values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+            if values.is_empty() {
+                return Err(String::from("values must not be empty"));
+            }
+
+            let percentiles = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let method = read_quantile_method(&mut decoder)?;
+
+            let quantiles = komet::select_quantiles(&mut values, &percentiles, method);
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            encoder.push(Header::Array(Some(quantiles.len()))).unwrap();
+            for quantile in quantiles {
+                encoder.push(Header::Float(quantile)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+fn read_whiskers<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+    whisker_pos: f64,
+) -> Result<komet::Whiskers, String> {
+    match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => match read::read_string(decoder, len)?.as_str() {
+            "tukey" => Ok(komet::Whiskers::Tukey(whisker_pos)),
+            "adjusted" => Ok(komet::Whiskers::Adjusted(whisker_pos)),
+            "std" => Ok(komet::Whiskers::StandardDeviation(whisker_pos)),
+            "range" => Ok(komet::Whiskers::Range),
+            name => Err(format!("Unknown whisker mode \"{name}\"")),
+        },
+        // Lower and upper percentile
+        Header::Array(Some(2)) => match (decoder.pull().unwrap(), decoder.pull().unwrap()) {
+            (Header::Float(low), Header::Float(high)) => {
+                Ok(komet::Whiskers::Percentiles(low, high))
+            }
+            _ => Err(String::from("Bad input")),
+        },
+        _ => Err(String::from("Bad input")),
+    }
+}
+
+fn read_boxplot_options<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<komet::BoxplotOptions, String> {
+    let whisker_pos = match decoder.pull().unwrap() {
+        Header::Float(whisker_pos) => whisker_pos,
+        _ => return Err(String::from("Bad input")),
+    };
+
+    let whiskers = read_whiskers(decoder, whisker_pos)?;
+    let quantile_method = read_quantile_method(decoder)?;
+    let notch = read_notch(decoder)?;
+
+    Ok(komet::BoxplotOptions {
+        whiskers,
+        quantile_method,
+        notch,
+        approximate: false,
+    })
+}
+
+fn write_boxplot_stats<W: ciborium_io::Write>(
+    encoder: &mut Encoder<W>,
+    boxplot_stats: komet::BoxplotStats,
+) where
+    W::Error: std::fmt::Debug,
+{
+    encoder.push(Header::Map(Some(11))).unwrap();
+
+    encoder.text("mean", None).unwrap();
+    encoder.push(Header::Float(boxplot_stats.mean)).unwrap();
+
+    encoder.text("median", None).unwrap();
+    encoder.push(Header::Float(boxplot_stats.median)).unwrap();
+
+    encoder.text("q1", None).unwrap();
+    encoder.push(Header::Float(boxplot_stats.q1)).unwrap();
+
+    encoder.text("q3", None).unwrap();
+    encoder.push(Header::Float(boxplot_stats.q3)).unwrap();
+
+    encoder.text("min", None).unwrap();
+    encoder.push(Header::Float(boxplot_stats.min)).unwrap();
+
+    encoder.text("max", None).unwrap();
+    encoder.push(Header::Float(boxplot_stats.max)).unwrap();
+
+    encoder.text("whisker-low", None).unwrap();
+    encoder
+        .push(Header::Float(boxplot_stats.whisker_low))
+        .unwrap();
+
+    encoder.text("whisker-high", None).unwrap();
+    encoder
+        .push(Header::Float(boxplot_stats.whisker_high))
+        .unwrap();
+
+    encoder.text("notch-low", None).unwrap();
+    encoder
+        .push(Header::Float(boxplot_stats.notch_low))
+        .unwrap();
+
+    encoder.text("notch-high", None).unwrap();
+    encoder
+        .push(Header::Float(boxplot_stats.notch_high))
+        .unwrap();
+
+    encoder.text("outliers", None).unwrap();
+    encoder
+        .push(Header::Array(Some(boxplot_stats.outliers.len())))
+        .unwrap();
+    for outlier in boxplot_stats.outliers {
+        encoder.push(Header::Float(outlier)).unwrap();
+    }
+}
+
+#[wasm_func]
+pub fn boxplot(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 7 {
+                return Err(String::from("Expected array of 7 elements"));
+            }
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let weights = read::read_optional_float_array(&mut decoder)?;
+            if weights.as_ref().is_some_and(|w| w.len() != values.len()) {
+                return Err(String::from(
+                    "values and weights need to have the same length",
+                ));
+            }
+
+            if let Some(weights) = &weights {
+                check_weights(weights)?;
+            }
+
+            let mut options = read_boxplot_options(&mut decoder)?;
+            options.approximate = read::read_bool(&mut decoder)?;
+
+            let boxplot_stats = match weights {
+                Some(weights) => komet::weighted_boxplot(&values, &weights, &options),
+                None => komet::boxplot(&values, &options),
+            };
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+            write_boxplot_stats(&mut encoder, boxplot_stats);
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+/// Computes box plot statistics for many groups, given either as arrays of
+/// values or as flat values with one label per value.
+#[wasm_func]
+pub fn boxplots(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 6 {
+                return Err(String::from("Expected array of 6 elements"));
+            }
+
+            let labels = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => {
+                    let mut labels = Vec::with_capacity(len);
+                    for _ in 0..len {
+                        labels.push(match decoder.pull().unwrap() {
+                            Header::Text(Some(len)) => read::read_string(&mut decoder, len)?,
+                            _ => return Err(String::from("labels need to be strings")),
+                        });
+                    }
+                    Some(labels)
+                }
+                Header::Simple(simple::NULL) => None,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let (keys, groups) = match (decoder.pull().unwrap(), labels) {
+                (Header::Array(Some(len)), None) => {
+                    (None, read::read_float_array_2d(&mut decoder, len)?)
+                }
+                (Header::Array(Some(len)), Some(labels)) => {
+                    let values = read::read_float_array(&mut decoder, len)?;
+                    if values.len() != labels.len() {
+                        return Err(String::from(
+                            "values and labels need to have the same length",
+                        ));
+                    }
+
+                    // Group the values by label in order of first appearance
+                    let mut keys = Vec::<String>::new();
+                    let mut groups = Vec::<Vec<f64>>::new();
+                    for (label, value) in labels.into_iter().zip(values) {
+                        match keys.iter().position(|key| *key == label) {
+                            Some(index) => groups[index].push(value),
+                            None => {
+                                keys.push(label);
+                                groups.push(vec![value]);
+                            }
+                        }
+                    }
+                    (Some(keys), groups)
+                }
+                _ => return Err(String::from("Bad input")),
+            };
+
+            if groups.iter().any(|group| group.is_empty()) {
+                return Err(String::from("groups must not be empty"));
+            }
+
+            let options = read_boxplot_options(&mut decoder)?;
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            // Write the structure
+            match keys {
+                None => {
+                    encoder.push(Header::Array(Some(groups.len()))).unwrap();
+                    for group in groups {
+                        write_boxplot_stats(&mut encoder, komet::boxplot(&group, &options));
+                    }
+                }
+                Some(keys) => {
+                    encoder.push(Header::Map(Some(keys.len()))).unwrap();
+                    for (key, group) in keys.iter().zip(groups) {
+                        encoder.text(key, None).unwrap();
+                        write_boxplot_stats(&mut encoder, komet::boxplot(&group, &options));
+                    }
+                }
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+#[wasm_func]
+pub fn letter_values(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 4 {
+                return Err(String::from("Expected array of 4 elements"));
+            }
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+            if values.is_empty() {
+                return Err(String::from("values must not be empty"));
+            }
+
+            let (outlier_prop, trust_alpha) =
+                match (decoder.pull().unwrap(), decoder.pull().unwrap()) {
+                    (Header::Float(outlier_prop), Header::Float(trust_alpha)) => {
+                        (outlier_prop, trust_alpha)
+                    }
+                    _ => return Err(String::from("Bad input")),
+                };
+
+            let depth = match decoder.pull().unwrap() {
+                Header::Text(Some(len)) => match read::read_string(&mut decoder, len)?.as_str() {
+                    "tukey" => komet::LetterValueDepth::Tukey,
+                    "proportion" => komet::LetterValueDepth::Proportion(outlier_prop),
+                    "trustworthy" => komet::LetterValueDepth::Trustworthy(trust_alpha),
+                    "full" => komet::LetterValueDepth::Full,
+                    name => return Err(format!("Unknown depth rule \"{name}\"")),
+                },
+                Header::Positive(k) => komet::LetterValueDepth::Fixed(k as usize),
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let result = komet::letter_values(&values, depth);
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            // Write the structure
+            encoder.push(Header::Map(Some(2))).unwrap();
+
+            encoder.text("levels", None).unwrap();
+            encoder
+                .push(Header::Array(Some(result.levels.len())))
+                .unwrap();
+            for level in result.levels {
+                encoder.push(Header::Map(Some(3))).unwrap();
+                encoder.text("depth", None).unwrap();
+                encoder.push(Header::Float(level.depth)).unwrap();
+                encoder.text("lower", None).unwrap();
+                encoder.push(Header::Float(level.lower)).unwrap();
+                encoder.text("upper", None).unwrap();
+                encoder.push(Header::Float(level.upper)).unwrap();
+            }
+
+            encoder.text("outliers", None).unwrap();
+            encoder
+                .push(Header::Array(Some(result.outliers.len())))
+                .unwrap();
+            for outlier in result.outliers {
+                encoder.push(Header::Float(outlier)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+#[wasm_func]
+pub fn violins(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 11 {
+                return Err(String::from("Expected array of 11 elements"));
+            }
+
+            let groups = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array_2d(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+            if groups.iter().any(|group| group.is_empty()) {
+                return Err(String::from("groups must not be empty"));
+            }
+
+            let kernel = read_kernel(&mut decoder)?;
+            let bandwidth = read_bandwidth(&mut decoder)?;
+            let cut = match decoder.pull().unwrap() {
+                Header::Float(cut) if cut >= 0. => cut,
+                Header::Float(_) => return Err(String::from("cut must not be negative")),
+                _ => return Err(String::from("Bad input")),
+            };
+            let num_points = match decoder.pull().unwrap() {
+                Header::Positive(num_points) if num_points >= 2 => num_points as usize,
+                _ => return Err(String::from("Expected at least 2 grid points")),
+            };
+            let common_grid = read::read_bool(&mut decoder)?;
+            let scale = match decoder.pull().unwrap() {
+                Header::Text(Some(len)) => match read::read_string(&mut decoder, len)?.as_str() {
+                    "area" => komet::ViolinScale::Area,
+                    "count" => komet::ViolinScale::Count,
+                    "width" => komet::ViolinScale::Width,
+                    name => return Err(format!("Unknown violin scale \"{name}\"")),
+                },
+                _ => return Err(String::from("Bad input")),
+            };
+            let boxplot = read_boxplot_options(&mut decoder)?;
+
+            let groups = groups.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            let violins = komet::violins(
+                &groups,
+                &komet::ViolinOptions {
+                    kernel,
+                    bandwidth,
+                    cut,
+                    num_points,
+                    common_grid,
+                    scale,
+                    boxplot,
+                },
+            );
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            // Write the structure
+            encoder.push(Header::Array(Some(violins.len()))).unwrap();
+            for violin in violins {
+                encoder.push(Header::Map(Some(5))).unwrap();
+
+                encoder.text("grid", None).unwrap();
+                encoder
+                    .push(Header::Array(Some(violin.grid.len())))
+                    .unwrap();
+                for y in violin.grid {
+                    encoder.push(Header::Float(y)).unwrap();
+                }
+
+                encoder.text("width", None).unwrap();
+                encoder
+                    .push(Header::Array(Some(violin.width.len())))
+                    .unwrap();
+                for w in violin.width {
+                    encoder.push(Header::Float(w)).unwrap();
+                }
+
+                encoder.text("outline", None).unwrap();
+                encoder
+                    .push(Header::Array(Some(violin.outline.len())))
+                    .unwrap();
+                for (x, y) in violin.outline {
+                    encoder.push(Header::Array(Some(2))).unwrap();
+                    encoder.push(Header::Float(x)).unwrap();
+                    encoder.push(Header::Float(y)).unwrap();
+                }
+
+                encoder.text("bandwidth", None).unwrap();
+                encoder.push(Header::Float(violin.bandwidth)).unwrap();
+
+                encoder.text("stats", None).unwrap();
+                write_boxplot_stats(&mut encoder, violin.stats);
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+#[wasm_func]
+pub fn boxplot_alt(input: &[u8]) -> Result<Vec<u8>, String> {
+    let values: Vec<f64> = input
+        .chunks_exact(8)
+        .map(|bytes| f64::from_be_bytes(bytes.try_into().expect("msg")))
+        .collect();
+
+    let whisker_pos = values[0];
+
+    let boxplot_stats = komet::boxplot(
+        &values[1..],
+        &komet::BoxplotOptions {
+            whiskers: komet::Whiskers::Tukey(whisker_pos),
+            ..Default::default()
+        },
+    );
+
+    let mut output_values: Vec<f64> = vec![
+        boxplot_stats.mean,
+        boxplot_stats.median,
+        boxplot_stats.min,
+        boxplot_stats.max,
+        boxplot_stats.q1,
+        boxplot_stats.q3,
+        boxplot_stats.whisker_low,
+        boxplot_stats.whisker_high,
+    ];
+    output_values.extend(boxplot_stats.outliers);
+    let p: Vec<[u8; 8]> = output_values
+        .iter()
+        .map(|value| f64::to_be_bytes(*value))
+        .collect();
+
+    Ok(p[..].concat())
+}
+
+fn read_fft_norm<R: std::io::Read>(decoder: &mut Decoder<R>) -> Result<komet::FftNorm, String> {
+    match decoder.pull().unwrap() {
+        Header::Positive(1) => Ok(komet::FftNorm::Backward),
+        Header::Positive(2) => Ok(komet::FftNorm::Ortho),
+        Header::Positive(3) => Ok(komet::FftNorm::Forward),
+        _ => Err(String::from("Bad input")),
+    }
+}
+
+/// Reads a window as its name (or none) followed by its parameter (or none).
+fn read_window<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<Option<komet::Window>, String> {
+    let name = match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => Some(read::read_string(decoder, len)?),
+        Header::Simple(simple::NULL) => None,
+        _ => return Err(String::from("Bad input")),
+    };
+    let parameter = read::read_optional_float(decoder)?;
+    let Some(name) = name else {
+        return Ok(None);
+    };
+    let required = || parameter.ok_or(format!("The window \"{name}\" requires a parameter"));
+
+    Ok(Some(match name.as_str() {
+        "rectangular" => komet::Window::Rectangular,
+        "hann" => komet::Window::Hann,
+        "hamming" => komet::Window::Hamming,
+        "blackman" => komet::Window::Blackman,
+        "blackman-harris" => komet::Window::BlackmanHarris,
+        "flattop" => komet::Window::FlatTop,
+        "bartlett" => komet::Window::Bartlett,
+        "tukey" => match required()? {
+            alpha if (0. ..=1.).contains(&alpha) => komet::Window::Tukey(alpha),
+            _ => {
+                return Err(String::from(
+                    "The parameter of the Tukey window must lie between 0 and 1",
+                ))
+            }
+        },
+        "gaussian" => match required()? {
+            sigma if sigma > 0. => komet::Window::Gaussian(sigma),
+            _ => {
+                return Err(String::from(
+                    "The standard deviation of the Gaussian window must be positive",
+                ))
+            }
+        },
+        "kaiser" => match required()? {
+            beta if beta >= 0. && beta.is_finite() => komet::Window::Kaiser(beta),
+            _ => {
+                return Err(String::from(
+                    "The shape parameter of the Kaiser window must be non-negative and finite",
+                ))
+            }
+        },
+        "dpss" => match required()? {
+            nw if nw > 0. && nw.is_finite() => komet::Window::Dpss(nw),
+            _ => return Err(String::from(
+                "The time-half-bandwidth product of the DPSS window must be positive and finite",
+            )),
+        },
+        _ => return Err(format!("Unknown window \"{name}\"")),
+    }))
+}
+
+/// Reads how a spectrum is returned, i.e., as complex values or as real
+/// values derived from them, followed by the reference power for decibels.
+fn read_spectrum_output<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<(String, komet::DecibelReference), String> {
+    let output = match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => read::read_string(decoder, len)?,
+        _ => return Err(String::from("Bad input")),
+    };
+    if ![
+        "complex",
+        "magnitude",
+        "power",
+        "db",
+        "phase",
+        "unwrapped-phase",
+    ]
+    .contains(&output.as_str())
+    {
+        return Err(format!("Unknown output \"{output}\""));
+    }
+
+    let reference = match decoder.pull().unwrap() {
+        Header::Float(reference) => komet::DecibelReference::Fixed(reference),
+        Header::Text(Some(len)) if read::read_string(decoder, len)? == "max" => {
+            komet::DecibelReference::Max
+        }
+        _ => return Err(String::from("reference is neither a float nor \"max\"")),
+    };
+
+    Ok((output, reference))
+}
+
+fn write_spectrum(
+    values: &[komet::Complex],
+    output: &str,
+    reference: komet::DecibelReference,
+) -> Vec<u8> {
+    let mut buffer = Vec::<u8>::new();
+    let mut encoder = Encoder::from(&mut buffer);
+
+    let real = match output {
+        "magnitude" => komet::magnitude(values),
+        "power" => komet::power(values),
+        "db" => komet::decibels(&komet::power(values), reference),
+        "phase" => komet::angle(values),
+        "unwrapped-phase" => komet::unwrap(&komet::angle(values)),
+        _ => {
+            encoder.push(Header::Array(Some(values.len()))).unwrap();
+            for value in values {
+                encoder.push(Header::Array(Some(2))).unwrap();
+                encoder.push(Header::Float(value.re)).unwrap();
+                encoder.push(Header::Float(value.im)).unwrap();
+            }
+            encoder.flush().unwrap();
+            return buffer;
+        }
+    };
+
+    encoder.push(Header::Array(Some(real.len()))).unwrap();
+    for value in real {
+        encoder.push(Header::Float(value)).unwrap();
+    }
+    encoder.flush().unwrap();
+    buffer
+}
+
+fn fft_impl(input: &[u8], inverse: bool) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    // Only the forward transform takes a window
+    let expected = if inverse { 4 } else { 6 };
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != expected {
+                return Err(format!("Expected array of {expected} elements"));
+            }
+        }
+        _ => return Err(String::from("Expected an array of inputs")),
+    };
+
+    let norm = read_fft_norm(&mut decoder)?;
+
+    let mut values = match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => read::read_complex_array(&mut decoder, len).unwrap(),
+        _ => return Err(String::from("Expected an array of inputs")),
+    };
+
+    // Windows for spectral analysis are periodic
+    let window = if inverse {
+        None
+    } else {
+        read_window(&mut decoder)?
+    };
+    if let Some(window) = window {
+        let weights = komet::window(window, values.len(), true);
+        for (value, weight) in values.iter_mut().zip(weights) {
+            *value = *value * weight;
+        }
+    }
+
+    let (output, reference) = read_spectrum_output(&mut decoder)?;
+
+    if inverse {
+        komet::ifft(&mut values, norm);
+    } else {
+        komet::fft(&mut values, norm);
+    }
+
+    Ok(write_spectrum(&values, &output, reference))
+}
+
+#[wasm_func]
+fn fft(input: &[u8]) -> Result<Vec<u8>, String> {
+    fft_impl(input, false)
+}
+
+#[wasm_func]
+fn ifft(input: &[u8]) -> Result<Vec<u8>, String> {
+    fft_impl(input, true)
+}
+
+#[wasm_func]
+fn window(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 4 {
+                return Err(String::from("Expected array of 4 elements"));
+            }
+
+            let window = read_window(&mut decoder)?.unwrap_or(komet::Window::Rectangular);
+
+            let len = match decoder.pull().unwrap() {
+                Header::Positive(len) => len as usize,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let periodic = read::read_bool(&mut decoder)?;
+
+            let values = komet::window(window, len, periodic);
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            encoder.push(Header::Array(Some(values.len()))).unwrap();
+            for value in values {
+                encoder.push(Header::Float(value)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+/// Computes the frequencies of the bins of a (real-input) DFT for a number
+/// of values and a sample spacing.
+#[wasm_func]
+fn fftfreq(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 3 {
+                return Err(String::from("Expected array of 3 elements"));
+            }
+
+            let n = match decoder.pull().unwrap() {
+                Header::Positive(n) => n as usize,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let spacing = match decoder.pull().unwrap() {
+                Header::Float(spacing) if spacing > 0. => spacing,
+                _ => return Err(String::from("sample spacing must be positive")),
+            };
+
+            let real = read::read_bool(&mut decoder)?;
+
+            let frequencies = if real {
+                komet::rfftfreq(n, spacing)
+            } else {
+                komet::fftfreq(n, spacing)
+            };
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            encoder
+                .push(Header::Array(Some(frequencies.len())))
+                .unwrap();
+            for frequency in frequencies {
+                encoder.push(Header::Float(frequency)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+#[wasm_func]
+fn rfft(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 4 {
+                return Err(String::from("Expected array of 4 elements"));
+            }
+
+            let norm = read_fft_norm(&mut decoder)?;
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let (output, reference) = read_spectrum_output(&mut decoder)?;
+
+            let bins = komet::rfft(&values, norm);
+
+            Ok(write_spectrum(&bins, &output, reference))
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+#[wasm_func]
+fn irfft(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 3 {
+                return Err(String::from("Expected array of 3 elements"));
+            }
+
+            let norm = read_fft_norm(&mut decoder)?;
+
+            let n = read::read_optional_index(&mut decoder)?;
+
+            let bins = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_complex_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            // By default, the output length assumes an even input length
+            let n = match n {
+                Some(n) => n,
+                None if bins.is_empty() => return Err(String::from("Bad input")),
+                None => 2 * (bins.len() - 1),
+            };
+            if n == 0 {
+                return Err(String::from("output length must be positive"));
+            }
+
+            let values = komet::irfft(&bins, n, norm);
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            encoder.push(Header::Array(Some(values.len()))).unwrap();
+            for value in values {
+                encoder.push(Header::Float(value)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+/// Computes the N-dimensional (inverse) DFT of a row-major grid with the
+/// given shape along the given axes (by default all of them). The grid can
+/// be cropped or zero-padded to the given lengths along these axes.
+#[wasm_func]
+fn fftn(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 6 {
+                return Err(String::from("Expected array of 6 elements"));
+            }
+
+            let norm = read_fft_norm(&mut decoder)?;
+
+            let inverse = read::read_bool(&mut decoder)?;
+
+            let shape = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_index_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_complex_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+            if values.len() != shape.iter().product::<usize>() {
+                return Err(String::from(
+                    "number of values does not match the shape of the grid",
+                ));
+            }
+
+            let axes = read::read_optional_index_array(&mut decoder)?
+                .unwrap_or_else(|| (0..shape.len()).collect());
+            if axes.iter().any(|&axis| axis >= shape.len()) {
+                return Err(String::from("axis out of range"));
+            }
+
+            let lengths = read::read_optional_index_array(&mut decoder)?
+                .unwrap_or_else(|| axes.iter().map(|&axis| shape[axis]).collect());
+            if lengths.len() != axes.len() {
+                return Err(String::from(
+                    "lengths and axes need to have the same length",
+                ));
+            }
+
+            let (values, shape) = komet::fftn(&values, &shape, &axes, &lengths, inverse, norm);
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            // Write the structure
+            encoder.push(Header::Map(Some(2))).unwrap();
+
+            encoder.text("shape", None).unwrap();
+            encoder.push(Header::Array(Some(shape.len()))).unwrap();
+            for len in shape {
+                encoder.push(Header::Positive(len as u64)).unwrap();
+            }
+
+            encoder.text("values", None).unwrap();
+            encoder.push(Header::Array(Some(values.len()))).unwrap();
+            for value in values {
+                encoder.push(Header::Array(Some(2))).unwrap();
+                encoder.push(Header::Float(value.re)).unwrap();
+                encoder.push(Header::Float(value.im)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+fn read_detrend<R: std::io::Read>(decoder: &mut Decoder<R>) -> Result<komet::Detrend, String> {
+    match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => match read::read_string(decoder, len)?.as_str() {
+            "none" => Ok(komet::Detrend::None),
+            "constant" => Ok(komet::Detrend::Constant),
+            "linear" => Ok(komet::Detrend::Linear),
+            name => Err(format!("Unknown detrend \"{name}\"")),
+        },
+        _ => Err(String::from("Bad input")),
+    }
+}
+
+/// Estimates the power spectrum with Welch's method. A periodogram is the
+/// special case of a single segment spanning the entire signal.
+#[wasm_func]
+fn welch(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 9 {
+                return Err(String::from("Expected array of 9 elements"));
+            }
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let sample_rate = match decoder.pull().unwrap() {
+                Header::Float(sample_rate) if sample_rate > 0. => sample_rate,
+                _ => return Err(String::from("sample rate must be positive")),
+            };
+
+            let window = read_window(&mut decoder)?.unwrap_or(komet::Window::Rectangular);
+
+            let segment_length = read::read_optional_index(&mut decoder)?;
+            if segment_length == Some(0) {
+                return Err(String::from("segment length must be positive"));
+            }
+
+            let overlap = read::read_optional_index(&mut decoder)?;
+            if let Some(overlap) = overlap {
+                if overlap >= segment_length.unwrap_or(256).min(values.len()).max(1) {
+                    return Err(String::from(
+                        "overlap must be smaller than the segment length",
+                    ));
+                }
+            }
+
+            let detrend = read_detrend(&mut decoder)?;
+
+            let one_sided = read::read_bool(&mut decoder)?;
+
+            let scaling = match decoder.pull().unwrap() {
+                Header::Text(Some(len)) => match read::read_string(&mut decoder, len)?.as_str() {
+                    "density" => komet::PsdScaling::Density,
+                    "spectrum" => komet::PsdScaling::Spectrum,
+                    name => return Err(format!("Unknown scaling \"{name}\"")),
+                },
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let psd = komet::welch(
+                &values,
+                &komet::PsdOptions {
+                    sample_rate,
+                    window,
+                    segment_length,
+                    overlap,
+                    detrend,
+                    one_sided,
+                    scaling,
+                },
+            );
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            // Write the structure
+            encoder.push(Header::Map(Some(2))).unwrap();
+
+            encoder.text("frequencies", None).unwrap();
+            encoder
+                .push(Header::Array(Some(psd.frequencies.len())))
+                .unwrap();
+            for frequency in psd.frequencies {
+                encoder.push(Header::Float(frequency)).unwrap();
+            }
+
+            encoder.text("power", None).unwrap();
+            encoder.push(Header::Array(Some(psd.power.len()))).unwrap();
+            for power in psd.power {
+                encoder.push(Header::Float(power)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+fn read_stft_options<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<komet::StftOptions, String> {
+    let sample_rate = match decoder.pull().unwrap() {
+        Header::Float(sample_rate) if sample_rate > 0. => sample_rate,
+        _ => return Err(String::from("sample rate must be positive")),
+    };
+
+    let window = read_window(decoder)?.unwrap_or(komet::Window::Rectangular);
+
+    let segment_length = match decoder.pull().unwrap() {
+        Header::Positive(len) if len > 0 => len as usize,
+        _ => return Err(String::from("segment length must be positive")),
+    };
+
+    let hop = read::read_optional_index(decoder)?;
+    if hop == Some(0) {
+        return Err(String::from("hop must be positive"));
+    }
+
+    let fft_length = read::read_optional_index(decoder)?;
+    if fft_length.is_some_and(|len| len < segment_length) {
+        return Err(String::from(
+            "FFT length must not be smaller than the segment length",
+        ));
+    }
+
+    let padding = read::read_bool(decoder)?;
+
+    Ok(komet::StftOptions {
+        sample_rate,
+        window,
+        segment_length,
+        hop,
+        fft_length,
+        padding,
+    })
+}
+
+/// Computes the short-time Fourier transform as complex values or the
+/// spectrogram in the given scale on a grid with one row per frequency.
+#[wasm_func]
+fn stft(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 9 {
+                return Err(String::from("Expected array of 9 elements"));
+            }
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let options = read_stft_options(&mut decoder)?;
+
+            let scale = match decoder.pull().unwrap() {
+                Header::Text(Some(len)) => match read::read_string(&mut decoder, len)?.as_str() {
+                    "complex" => None,
+                    "magnitude" => Some(komet::SpectrogramScale::Magnitude),
+                    "power" => Some(komet::SpectrogramScale::Power),
+                    "db" => Some(komet::SpectrogramScale::Decibels),
+                    name => return Err(format!("Unknown output \"{name}\"")),
+                },
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            let write_axes = |encoder: &mut Encoder<_>, times: Vec<f64>, frequencies: Vec<f64>| {
+                encoder.text("times", None).unwrap();
+                encoder.push(Header::Array(Some(times.len()))).unwrap();
+                for time in times {
+                    encoder.push(Header::Float(time)).unwrap();
+                }
+
+                encoder.text("frequencies", None).unwrap();
+                encoder
+                    .push(Header::Array(Some(frequencies.len())))
+                    .unwrap();
+                for frequency in frequencies {
+                    encoder.push(Header::Float(frequency)).unwrap();
+                }
+            };
+
+            // Write the structure
+            encoder.push(Header::Map(Some(3))).unwrap();
+
+            match scale {
+                None => {
+                    let stft = komet::stft(&values, &options);
+                    write_axes(&mut encoder, stft.times, stft.frequencies);
+
+                    encoder.text("values", None).unwrap();
+                    encoder
+                        .push(Header::Array(Some(stft.values.len())))
+                        .unwrap();
+                    for value in stft.values {
+                        encoder.push(Header::Array(Some(2))).unwrap();
+                        encoder.push(Header::Float(value.re)).unwrap();
+                        encoder.push(Header::Float(value.im)).unwrap();
+                    }
+                }
+                Some(scale) => {
+                    let spectrogram = komet::spectrogram(&values, &options, scale);
+                    write_axes(&mut encoder, spectrogram.times, spectrogram.frequencies);
+
+                    encoder.text("values", None).unwrap();
+                    encoder
+                        .push(Header::Array(Some(spectrogram.values.len())))
+                        .unwrap();
+                    for value in spectrogram.values {
+                        encoder.push(Header::Float(value)).unwrap();
+                    }
+                }
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+/// Reconstructs a signal from its short-time Fourier transform given as a
+/// flat grid with one row per frequency.
+#[wasm_func]
+fn istft(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 9 {
+                return Err(String::from("Expected array of 9 elements"));
+            }
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_complex_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let options = read_stft_options(&mut decoder)?;
+
+            let len = read::read_optional_index(&mut decoder)?;
+
+            let bins = options.fft_length.unwrap_or(options.segment_length) / 2 + 1;
+            if !values.len().is_multiple_of(bins) {
+                return Err(String::from(
+                    "number of values does not match the number of frequencies",
+                ));
+            }
+
+            let signal = komet::istft(&values, &options, len);
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            encoder.push(Header::Array(Some(signal.len()))).unwrap();
+            for value in signal {
+                encoder.push(Header::Float(value)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+/// Computes the analytic signal of real values together with the
+/// instantaneous amplitude, phase and frequency.
+#[wasm_func]
+fn hilbert(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
     match decoder.pull().unwrap() {
         Header::Array(Some(len)) => {
             if len != 2 {
-                return Err(String::from("Expected array of 2 elements, got more"));
+                return Err(String::from("Expected array of 2 elements"));
             }
 
             let values = match decoder.pull().unwrap() {
@@ -81,36 +2143,41 @@ pub fn histogram(input: &[u8]) -> Result<Vec<u8>, String> {
                 _ => return Err(String::from("Bad input")),
             };
 
-            let edges = match decoder.pull().unwrap() {
-                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
-                Header::Positive(num_bins) => {
-                    let min = values.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-                    let max = values.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-
-                    let step = (max - min) / (num_bins as f64);
-                    (0..num_bins + 1).map(|x| min + (x as f64) * step).collect()
-                }
-                _ => return Err(String::from("Bad input")),
+            let sample_rate = match decoder.pull().unwrap() {
+                Header::Float(sample_rate) if sample_rate > 0. => sample_rate,
+                _ => return Err(String::from("sample rate must be positive")),
             };
 
-            let counts = komet::histogram(&values, &edges);
+            let result = komet::analytic_signal(&values, sample_rate);
 
             let mut output = Vec::<u8>::new();
             let mut encoder = Encoder::from(&mut output);
+
             // Write the structure
-            encoder.push(Header::Map(Some(2))).unwrap();
+            encoder.push(Header::Map(Some(4))).unwrap();
 
-            encoder.text("counts", None).unwrap();
-            encoder.push(Header::Array(Some(counts.len()))).unwrap();
-            for count in counts {
-                encoder.push(Header::Positive(count)).unwrap();
+            encoder.text("values", None).unwrap();
+            encoder
+                .push(Header::Array(Some(result.values.len())))
+                .unwrap();
+            for value in result.values {
+                encoder.push(Header::Array(Some(2))).unwrap();
+                encoder.push(Header::Float(value.re)).unwrap();
+                encoder.push(Header::Float(value.im)).unwrap();
             }
 
-            encoder.text("edges", None).unwrap();
-            encoder.push(Header::Array(Some(edges.len()))).unwrap();
-            for edge in edges {
-                encoder.push(Header::Float(edge)).unwrap();
+            for (key, series) in [
+                ("envelope", result.envelope),
+                ("phase", result.phase),
+                ("frequency", result.frequency),
+            ] {
+                encoder.text(key, None).unwrap();
+                encoder.push(Header::Array(Some(series.len()))).unwrap();
+                for value in series {
+                    encoder.push(Header::Float(value)).unwrap();
+                }
             }
+
             encoder.flush().unwrap();
             Ok(output)
         }
@@ -118,67 +2185,203 @@ pub fn histogram(input: &[u8]) -> Result<Vec<u8>, String> {
     }
 }
 
+fn read_convolve_mode<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<komet::ConvolveMode, String> {
+    match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => match read::read_string(decoder, len)?.as_str() {
+            "full" => Ok(komet::ConvolveMode::Full),
+            "same" => Ok(komet::ConvolveMode::Same),
+            "valid" => Ok(komet::ConvolveMode::Valid),
+            name => Err(format!("Unknown mode \"{name}\"")),
+        },
+        _ => Err(String::from("Bad input")),
+    }
+}
+
+fn read_convolve_method<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<komet::ConvolveMethod, String> {
+    match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => match read::read_string(decoder, len)?.as_str() {
+            "auto" => Ok(komet::ConvolveMethod::Auto),
+            "direct" => Ok(komet::ConvolveMethod::Direct),
+            "fft" => Ok(komet::ConvolveMethod::Fft),
+            name => Err(format!("Unknown method \"{name}\"")),
+        },
+        _ => Err(String::from("Bad input")),
+    }
+}
+
+/// Computes the convolution or, if requested, the cross-correlation of two
+/// sequences.
 #[wasm_func]
-pub fn boxplot(input: &[u8]) -> Result<Vec<u8>, String> {
+fn convolve(input: &[u8]) -> Result<Vec<u8>, String> {
     let mut decoder = Decoder::from(input);
 
     match decoder.pull().unwrap() {
         Header::Array(Some(len)) => {
-            if len != 2 {
-                return Err(String::from("Expected array of 2 elements, got more"));
+            if len != 5 {
+                return Err(String::from("Expected array of 5 elements"));
             }
 
-            let values = match decoder.pull().unwrap() {
+            let a = match decoder.pull().unwrap() {
                 Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
                 _ => return Err(String::from("Bad input")),
             };
 
-            let whisker_pos = match decoder.pull().unwrap() {
-                Header::Float(whisker_pos) => whisker_pos,
+            let b = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
                 _ => return Err(String::from("Bad input")),
             };
 
-            let boxplot_stats = komet::boxplot(&values, whisker_pos);
+            let mode = read_convolve_mode(&mut decoder)?;
+
+            let method = read_convolve_method(&mut decoder)?;
+
+            let correlate = read::read_bool(&mut decoder)?;
+
+            let values = if correlate {
+                komet::correlate(&a, &b, mode, method)
+            } else {
+                komet::convolve(&a, &b, mode, method)
+            };
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            encoder.push(Header::Array(Some(values.len()))).unwrap();
+            for value in values {
+                encoder.push(Header::Float(value)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+/// Computes the two-dimensional convolution or, if requested, the
+/// cross-correlation of two row-major grids with the given shapes.
+#[wasm_func]
+fn convolve_2d(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 7 {
+                return Err(String::from("Expected array of 7 elements"));
+            }
+
+            let read_grid = |decoder: &mut Decoder<_>| {
+                let values = match decoder.pull().unwrap() {
+                    Header::Array(Some(len)) => read::read_float_array(decoder, len)?,
+                    _ => return Err(String::from("Bad input")),
+                };
+                let shape = match decoder.pull().unwrap() {
+                    Header::Array(Some(2)) => {
+                        let shape = read::read_index_array(decoder, 2)?;
+                        (shape[0], shape[1])
+                    }
+                    _ => return Err(String::from("Expected a shape of two elements")),
+                };
+                if values.len() != shape.0 * shape.1 {
+                    return Err(String::from(
+                        "number of values does not match the shape of the grid",
+                    ));
+                }
+                Ok((values, shape))
+            };
+            let (a, a_shape) = read_grid(&mut decoder)?;
+            let (b, b_shape) = read_grid(&mut decoder)?;
+
+            let mode = read_convolve_mode(&mut decoder)?;
+
+            let method = read_convolve_method(&mut decoder)?;
+
+            let correlate = read::read_bool(&mut decoder)?;
+
+            let (values, shape) = if correlate {
+                komet::correlate_2d(&a, a_shape, &b, b_shape, mode, method)
+            } else {
+                komet::convolve_2d(&a, a_shape, &b, b_shape, mode, method)
+            };
 
             let mut output = Vec::<u8>::new();
             let mut encoder = Encoder::from(&mut output);
+
             // Write the structure
-            encoder.push(Header::Map(Some(9))).unwrap();
+            encoder.push(Header::Map(Some(2))).unwrap();
+
+            encoder.text("shape", None).unwrap();
+            encoder.push(Header::Array(Some(2))).unwrap();
+            encoder.push(Header::Positive(shape.0 as u64)).unwrap();
+            encoder.push(Header::Positive(shape.1 as u64)).unwrap();
+
+            encoder.text("values", None).unwrap();
+            encoder.push(Header::Array(Some(values.len()))).unwrap();
+            for value in values {
+                encoder.push(Header::Float(value)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+#[wasm_func]
+fn cross_correlation(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
 
-            encoder.text("mean", None).unwrap();
-            encoder.push(Header::Float(boxplot_stats.mean)).unwrap();
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 4 {
+                return Err(String::from("Expected array of 4 elements"));
+            }
 
-            encoder.text("median", None).unwrap();
-            encoder.push(Header::Float(boxplot_stats.median)).unwrap();
+            let x = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
 
-            encoder.text("q1", None).unwrap();
-            encoder.push(Header::Float(boxplot_stats.q1)).unwrap();
+            let y = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
 
-            encoder.text("q3", None).unwrap();
-            encoder.push(Header::Float(boxplot_stats.q3)).unwrap();
+            let max_lag = read::read_optional_index(&mut decoder)?;
 
-            encoder.text("min", None).unwrap();
-            encoder.push(Header::Float(boxplot_stats.min)).unwrap();
+            let normalized = read::read_bool(&mut decoder)?;
 
-            encoder.text("max", None).unwrap();
-            encoder.push(Header::Float(boxplot_stats.max)).unwrap();
+            let result = komet::cross_correlation(&x, &y, max_lag, normalized);
 
-            encoder.text("whisker-low", None).unwrap();
-            encoder
-                .push(Header::Float(boxplot_stats.whisker_low))
-                .unwrap();
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            // Write the structure
+            encoder.push(Header::Map(Some(2))).unwrap();
 
-            encoder.text("whisker-high", None).unwrap();
+            encoder.text("lags", None).unwrap();
             encoder
-                .push(Header::Float(boxplot_stats.whisker_high))
+                .push(Header::Array(Some(result.lags.len())))
                 .unwrap();
+            for lag in result.lags {
+                if lag < 0 {
+                    encoder.push(Header::Negative((-lag - 1) as u64)).unwrap();
+                } else {
+                    encoder.push(Header::Positive(lag as u64)).unwrap();
+                }
+            }
 
-            encoder.text("outliers", None).unwrap();
+            encoder.text("values", None).unwrap();
             encoder
-                .push(Header::Array(Some(boxplot_stats.outliers.len())))
+                .push(Header::Array(Some(result.values.len())))
                 .unwrap();
-            for outlier in boxplot_stats.outliers {
-                encoder.push(Header::Float(outlier)).unwrap();
+            for value in result.values {
+                encoder.push(Header::Float(value)).unwrap();
             }
 
             encoder.flush().unwrap();
@@ -188,94 +2391,272 @@ pub fn boxplot(input: &[u8]) -> Result<Vec<u8>, String> {
     }
 }
 
-#[wasm_func]
-pub fn boxplot_alt(input: &[u8]) -> Result<Vec<u8>, String> {
-    let values: Vec<f64> = input
-        .chunks_exact(8)
-        .map(|bytes| f64::from_be_bytes(bytes.try_into().expect("msg")))
-        .collect();
+/// Reads the kind of a filter band, its cutoff frequencies and the sample
+/// rate in whose units they are given.
+fn read_filter_band<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<(komet::FilterBand, f64), String> {
+    let kind = match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => read::read_string(decoder, len)?,
+        _ => return Err(String::from("Bad input")),
+    };
 
-    let whisker_pos = values[0];
+    let cutoffs = match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => read::read_float_array(decoder, len)?,
+        _ => return Err(String::from("Bad input")),
+    };
 
-    let boxplot_stats = komet::boxplot(&values[1..], whisker_pos);
+    let sample_rate = match decoder.pull().unwrap() {
+        Header::Float(sample_rate) if sample_rate > 0. => sample_rate,
+        _ => return Err(String::from("sample rate must be positive")),
+    };
 
-    let mut output_values: Vec<f64> = vec![
-        boxplot_stats.mean,
-        boxplot_stats.median,
-        boxplot_stats.min,
-        boxplot_stats.max,
-        boxplot_stats.q1,
-        boxplot_stats.q3,
-        boxplot_stats.whisker_low,
-        boxplot_stats.whisker_high,
-    ];
-    output_values.extend(boxplot_stats.outliers);
-    let p: Vec<[u8; 8]> = output_values
-        .iter()
-        .map(|value| f64::to_be_bytes(*value))
-        .collect();
+    if cutoffs.iter().any(|&f| !(f > 0. && f < sample_rate / 2.)) {
+        return Err(String::from(
+            "cutoff frequencies must lie between zero and half the sample rate",
+        ));
+    }
 
-    Ok(p[..].concat())
+    let band = match (kind.as_str(), cutoffs.as_slice()) {
+        ("lowpass", &[cutoff]) => komet::FilterBand::Lowpass(cutoff),
+        ("highpass", &[cutoff]) => komet::FilterBand::Highpass(cutoff),
+        ("bandpass", &[low, high]) if low < high => komet::FilterBand::Bandpass(low, high),
+        ("bandstop", &[low, high]) if low < high => komet::FilterBand::Bandstop(low, high),
+        ("lowpass" | "highpass", _) => {
+            return Err(format!("A {kind} filter requires one cutoff frequency"))
+        }
+        ("bandpass" | "bandstop", _) => {
+            return Err(format!(
+                "A {kind} filter requires two ascending cutoff frequencies"
+            ))
+        }
+        _ => return Err(format!("Unknown filter band \"{kind}\"")),
+    };
+
+    Ok((band, sample_rate))
 }
 
-fn fft_impl(input: &[u8], direction: FftDirection) -> Result<Vec<u8>, String> {
+/// Designs a digital IIR filter as second-order sections.
+#[wasm_func]
+fn iir_filter(input: &[u8]) -> Result<Vec<u8>, String> {
     let mut decoder = Decoder::from(input);
 
     match decoder.pull().unwrap() {
         Header::Array(Some(len)) => {
-            assert_eq!(len, 2);
-        }
-        _ => return Err(String::from("Expected two elements")),
-    };
+            if len != 6 {
+                return Err(String::from("Expected array of 6 elements"));
+            }
 
-    let norm_code = match decoder.pull().unwrap() {
-        Header::Positive(code) => code,
-        _ => return Err(String::from("Bad input")),
-    };
+            let name = match decoder.pull().unwrap() {
+                Header::Text(Some(len)) => read::read_string(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+            let parameter = read::read_optional_float(&mut decoder)?;
+            let required = || match parameter {
+                Some(parameter) if parameter > 0. => Ok(parameter),
+                Some(_) => Err(format!("The parameter of \"{name}\" must be positive")),
+                None => Err(format!("The design \"{name}\" requires a parameter")),
+            };
+            let family = match name.as_str() {
+                "butterworth" => komet::IirFamily::Butterworth,
+                "chebyshev1" => komet::IirFamily::ChebyshevI(required()?),
+                "chebyshev2" => komet::IirFamily::ChebyshevII(required()?),
+                "bessel" => komet::IirFamily::Bessel,
+                _ => return Err(format!("Unknown design \"{name}\"")),
+            };
 
-    let mut values = match decoder.pull().unwrap() {
-        Header::Array(Some(len)) => read::read_complex_array(&mut decoder, len).unwrap(),
-        _ => return Err(String::from("Expected an array of inputs")),
-    };
+            let order = match decoder.pull().unwrap() {
+                Header::Positive(order) if order > 0 => order as usize,
+                _ => return Err(String::from("order must be a positive integer")),
+            };
+            if family == komet::IirFamily::Bessel && order > 15 {
+                return Err(String::from("Bessel filters are limited to order 15"));
+            }
 
-    let mut planner = FftPlanner::<f64>::new();
-    let fft = planner.plan_fft(values.len(), direction);
-    fft.process(&mut values);
-    if (norm_code == 1 && direction == FftDirection::Inverse)
-        || (norm_code == 3 && direction == FftDirection::Forward)
-    {
-        let normalization = 1. / (values.len() as f64);
-        for value in values.iter_mut() {
-            *value *= normalization;
+            let (band, sample_rate) = read_filter_band(&mut decoder)?;
+
+            let sos = komet::iir_filter(family, order, band, sample_rate);
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            encoder.push(Header::Array(Some(sos.len()))).unwrap();
+            for section in sos {
+                encoder.push(Header::Array(Some(6))).unwrap();
+                for coefficient in section {
+                    encoder.push(Header::Float(coefficient)).unwrap();
+                }
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
         }
-    } else if norm_code == 2 {
-        let normalization = (1. / (values.len() as f64)).sqrt();
-        for value in values.iter_mut() {
-            *value *= normalization;
+        _ => Err(String::from("Expected an array of inputs")),
+    }
+}
+
+/// Designs a linear-phase FIR filter by the window method.
+#[wasm_func]
+fn fir_filter(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 6 {
+                return Err(String::from("Expected array of 6 elements"));
+            }
+
+            let taps = match decoder.pull().unwrap() {
+                Header::Positive(taps) if taps > 0 => taps as usize,
+                _ => return Err(String::from("number of taps must be a positive integer")),
+            };
+
+            let (band, sample_rate) = read_filter_band(&mut decoder)?;
+            if matches!(
+                band,
+                komet::FilterBand::Highpass(_) | komet::FilterBand::Bandstop(_, _)
+            ) && taps.is_multiple_of(2)
+            {
+                return Err(String::from(
+                    "High-pass and band-stop filters require an odd number of taps",
+                ));
+            }
+
+            let window = read_window(&mut decoder)?.unwrap_or(komet::Window::Rectangular);
+
+            let coefficients = komet::fir_filter(taps, band, window, sample_rate);
+
+            let mut output = Vec::<u8>::new();
+            let mut encoder = Encoder::from(&mut output);
+
+            encoder
+                .push(Header::Array(Some(coefficients.len())))
+                .unwrap();
+            for coefficient in coefficients {
+                encoder.push(Header::Float(coefficient)).unwrap();
+            }
+
+            encoder.flush().unwrap();
+            Ok(output)
         }
+        _ => Err(String::from("Expected an array of inputs")),
     }
+}
+
+/// Reads whether to filter forward and backward and, if so, how to extend
+/// the signal at its ends.
+fn read_zero_phase<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<Option<komet::FilterPadding>, String> {
+    let zero_phase = read::read_bool(decoder)?;
+    let padding = match decoder.pull().unwrap() {
+        Header::Text(Some(len)) => match read::read_string(decoder, len)?.as_str() {
+            "odd" => komet::FilterPadding::Odd,
+            "even" => komet::FilterPadding::Even,
+            "constant" => komet::FilterPadding::Constant,
+            name => return Err(format!("Unknown padding \"{name}\"")),
+        },
+        Header::Simple(simple::NULL) => komet::FilterPadding::None,
+        _ => return Err(String::from("Bad input")),
+    };
+    Ok(zero_phase.then_some(padding))
+}
 
+fn write_float_array(values: Vec<f64>) -> Vec<u8> {
     let mut output = Vec::<u8>::new();
     let mut encoder = Encoder::from(&mut output);
 
     encoder.push(Header::Array(Some(values.len()))).unwrap();
     for value in values {
-        encoder.push(Header::Array(Some(2))).unwrap();
-        encoder.push(Header::Float(value.re)).unwrap();
-        encoder.push(Header::Float(value.im)).unwrap();
+        encoder.push(Header::Float(value)).unwrap();
     }
 
-    Ok(output)
+    encoder.flush().unwrap();
+    output
 }
 
+/// Filters values with a transfer function, optionally forward and backward
+/// for zero phase.
 #[wasm_func]
-fn fft(input: &[u8]) -> Result<Vec<u8>, String> {
-    fft_impl(input, FftDirection::Forward)
+fn lfilter(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 5 {
+                return Err(String::from("Expected array of 5 elements"));
+            }
+
+            let mut read_array = || match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len),
+                _ => Err(String::from("Bad input")),
+            };
+            let b = read_array()?;
+            let a = read_array()?;
+            let values = read_array()?;
+            if b.is_empty() {
+                return Err(String::from("numerator must not be empty"));
+            }
+            if a.first().is_none_or(|&a| a == 0.) {
+                return Err(String::from(
+                    "first coefficient of the denominator must not be zero",
+                ));
+            }
+
+            let result = match read_zero_phase(&mut decoder)? {
+                Some(padding) => komet::filtfilt(&b, &a, &values, padding),
+                None => komet::lfilter(&b, &a, &values),
+            };
+
+            Ok(write_float_array(result))
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
 }
 
+/// Filters values with second-order sections given as a flat array of six
+/// coefficients per section, optionally forward and backward for zero phase.
 #[wasm_func]
-fn ifft(input: &[u8]) -> Result<Vec<u8>, String> {
-    fft_impl(input, FftDirection::Inverse)
+fn sosfilt(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = Decoder::from(input);
+
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => {
+            if len != 4 {
+                return Err(String::from("Expected array of 4 elements"));
+            }
+
+            let coefficients = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+            if !coefficients.len().is_multiple_of(6) {
+                return Err(String::from("each section needs 6 coefficients"));
+            }
+            let sos = coefficients
+                .chunks(6)
+                .map(|section| <[f64; 6]>::try_from(section).unwrap())
+                .collect::<Vec<_>>();
+            if sos.iter().any(|section| section[3] == 0.) {
+                return Err(String::from(
+                    "first coefficient of the denominator must not be zero",
+                ));
+            }
+
+            let values = match decoder.pull().unwrap() {
+                Header::Array(Some(len)) => read::read_float_array(&mut decoder, len)?,
+                _ => return Err(String::from("Bad input")),
+            };
+
+            let result = match read_zero_phase(&mut decoder)? {
+                Some(padding) => komet::sosfiltfilt(&sos, &values, padding),
+                None => komet::sosfilt(&sos, &values),
+            };
+
+            Ok(write_float_array(result))
+        }
+        _ => Err(String::from("Expected an array of inputs")),
+    }
 }
 
 #[wasm_func]
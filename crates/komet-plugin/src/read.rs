@@ -1,5 +1,5 @@
-use ciborium_ll::{Decoder, Header};
-use rustfft::num_complex::Complex64;
+use ciborium_ll::{simple, Decoder, Header};
+use komet::Complex;
 
 pub fn read_float_array<R: std::io::Read>(
     decoder: &mut Decoder<R>,
@@ -15,6 +15,108 @@ pub fn read_float_array<R: std::io::Read>(
     Ok(values)
 }
 
+pub fn read_bool<R: std::io::Read>(decoder: &mut Decoder<R>) -> Result<bool, String> {
+    match decoder.pull().unwrap() {
+        Header::Simple(simple::TRUE) => Ok(true),
+        Header::Simple(simple::FALSE) => Ok(false),
+        _ => Err(String::from("value is not a boolean")),
+    }
+}
+
+pub fn read_optional_float<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<Option<f64>, String> {
+    match decoder.pull().unwrap() {
+        Header::Float(x) => Ok(Some(x)),
+        Header::Simple(simple::NULL) => Ok(None),
+        _ => Err(String::from("value is neither a float nor none")),
+    }
+}
+
+pub fn read_optional_float_array<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<Option<Vec<f64>>, String> {
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => Ok(Some(read_float_array(decoder, len)?)),
+        Header::Simple(simple::NULL) => Ok(None),
+        _ => Err(String::from("value is neither an array nor none")),
+    }
+}
+
+pub fn read_index_array<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+    len: usize,
+) -> Result<Vec<usize>, String> {
+    let mut values = Vec::<usize>::with_capacity(len);
+    for _ in 0..len {
+        match decoder.pull().unwrap() {
+            Header::Positive(x) => values.push(x as usize),
+            _ => return Err(String::from("array element is not a non-negative integer")),
+        }
+    }
+    Ok(values)
+}
+
+pub fn read_optional_index<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<Option<usize>, String> {
+    match decoder.pull().unwrap() {
+        Header::Positive(x) => Ok(Some(x as usize)),
+        Header::Simple(simple::NULL) => Ok(None),
+        _ => Err(String::from(
+            "value is neither a non-negative integer nor none",
+        )),
+    }
+}
+
+pub fn read_optional_index_array<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+) -> Result<Option<Vec<usize>>, String> {
+    match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => Ok(Some(read_index_array(decoder, len)?)),
+        Header::Simple(simple::NULL) => Ok(None),
+        _ => Err(String::from("value is neither an array nor none")),
+    }
+}
+
+pub fn read_string<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+    len: usize,
+) -> Result<String, String> {
+    let mut string = String::with_capacity(len);
+    let mut buffer = [0u8; 64];
+    let mut segments = decoder.text(Some(len));
+    while let Some(mut segment) = segments.pull().unwrap() {
+        while let Some(chunk) = segment.pull(&mut buffer).unwrap() {
+            string.push_str(chunk);
+        }
+    }
+    Ok(string)
+}
+
+/// Reads either an explicit array of bin edges or a number of bins. In the
+/// latter case, the bins are evenly spaced over the range of `values`.
+pub fn read_edges<R: std::io::Read>(
+    decoder: &mut Decoder<R>,
+    values: &[f64],
+) -> Result<Vec<f64>, String> {
+    let edges = match decoder.pull().unwrap() {
+        Header::Array(Some(len)) => read_float_array(decoder, len)?,
+        Header::Positive(num_bins) => {
+            let min = values.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+            let max = values.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+
+            let step = (max - min) / (num_bins as f64);
+            (0..num_bins + 1).map(|x| min + (x as f64) * step).collect()
+        }
+        _ => return Err(String::from("Bad input")),
+    };
+    if edges.len() < 2 {
+        return Err(String::from("at least two bin edges are required"));
+    }
+    Ok(edges)
+}
+
 pub fn read_float_array_2d<R: std::io::Read>(
     decoder: &mut Decoder<R>,
     len: usize,
@@ -36,8 +138,8 @@ pub fn read_float_array_2d<R: std::io::Read>(
 pub fn read_complex_array<R: std::io::Read>(
     decoder: &mut Decoder<R>,
     len: usize,
-) -> Result<Vec<Complex64>, String> {
-    let mut values = vec![Complex64 { re: 0.0, im: 0.0 }; len];
+) -> Result<Vec<Complex>, String> {
+    let mut values = vec![Complex::default(); len];
 
     for value in values.iter_mut() {
         *value = match decoder.pull().unwrap() {
@@ -51,7 +153,7 @@ pub fn read_complex_array<R: std::io::Read>(
                     Header::Float(x) => x,
                     _ => return Err(String::from("Bad input")),
                 };
-                Complex64 { re, im }
+                Complex::new(re, im)
             }
             _ => return Err(String::from("Bad input")),
         };
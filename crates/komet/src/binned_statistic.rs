@@ -0,0 +1,142 @@
+use crate::histogram::{locate, Bin};
+use crate::quantile::percentile;
+
+/// A statistic to compute over the values in a bin.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Statistic {
+    Count,
+    Sum,
+    Mean,
+    Median,
+    /// The population standard deviation.
+    Std,
+    Min,
+    Max,
+    /// The q-th percentile with q in the range 0 to 100.
+    Percentile(f64),
+}
+
+impl Statistic {
+    /// Computes the statistic for the values of a single bin. Empty bins
+    /// yield NaN, except for the count and the sum which are zero.
+    fn compute(self, values: &mut [f64]) -> f64 {
+        let n = values.len() as f64;
+        match self {
+            Statistic::Count => n,
+            Statistic::Sum => values.iter().sum(),
+            _ if values.is_empty() => f64::NAN,
+            Statistic::Mean => values.iter().sum::<f64>() / n,
+            Statistic::Std => {
+                let mean = values.iter().sum::<f64>() / n;
+                (values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n).sqrt()
+            }
+            Statistic::Min => values.iter().fold(f64::INFINITY, |a, &b| a.min(b)),
+            Statistic::Max => values.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b)),
+            Statistic::Median => Statistic::Percentile(50.).compute(values),
+            Statistic::Percentile(q) => {
+                values.sort_by(f64::total_cmp);
+                percentile(values, q)
+            }
+        }
+    }
+}
+
+/// Computes a statistic of `values` for each bin of `x`. The values are
+/// sorted into bins by their corresponding `x` coordinate, following the
+/// same edge semantics as [`histogram`](crate::histogram). Values whose
+/// coordinate lies outside the edges or is NaN are ignored.
+pub fn binned_statistic(
+    x: &[f64],
+    values: &[f64],
+    edges: &[f64],
+    statistic: Statistic,
+) -> Vec<f64> {
+    assert_eq!(x.len(), values.len());
+    let mut bins = vec![Vec::new(); edges.len() - 1];
+
+    for (&x, &value) in x.iter().zip(values) {
+        if let Bin::Index(i) = locate(x, edges, false) {
+            bins[i].push(value);
+        }
+    }
+
+    bins.iter_mut().map(|bin| statistic.compute(bin)).collect()
+}
+
+/// Computes a statistic of `values` for each bin of a two-dimensional grid
+/// spanned by `x_edges` and `y_edges`.
+///
+/// The result is arranged in a flat array in the same layout that
+/// [`contour`](crate::contour) expects for z values, i.e., row by row with
+/// one row per y bin.
+pub fn binned_statistic_2d(
+    x: &[f64],
+    y: &[f64],
+    values: &[f64],
+    x_edges: &[f64],
+    y_edges: &[f64],
+    statistic: Statistic,
+) -> Vec<f64> {
+    assert_eq!(x.len(), values.len());
+    assert_eq!(y.len(), values.len());
+    let cols = x_edges.len() - 1;
+    let mut bins = vec![Vec::new(); cols * (y_edges.len() - 1)];
+
+    for ((&x, &y), &value) in x.iter().zip(y).zip(values) {
+        if let (Bin::Index(i), Bin::Index(j)) =
+            (locate(x, x_edges, false), locate(y, y_edges, false))
+        {
+            bins[j * cols + i].push(value);
+        }
+    }
+
+    bins.iter_mut().map(|bin| statistic.compute(bin)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X: [f64; 7] = [0.5, 1.5, 1.2, 0.1, 2.5, 3.0, 4.0];
+    const VALUES: [f64; 7] = [1., 4., 2., 3., 10., 20., 100.];
+    const EDGES: [f64; 4] = [0., 1., 2., 3.];
+
+    #[test]
+    fn test_statistics() {
+        let stat = |statistic| binned_statistic(&X, &VALUES, &EDGES, statistic);
+        assert_eq!(stat(Statistic::Count), [2., 2., 2.]);
+        assert_eq!(stat(Statistic::Sum), [4., 6., 30.]);
+        assert_eq!(stat(Statistic::Mean), [2., 3., 15.]);
+        assert_eq!(stat(Statistic::Median), [2., 3., 15.]);
+        assert_eq!(stat(Statistic::Std), [1., 1., 5.]);
+        assert_eq!(stat(Statistic::Min), [1., 2., 10.]);
+        assert_eq!(stat(Statistic::Max), [3., 4., 20.]);
+        assert_eq!(stat(Statistic::Percentile(25.)), [1.5, 2.5, 12.5]);
+    }
+
+    #[test]
+    fn test_empty_bins() {
+        let result = binned_statistic(&[0.5], &[1.], &[0., 1., 2.], Statistic::Mean);
+        assert_eq!(result[0], 1.);
+        assert!(result[1].is_nan());
+        assert_eq!(
+            binned_statistic(&[0.5], &[1.], &[0., 1., 2.], Statistic::Count),
+            [1., 0.]
+        );
+    }
+
+    #[test]
+    fn test_2d() {
+        assert_eq!(
+            binned_statistic_2d(
+                &[0.5, 1.5, 1.5, 0.2, 5.],
+                &[0.5, 0.5, 1.5, 0.1, 0.5],
+                &[1., 2., 3., 4., 5.],
+                &[0., 1., 2.],
+                &[0., 1., 2.],
+                Statistic::Sum
+            ),
+            [5., 2., 0., 3.]
+        );
+    }
+}
@@ -1,3 +1,10 @@
+use crate::medcouple::medcouple;
+use crate::quantile::{
+    quantile_sorted, sort_weighted, weighted_quantile_sorted, QuantileMethod, Selection,
+};
+use crate::random::Rng;
+use crate::streaming::P2Quantile;
+
 #[derive(Debug, PartialEq)]
 pub struct BoxplotStats {
     pub mean: f64,
@@ -9,39 +16,212 @@ pub struct BoxplotStats {
     pub outliers: Vec<f64>,
     pub whisker_low: f64,
     pub whisker_high: f64,
+    /// The lower end of the confidence interval of the median.
+    pub notch_low: f64,
+    /// The upper end of the confidence interval of the median.
+    pub notch_high: f64,
 }
 
-pub fn boxplot(values: &[f64], whiskers: f64) -> BoxplotStats {
-    let mut sorted_values = values.to_vec();
-    sorted_values.sort_by(f64::total_cmp);
+/// A method for computing the confidence interval of the median that is
+/// displayed by the notches of a box plot.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Notch {
+    /// The interval `median ± 1.57·IQR/√n` by McGill et al. (1978).
+    McGill,
+    /// A percentile bootstrap interval of the median. Computing the interval
+    /// panics unless `resamples` is positive and `confidence` lies strictly
+    /// between zero and one.
+    Bootstrap {
+        /// The number of bootstrap resamples, at least one.
+        resamples: usize,
+        /// The confidence level in `(0, 1)`, e.g., 0.95.
+        confidence: f64,
+        /// The seed of the random number generator.
+        seed: u64,
+    },
+}
 
-    let mean = values.iter().sum::<f64>() / values.len() as f64;
-    let median = percentile(&sorted_values, 50.);
-    let q1 = percentile(&sorted_values, 25.);
-    let q3 = percentile(&sorted_values, 75.);
+/// A definition of the whisker positions of a box plot. Values beyond the
+/// whiskers are reported as outliers.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Whiskers {
+    /// Tukey's whiskers extend to the most extreme values within the given
+    /// multiple of the interquartile range from the quartiles.
+    Tukey(f64),
+    /// The adjusted box plot for skewed data by Hubert and Vandervieren
+    /// (2008). The fences `q1 - k·exp(-4·MC)·IQR` and `q3 + k·exp(3·MC)·IQR`
+    /// are skewed by the [`medcouple`](crate::medcouple) `MC` (with the
+    /// exponents swapped and negated for negative `MC`) and the whiskers
+    /// extend to the most extreme values within them.
+    Adjusted(f64),
+    /// The whiskers extend to the most extreme values within the given
+    /// multiple of the (population) standard deviation from the mean.
+    StandardDeviation(f64),
+    /// The whiskers are placed at the given lower and upper percentiles
+    /// (between 0 and 100), e.g., 5 and 95.
+    Percentiles(f64, f64),
+    /// The whiskers span the full range of the values, i.e., there are no
+    /// outliers.
+    Range,
+}
 
-    let iqr = q3 - q1;
-    let whisker_low = sorted_values
+/// Options for [`boxplot`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BoxplotOptions {
+    /// The definition of the whiskers.
+    pub whiskers: Whiskers,
+    /// The method for computing the median and the quartiles.
+    pub quantile_method: QuantileMethod,
+    /// The method for computing the confidence interval of the median.
+    pub notch: Notch,
+    /// Whether to estimate the quantiles with the streaming
+    /// [`P2Quantile`](crate::P2Quantile) estimator instead of computing them
+    /// exactly. The values are then never copied, which matters for inputs
+    /// too large to fit into memory twice, at the cost of approximate
+    /// quartiles and whiskers. Adjusted whiskers and bootstrap notches still
+    /// allocate a copy of the values. The estimators skip NaN values. This
+    /// option is ignored for weighted values.
+    pub approximate: bool,
+}
+
+impl Default for BoxplotOptions {
+    fn default() -> Self {
+        BoxplotOptions {
+            whiskers: Whiskers::Tukey(1.5),
+            quantile_method: QuantileMethod::Linear,
+            notch: Notch::McGill,
+            approximate: false,
+        }
+    }
+}
+
+/// Computes the statistics needed to draw a box plot of the given values.
+///
+/// The quantiles are found by selection in expected linear time instead of
+/// sorting the values, see [`select_quantiles`](crate::select_quantiles).
+pub fn boxplot(values: &[f64], options: &BoxplotOptions) -> BoxplotStats {
+    boxplot_impl(values, None, options)
+}
+
+/// Computes the statistics needed to draw a box plot of the given values
+/// with non-negative weights, e.g., sampling weights of survey data.
+///
+/// The mean, the quantiles (see
+/// [`weighted_quantile_sorted`](crate::weighted_quantile_sorted)), and the
+/// standard deviation for [`Whiskers::StandardDeviation`] are weighted.
+/// The McGill notches use the effective sample size `(Σw)²/Σw²` and the
+/// bootstrap notches resample the values together with their weights.
+/// The medcouple for [`Whiskers::Adjusted`] ignores the weights, so the
+/// adjusted fences only account for the skewness of the values themselves.
+/// Values with zero weight are left out entirely, so they neither count as
+/// extremes nor as outliers.
+pub fn weighted_boxplot(values: &[f64], weights: &[f64], options: &BoxplotOptions) -> BoxplotStats {
+    assert_eq!(values.len(), weights.len());
+    let (values, weights): (Vec<f64>, Vec<f64>) =
+        values.iter().zip(weights).filter(|(_, &w)| w != 0.).unzip();
+    boxplot_impl(&values, Some(&weights), options)
+}
+
+fn boxplot_impl(values: &[f64], weights: Option<&[f64]>, options: &BoxplotOptions) -> BoxplotStats {
+    let method = options.quantile_method;
+    let mut probabilities = vec![0.5, 0.25, 0.75];
+    if let Whiskers::Percentiles(low, high) = options.whiskers {
+        probabilities.extend([low / 100., high / 100.]);
+    }
+    let quantiles = match weights {
+        Some(weights) => {
+            let (sorted_values, sorted_weights) = sort_weighted(values, weights);
+            probabilities
+                .iter()
+                .map(|&p| weighted_quantile_sorted(&sorted_values, &sorted_weights, p, method))
+                .collect::<Vec<_>>()
+        }
+        None if options.approximate => {
+            let mut estimators = probabilities
+                .iter()
+                .map(|&p| P2Quantile::new(p))
+                .collect::<Vec<_>>();
+            for &x in values {
+                estimators.iter_mut().for_each(|e| e.push(x));
+            }
+            estimators.iter().map(P2Quantile::estimate).collect()
+        }
+        None => {
+            let mut values = values.to_vec();
+            let mut selection = Selection::new(&mut values);
+            probabilities
+                .iter()
+                .map(|&p| selection.quantile(p, method))
+                .collect()
+        }
+    };
+    let (median, q1, q3) = (quantiles[0], quantiles[1], quantiles[2]);
+
+    let weight = |i: usize| weights.map_or(1., |w| w[i]);
+    let total = (0..values.len()).map(weight).sum::<f64>();
+    let mean = values
+        .iter()
+        .enumerate()
+        .map(|(i, x)| weight(i) * x)
+        .sum::<f64>()
+        / total;
+    // Order like the quantiles, so that NaN values are the largest
+    let min = values
         .iter()
-        .find(|&&x| x >= q1 - iqr * whiskers)
-        .unwrap_or(&q1)
-        .min(q1);
-    let whisker_high = sorted_values
+        .copied()
+        .min_by(f64::total_cmp)
+        .unwrap_or(f64::NAN);
+    let max = values
         .iter()
-        .rev()
-        .find(|&&x| x <= q3 + iqr * whiskers)
-        .unwrap_or(&q3)
-        .max(q3);
+        .copied()
+        .max_by(f64::total_cmp)
+        .unwrap_or(f64::NAN);
+
+    let iqr = q3 - q1;
+    let (whisker_low, whisker_high) = match options.whiskers {
+        Whiskers::Percentiles(..) => (quantiles[3], quantiles[4]),
+        Whiskers::Range => (min, max),
+        fences => {
+            let (lower, upper) = match fences {
+                Whiskers::Tukey(k) => (q1 - iqr * k, q3 + iqr * k),
+                Whiskers::Adjusted(k) => {
+                    let mc = medcouple(values);
+                    let (a, b) = if mc >= 0. { (-4., 3.) } else { (-3., 4.) };
+                    (q1 - iqr * k * (a * mc).exp(), q3 + iqr * k * (b * mc).exp())
+                }
+                Whiskers::StandardDeviation(k) => {
+                    let variance = values
+                        .iter()
+                        .enumerate()
+                        .map(|(i, x)| weight(i) * (x - mean).powi(2))
+                        .sum::<f64>()
+                        / total;
+                    (mean - variance.sqrt() * k, mean + variance.sqrt() * k)
+                }
+                Whiskers::Percentiles(..) | Whiskers::Range => unreachable!(),
+            };
+            let inside = values.iter().filter(|&&x| x >= lower && x <= upper);
+            let (low, high) = inside.fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), &x| {
+                (a.min(x), b.max(x))
+            });
+            (low.min(q1), high.max(q3))
+        }
+    };
+
+    let (notch_low, notch_high) =
+        confidence_interval(values, weights, options.notch, method, median, iqr);
 
     BoxplotStats {
         mean,
         median,
         q1,
         q3,
-        min: sorted_values[0],
-        max: *sorted_values.last().unwrap(),
+        min,
+        max,
         whisker_low,
         whisker_high,
+        notch_low,
+        notch_high,
         outliers: values
             .iter()
             .filter(|&&x| x < whisker_low || x > whisker_high)
@@ -50,36 +230,93 @@ pub fn boxplot(values: &[f64], whiskers: f64) -> BoxplotStats {
     }
 }
 
-/// Computes the value at a rational index into an array by performing linear
-/// interpolation. If the index is an integer, the exact value at the index is
-/// returned.
-fn interpolate(values: &[f64], index: f64) -> f64 {
-    assert!(!values.is_empty());
-    if index < 0. {
-        values[0]
-    } else if index >= values.len() as f64 - 1. {
-        *values.last().unwrap()
-    } else {
-        let lower = index.floor() as usize;
-        let upper = lower + 1;
-        let t = index - (lower as f64);
-
-        values[lower] * (1. - t) + values[upper] * t
-    }
+/// Computes a confidence interval of the median of values that are sorted in
+/// ascending order.
+pub fn median_confidence_interval(
+    sorted_values: &[f64],
+    notch: Notch,
+    method: QuantileMethod,
+) -> (f64, f64) {
+    let median = quantile_sorted(sorted_values, 0.5, method);
+    let iqr =
+        quantile_sorted(sorted_values, 0.75, method) - quantile_sorted(sorted_values, 0.25, method);
+    confidence_interval(sorted_values, None, notch, method, median, iqr)
 }
 
-fn percentile(values: &[f64], q: f64) -> f64 {
-    interpolate(values, q / 100. * (values.len() as f64 - 1.))
+/// Computes a confidence interval of the median of values in any order with
+/// optional weights, given their median and interquartile range.
+fn confidence_interval(
+    values: &[f64],
+    weights: Option<&[f64]>,
+    notch: Notch,
+    method: QuantileMethod,
+    median: f64,
+    iqr: f64,
+) -> (f64, f64) {
+    let n = values.len();
+    match notch {
+        Notch::McGill => {
+            let n_eff = match weights {
+                Some(weights) => {
+                    weights.iter().sum::<f64>().powi(2) / weights.iter().map(|w| w * w).sum::<f64>()
+                }
+                None => n as f64,
+            };
+            let half_width = 1.57 * iqr / n_eff.sqrt();
+            (median - half_width, median + half_width)
+        }
+        Notch::Bootstrap {
+            resamples,
+            confidence,
+            seed,
+        } => {
+            assert!(
+                resamples > 0,
+                "bootstrap notches need at least one resample"
+            );
+            assert!(
+                confidence > 0. && confidence < 1.,
+                "bootstrap confidence needs to lie between 0 and 1"
+            );
+            let mut rng = Rng::new(seed);
+            let mut resample = vec![0.; n];
+            let mut resample_weights = vec![0.; n];
+            let mut medians = (0..resamples)
+                .map(|_| {
+                    for j in 0..n {
+                        let i = rng.below(n);
+                        resample[j] = values[i];
+                        resample_weights[j] = weights.map_or(1., |w| w[i]);
+                    }
+                    match weights {
+                        Some(_) => {
+                            let (sorted_values, sorted_weights) =
+                                sort_weighted(&resample, &resample_weights);
+                            weighted_quantile_sorted(&sorted_values, &sorted_weights, 0.5, method)
+                        }
+                        None => Selection::new(&mut resample).quantile(0.5, method),
+                    }
+                })
+                .collect::<Vec<_>>();
+            medians.sort_by(f64::total_cmp);
+            let alpha = 0.5 * (1. - confidence);
+            (
+                quantile_sorted(&medians, alpha, QuantileMethod::Linear),
+                quantile_sorted(&medians, 1. - alpha, QuantileMethod::Linear),
+            )
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use approx::assert_abs_diff_eq;
 
     #[test]
     fn test_basic() {
         assert_eq!(
-            boxplot(&[1., 2., 3., 4.], 1.5),
+            boxplot(&[1., 2., 3., 4.], &BoxplotOptions::default()),
             BoxplotStats {
                 mean: 2.5,
                 median: 2.5,
@@ -89,12 +326,14 @@ mod tests {
                 max: 4.,
                 whisker_low: 1.,
                 whisker_high: 4.,
+                notch_low: 2.5 - 1.57 * 1.5 / 2.,
+                notch_high: 2.5 + 1.57 * 1.5 / 2.,
                 outliers: vec![],
             }
         );
 
         assert_eq!(
-            boxplot(&[1., 2., 3., 4., -2., 100.], 1.5),
+            boxplot(&[1., 2., 3., 4., -2., 100.], &BoxplotOptions::default()),
             BoxplotStats {
                 mean: 18.0,
                 median: 2.5,
@@ -104,8 +343,212 @@ mod tests {
                 max: 100.,
                 whisker_low: -2.,
                 whisker_high: 4.,
+                notch_low: 2.5 - 1.57 * 2.5 / 6f64.sqrt(),
+                notch_high: 2.5 + 1.57 * 2.5 / 6f64.sqrt(),
                 outliers: vec![100.],
             }
         );
     }
+
+    #[test]
+    fn test_quantile_method() {
+        let options = BoxplotOptions {
+            quantile_method: QuantileMethod::Weibull,
+            ..Default::default()
+        };
+        let stats = boxplot(&[1., 2., 3., 4.], &options);
+        assert_eq!((stats.q1, stats.median, stats.q3), (1.25, 2.5, 3.75));
+    }
+
+    #[test]
+    fn test_whiskers() {
+        let values = [-2., 1., 2., 3., 4., 5., 6., 7., 8., 100.];
+        let stats = |whiskers| {
+            let stats = boxplot(
+                &values,
+                &BoxplotOptions {
+                    whiskers,
+                    ..Default::default()
+                },
+            );
+            (stats.whisker_low, stats.whisker_high, stats.outliers)
+        };
+        assert_eq!(stats(Whiskers::Tukey(1.5)), (-2., 8., vec![100.]));
+        assert_eq!(stats(Whiskers::Tukey(0.5)), (1., 8., vec![-2., 100.]));
+        assert_eq!(stats(Whiskers::Range), (-2., 100., vec![]));
+        let (low, high, outliers) = stats(Whiskers::Percentiles(5., 95.));
+        assert_abs_diff_eq!(low, -2. + 0.45 * 3., epsilon = 1e-12);
+        assert_abs_diff_eq!(high, 8. + 0.55 * 92., epsilon = 1e-12);
+        assert_eq!(outliers, [-2., 100.]);
+        // The mean is 13.4 and the standard deviation about 29.1
+        assert_eq!(
+            stats(Whiskers::StandardDeviation(1.)),
+            (-2., 8., vec![100.])
+        );
+        assert_eq!(stats(Whiskers::StandardDeviation(3.)), (-2., 100., vec![]));
+    }
+
+    #[test]
+    fn test_adjusted_whiskers() {
+        // Right-skewed values get a longer upper and a shorter lower whisker
+        let values = (1..100)
+            .map(|i| -(1. - i as f64 / 100.).ln())
+            .collect::<Vec<_>>();
+        let stats = |whiskers| {
+            boxplot(
+                &values,
+                &BoxplotOptions {
+                    whiskers,
+                    ..Default::default()
+                },
+            )
+        };
+        let tukey = stats(Whiskers::Tukey(1.5));
+        let adjusted = stats(Whiskers::Adjusted(1.5));
+        assert!(!tukey.outliers.is_empty());
+        assert!(adjusted.outliers.is_empty());
+        assert!(adjusted.whisker_high > tukey.whisker_high);
+        assert!(adjusted.whisker_low >= tukey.whisker_low);
+
+        // Without skewness, the adjusted box plot equals Tukey's
+        let symmetric = [1., 2., 3., 4., 5., 6., 7.];
+        let options = |whiskers| BoxplotOptions {
+            whiskers,
+            ..Default::default()
+        };
+        assert_eq!(medcouple(&symmetric), 0.);
+        assert_eq!(
+            boxplot(&symmetric, &options(Whiskers::Adjusted(1.5))),
+            boxplot(&symmetric, &options(Whiskers::Tukey(1.5)))
+        );
+    }
+
+    #[test]
+    fn test_weighted() {
+        let values = [1., 2., 3., 4., -2., 100.];
+        let options = BoxplotOptions::default();
+        assert_eq!(
+            weighted_boxplot(&values, &[1.; 6], &options),
+            boxplot(&values, &options)
+        );
+        assert_eq!(
+            weighted_boxplot(&values, &[3.; 6], &options),
+            boxplot(&values, &options)
+        );
+
+        // A heavily weighted outlier moves the box
+        let stats = weighted_boxplot(&values, &[1., 1., 1., 1., 1., 5.], &options);
+        assert_abs_diff_eq!(stats.mean, (8. + 500.) / 10., epsilon = 1e-12);
+        assert_eq!(stats.q3, 100.);
+        assert_eq!(stats.whisker_high, 100.);
+        assert!(stats.outliers.is_empty());
+
+        let bootstrap = BoxplotOptions {
+            notch: Notch::Bootstrap {
+                resamples: 200,
+                confidence: 0.9,
+                seed: 2,
+            },
+            ..Default::default()
+        };
+        let stats = weighted_boxplot(&values, &[1.; 6], &bootstrap);
+        assert_eq!(stats, boxplot(&values, &bootstrap));
+
+        // Values with zero weight are ignored
+        let stats = weighted_boxplot(
+            &[-50., 1., 2., 3., 4., 100.],
+            &[0., 1., 1., 1., 1., 0.],
+            &options,
+        );
+        assert_eq!(stats, boxplot(&[1., 2., 3., 4.], &options));
+        assert_eq!((stats.min, stats.max), (1., 4.));
+        assert!(stats.outliers.is_empty());
+
+        // Without positive weights, the statistics are undefined
+        let stats = weighted_boxplot(&values, &[0.; 6], &options);
+        assert!(stats.median.is_nan() && stats.q1.is_nan() && stats.mean.is_nan());
+    }
+
+    #[test]
+    fn test_nan() {
+        // NaN values sort after all other values
+        let stats = boxplot(&[3., f64::NAN, 1., 2.], &BoxplotOptions::default());
+        assert_eq!((stats.min, stats.median, stats.q1), (1., 2.5, 1.75));
+        assert!(stats.max.is_nan() && stats.q3.is_nan());
+
+        // The streaming estimators skip NaN values
+        let options = BoxplotOptions {
+            approximate: true,
+            ..Default::default()
+        };
+        let stats = boxplot(&[1., 2., 3., 4., 5., 6., 7., f64::NAN, 9.], &options);
+        assert_eq!(stats.min, 1.);
+        assert!(stats.median.is_finite() && stats.max.is_nan());
+    }
+
+    #[test]
+    fn test_approximate() {
+        let values = (0..10_000)
+            .map(|i| (i * 7919 % 10_000) as f64)
+            .chain([-1e4, 3e4])
+            .collect::<Vec<_>>();
+        let exact = boxplot(&values, &BoxplotOptions::default());
+        let approximate = boxplot(
+            &values,
+            &BoxplotOptions {
+                approximate: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!((approximate.min, approximate.max), (-1e4, 3e4));
+        assert_eq!(approximate.mean, exact.mean);
+        assert_abs_diff_eq!(approximate.median, exact.median, epsilon = 50.);
+        assert_abs_diff_eq!(approximate.q1, exact.q1, epsilon = 50.);
+        assert_abs_diff_eq!(approximate.q3, exact.q3, epsilon = 50.);
+        assert_eq!(approximate.outliers, [-1e4, 3e4]);
+        assert_eq!(approximate.outliers, exact.outliers);
+    }
+
+    #[test]
+    fn test_bootstrap_notch() {
+        let values = (0..101).map(f64::from).collect::<Vec<_>>();
+        let notch = Notch::Bootstrap {
+            resamples: 2000,
+            confidence: 0.95,
+            seed: 1,
+        };
+        let (low, high) = median_confidence_interval(&values, notch, QuantileMethod::Linear);
+        assert!(low < 50. && high > 50.);
+        // The McGill interval is a normal approximation of the same quantity
+        let (mcgill_low, mcgill_high) =
+            median_confidence_interval(&values, Notch::McGill, QuantileMethod::Linear);
+        assert!((low - mcgill_low).abs() < 5. && (high - mcgill_high).abs() < 5.);
+        // The result is reproducible for the same seed
+        assert_eq!(
+            median_confidence_interval(&values, notch, QuantileMethod::Linear),
+            (low, high)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "bootstrap notches need at least one resample")]
+    fn test_bootstrap_without_resamples() {
+        let notch = Notch::Bootstrap {
+            resamples: 0,
+            confidence: 0.95,
+            seed: 0,
+        };
+        median_confidence_interval(&[1., 2., 3.], notch, QuantileMethod::Linear);
+    }
+
+    #[test]
+    #[should_panic(expected = "bootstrap confidence needs to lie between 0 and 1")]
+    fn test_bootstrap_confidence_out_of_range() {
+        let notch = Notch::Bootstrap {
+            resamples: 10,
+            confidence: 95.,
+            seed: 0,
+        };
+        median_confidence_interval(&[1., 2., 3.], notch, QuantileMethod::Linear);
+    }
 }
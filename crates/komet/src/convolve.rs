@@ -0,0 +1,366 @@
+use crate::fft::{circular_convolve, circular_convolve_2d};
+use std::ops::Range;
+
+/// Which part of the full convolution is returned.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ConvolveMode {
+    /// The full convolution of length `N + M - 1`.
+    Full,
+    /// The central part with the same size as the first input.
+    Same,
+    /// The part where the inputs overlap completely, of length
+    /// `max(N, M) - min(N, M) + 1`.
+    Valid,
+}
+
+/// How the convolution is computed.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ConvolveMethod {
+    /// Chooses the faster method based on the sizes of the inputs.
+    Auto,
+    /// Computes the sums directly, which takes `O(N·M)` operations.
+    Direct,
+    /// Multiplies the spectra, which takes `O((N + M)·log(N + M))` operations
+    /// but is subject to rounding errors relative to the largest values.
+    Fft,
+}
+
+/// Returns the indices of the full convolution of inputs with lengths `n`
+/// and `m` that are kept in the given mode.
+fn output_range(n: usize, m: usize, mode: ConvolveMode) -> Range<usize> {
+    match mode {
+        ConvolveMode::Full => 0..n + m - 1,
+        ConvolveMode::Same => {
+            let start = (m - 1) / 2;
+            start..start + n
+        }
+        ConvolveMode::Valid => n.min(m) - 1..n.max(m),
+    }
+}
+
+/// Returns the smallest length of at least `n` whose prime factors are 2, 3,
+/// and 5, for which the FFT is fastest.
+fn fast_length(n: usize) -> usize {
+    (n.max(1)..)
+        .find(|&len| {
+            let mut rest = len;
+            for factor in [2, 3, 5] {
+                while rest.is_multiple_of(factor) {
+                    rest /= factor;
+                }
+            }
+            rest == 1
+        })
+        .unwrap()
+}
+
+/// Decides whether the FFT method is faster based on rough operation counts
+/// for `products` multiplications of the direct method and a transform of
+/// `len` values.
+fn use_fft(method: ConvolveMethod, products: usize, len: usize) -> bool {
+    match method {
+        ConvolveMethod::Auto => products as f64 > 15. * len as f64 * (len as f64).log2(),
+        ConvolveMethod::Direct => false,
+        ConvolveMethod::Fft => true,
+    }
+}
+
+fn full_convolve(a: &[f64], b: &[f64], method: ConvolveMethod) -> Vec<f64> {
+    let len = a.len() + b.len() - 1;
+    let fft_length = fast_length(len);
+    if use_fft(method, a.len() * b.len(), fft_length) {
+        let pad = |values: &[f64]| {
+            let mut padded = values.to_vec();
+            padded.resize(fft_length, 0.);
+            padded
+        };
+        let mut result = circular_convolve(&pad(a), &pad(b));
+        result.truncate(len);
+        result
+    } else {
+        let mut result = vec![0.; len];
+        for (i, x) in a.iter().enumerate() {
+            for (y, z) in b.iter().zip(&mut result[i..]) {
+                *z += x * y;
+            }
+        }
+        result
+    }
+}
+
+/// Computes the discrete linear convolution `(a ∗ b)[k] = Σ a[i]·b[k - i]`
+/// of two sequences, e.g., for smoothing a signal `a` with a kernel `b`.
+pub fn convolve(a: &[f64], b: &[f64], mode: ConvolveMode, method: ConvolveMethod) -> Vec<f64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let full = full_convolve(a, b, method);
+    full[output_range(a.len(), b.len(), mode)].to_vec()
+}
+
+/// Computes the discrete cross-correlation `Σ a[i + k]·b[i]` of two
+/// sequences, i.e., the convolution of `a` with the reversed `b`. In full
+/// mode, the index `k + M - 1` corresponds to the lag `k`.
+pub fn correlate(a: &[f64], b: &[f64], mode: ConvolveMode, method: ConvolveMethod) -> Vec<f64> {
+    let reversed = b.iter().rev().copied().collect::<Vec<_>>();
+    convolve(a, &reversed, mode, method)
+}
+
+/// Computes the two-dimensional linear convolution of two arrays with the
+/// given `(rows, cols)` shapes in row-major order. The modes apply to both
+/// axes.
+///
+/// Returns the convolution in row-major order with its shape.
+pub fn convolve_2d(
+    a: &[f64],
+    a_shape: (usize, usize),
+    b: &[f64],
+    b_shape: (usize, usize),
+    mode: ConvolveMode,
+    method: ConvolveMethod,
+) -> (Vec<f64>, (usize, usize)) {
+    assert_eq!(a.len(), a_shape.0 * a_shape.1);
+    assert_eq!(b.len(), b_shape.0 * b_shape.1);
+    if a.is_empty() || b.is_empty() {
+        return (Vec::new(), (0, 0));
+    }
+
+    let shape = (a_shape.0 + b_shape.0 - 1, a_shape.1 + b_shape.1 - 1);
+    let fft_shape = (fast_length(shape.0), fast_length(shape.1));
+    let mut full = vec![0.; shape.0 * shape.1];
+    if use_fft(method, a.len() * b.len(), fft_shape.0 * fft_shape.1) {
+        let pad = |values: &[f64], cols: usize| {
+            let mut padded = vec![0.; fft_shape.0 * fft_shape.1];
+            for (row, chunk) in values.chunks(cols).enumerate() {
+                padded[row * fft_shape.1..][..cols].copy_from_slice(chunk);
+            }
+            padded
+        };
+        let result = circular_convolve_2d(
+            &pad(a, a_shape.1),
+            &pad(b, b_shape.1),
+            fft_shape.0,
+            fft_shape.1,
+        );
+        for (row, chunk) in full.chunks_mut(shape.1).enumerate() {
+            chunk.copy_from_slice(&result[row * fft_shape.1..][..shape.1]);
+        }
+    } else {
+        for (i, a_row) in a.chunks(a_shape.1).enumerate() {
+            for (k, b_row) in b.chunks(b_shape.1).enumerate() {
+                let row = &mut full[(i + k) * shape.1..][..shape.1];
+                for (j, x) in a_row.iter().enumerate() {
+                    for (y, z) in b_row.iter().zip(&mut row[j..]) {
+                        *z += x * y;
+                    }
+                }
+            }
+        }
+    }
+
+    let rows = output_range(a_shape.0, b_shape.0, mode);
+    let cols = output_range(a_shape.1, b_shape.1, mode);
+    let values = rows
+        .clone()
+        .flat_map(|row| full[row * shape.1..][cols.clone()].to_vec())
+        .collect();
+    (values, (rows.len(), cols.len()))
+}
+
+/// Computes the two-dimensional cross-correlation of two arrays, i.e., the
+/// convolution of `a` with `b` reversed along both axes. See
+/// [`convolve_2d`].
+pub fn correlate_2d(
+    a: &[f64],
+    a_shape: (usize, usize),
+    b: &[f64],
+    b_shape: (usize, usize),
+    mode: ConvolveMode,
+    method: ConvolveMethod,
+) -> (Vec<f64>, (usize, usize)) {
+    let reversed = b.iter().rev().copied().collect::<Vec<_>>();
+    convolve_2d(a, a_shape, &reversed, b_shape, mode, method)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CrossCorrelation {
+    /// The lags in samples in ascending order.
+    pub lags: Vec<i64>,
+    /// The cross-correlation at each lag.
+    pub values: Vec<f64>,
+}
+
+/// Computes the cross-correlation `Σ x[i + k]·y[i]` of two signals for the
+/// lags `k` from `-max_lag` to `max_lag`, by default all lags with an
+/// overlap. A peak at a positive lag indicates that `x` is delayed with
+/// respect to `y`.
+///
+/// If `normalized` is true, the means are subtracted from the signals and
+/// the result is divided by `√(Σ(x - x̄)²·Σ(y - ȳ)²)`, so that the values
+/// range from -1 to 1. This is undefined (NaN) for constant signals.
+pub fn cross_correlation(
+    x: &[f64],
+    y: &[f64],
+    max_lag: Option<usize>,
+    normalized: bool,
+) -> CrossCorrelation {
+    if x.is_empty() || y.is_empty() {
+        return CrossCorrelation {
+            lags: Vec::new(),
+            values: Vec::new(),
+        };
+    }
+
+    let (x, y, norm) = if normalized {
+        let center = |values: &[f64]| {
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            values.iter().map(|v| v - mean).collect::<Vec<_>>()
+        };
+        let (x, y) = (center(x), center(y));
+        let energy = |values: &[f64]| values.iter().map(|v| v * v).sum::<f64>();
+        let norm = (energy(&x) * energy(&y)).sqrt();
+        (x, y, norm)
+    } else {
+        (x.to_vec(), y.to_vec(), 1.)
+    };
+
+    let values = correlate(&x, &y, ConvolveMode::Full, ConvolveMethod::Auto);
+    let first = -(y.len() as i64 - 1);
+    let (lags, values) = values
+        .iter()
+        .enumerate()
+        .map(|(k, value)| (first + k as i64, value / norm))
+        .filter(|(lag, _)| max_lag.is_none_or(|max_lag| lag.unsigned_abs() as usize <= max_lag))
+        .unzip();
+
+    CrossCorrelation { lags, values }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert_abs_diff_eq!(x, y, epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_convolve() {
+        let a = [1., 2., 3., 4., 5.];
+        let b = [1., 0., -1.];
+        for method in [ConvolveMethod::Direct, ConvolveMethod::Fft] {
+            assert_close(
+                &convolve(&a, &b, ConvolveMode::Full, method),
+                &[1., 2., 2., 2., 2., -4., -5.],
+            );
+            assert_close(
+                &convolve(&a, &b, ConvolveMode::Same, method),
+                &[2., 2., 2., 2., -4.],
+            );
+            assert_close(
+                &convolve(&a, &b, ConvolveMode::Valid, method),
+                &[2., 2., 2.],
+            );
+            // The valid part does not depend on the order of the inputs
+            assert_close(
+                &convolve(&b, &a, ConvolveMode::Valid, method),
+                &[2., 2., 2.],
+            );
+            assert_close(
+                &convolve(&[1., 2.], &[1., 1., 1., 1.], ConvolveMode::Same, method),
+                &[3., 3.],
+            );
+        }
+        assert!(convolve(&a, &[], ConvolveMode::Full, ConvolveMethod::Auto).is_empty());
+    }
+
+    #[test]
+    fn test_methods_agree() {
+        let a = (0..1000)
+            .map(|i| (i as f64 * 0.1).sin())
+            .collect::<Vec<_>>();
+        let b = (0..300)
+            .map(|i| (i as f64 * 0.37).cos())
+            .collect::<Vec<_>>();
+        assert!(use_fft(
+            ConvolveMethod::Auto,
+            a.len() * b.len(),
+            fast_length(1299)
+        ));
+        assert!(!use_fft(ConvolveMethod::Auto, 1000 * 5, fast_length(1004)));
+        for mode in [ConvolveMode::Full, ConvolveMode::Same, ConvolveMode::Valid] {
+            assert_close(
+                &convolve(&a, &b, mode, ConvolveMethod::Fft),
+                &convolve(&a, &b, mode, ConvolveMethod::Direct),
+            );
+        }
+        assert_eq!(fast_length(1299), 1350);
+        assert_eq!(fast_length(1), 1);
+    }
+
+    #[test]
+    fn test_correlate() {
+        let a = [1., 2., 3.];
+        let b = [0., 1., 0.5];
+        assert_close(
+            &correlate(&a, &b, ConvolveMode::Full, ConvolveMethod::Direct),
+            &[0.5, 2., 3.5, 3., 0.],
+        );
+        assert_close(
+            &correlate(&a, &b, ConvolveMode::Same, ConvolveMethod::Fft),
+            &[2., 3.5, 3.],
+        );
+    }
+
+    #[test]
+    fn test_convolve_2d() {
+        let a = [1., 2., 3., 4., 5., 6.];
+        let b = [1., 1., 1., 1.];
+        for method in [ConvolveMethod::Direct, ConvolveMethod::Fft] {
+            let (values, shape) = convolve_2d(&a, (2, 3), &b, (2, 2), ConvolveMode::Full, method);
+            assert_eq!(shape, (3, 4));
+            assert_close(
+                &values,
+                &[1., 3., 5., 3., 5., 12., 16., 9., 4., 9., 11., 6.],
+            );
+
+            let (values, shape) = convolve_2d(&a, (2, 3), &b, (2, 2), ConvolveMode::Valid, method);
+            assert_eq!(shape, (1, 2));
+            assert_close(&values, &[12., 16.]);
+
+            let (values, shape) = convolve_2d(&a, (2, 3), &b, (2, 2), ConvolveMode::Same, method);
+            assert_eq!(shape, (2, 3));
+            assert_close(&values, &[1., 3., 5., 5., 12., 16.]);
+
+            let (values, shape) =
+                correlate_2d(&a, (2, 3), &[0., 1.], (1, 2), ConvolveMode::Full, method);
+            assert_eq!(shape, (2, 4));
+            assert_close(&values, &[1., 2., 3., 0., 4., 5., 6., 0.]);
+        }
+    }
+
+    #[test]
+    fn test_cross_correlation() {
+        // x is y delayed by two samples
+        let y = [0., 1., 3., 1., 0., 0., 0., 0.];
+        let x = [0., 0., 0., 1., 3., 1., 0., 0.];
+        let result = cross_correlation(&x, &y, None, false);
+        assert_eq!(result.lags.len(), 15);
+        assert_eq!(result.lags[0], -7);
+        let peak = (0..15)
+            .max_by(|&i, &j| result.values[i].total_cmp(&result.values[j]))
+            .unwrap();
+        assert_eq!(result.lags[peak], 2);
+        assert_abs_diff_eq!(result.values[peak], 11., epsilon = 1e-12);
+
+        let result = cross_correlation(&x, &y, Some(3), true);
+        assert_eq!(result.lags, [-3, -2, -1, 0, 1, 2, 3]);
+        assert!(result.values.iter().all(|v| v.abs() <= 1.));
+        let result = cross_correlation(&y, &y, Some(0), true);
+        assert_abs_diff_eq!(result.values[0], 1., epsilon = 1e-12);
+    }
+}
@@ -0,0 +1,270 @@
+use crate::quantile::percentile;
+
+/// How to treat NaN values in the input.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum NanPolicy {
+    /// Any NaN value makes all statistics NaN.
+    Propagate,
+    /// NaN values are ignored.
+    Omit,
+}
+
+/// Options for [`describe`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DescribeOptions {
+    /// The delta degrees of freedom, i.e., the variance is computed with the
+    /// divisor `n - ddof`.
+    pub ddof: f64,
+    pub nan_policy: NanPolicy,
+}
+
+impl Default for DescribeOptions {
+    fn default() -> Self {
+        DescribeOptions {
+            ddof: 1.,
+            nan_policy: NanPolicy::Propagate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Description {
+    /// The number of values, excluding NaN values if they are omitted.
+    pub count: usize,
+    pub sum: f64,
+    pub mean: f64,
+    pub variance: f64,
+    /// The standard deviation.
+    pub std: f64,
+    /// The standard error of the mean.
+    pub sem: f64,
+    /// The (biased) sample skewness `m₃/m₂^(3/2)`.
+    pub skewness: f64,
+    /// The (biased) sample excess kurtosis `m₄/m₂² - 3`.
+    pub kurtosis: f64,
+    pub min: f64,
+    pub max: f64,
+    pub median: f64,
+    /// The median absolute deviation from the median.
+    pub mad: f64,
+    /// The geometric mean which is NaN if any value is negative.
+    pub geometric_mean: f64,
+    /// The harmonic mean which is NaN if any value is negative.
+    pub harmonic_mean: f64,
+    /// The most frequent value (the smallest one in case of ties).
+    pub mode: f64,
+}
+
+/// A compensated sum by Neumaier's variant of the Kahan summation.
+#[derive(Default)]
+struct KahanSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl KahanSum {
+    fn add(&mut self, x: f64) {
+        let t = self.sum + x;
+        if self.sum.abs() >= x.abs() {
+            self.compensation += (self.sum - t) + x;
+        } else {
+            self.compensation += (x - t) + self.sum;
+        }
+        self.sum = t;
+    }
+
+    fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+}
+
+/// Computes descriptive statistics of the given values.
+///
+/// The central moments are accumulated in a single pass with the update
+/// formulas by Welford and Pébay, and the sums use compensated summation,
+/// so that the results remain accurate for many values.
+pub fn describe(values: &[f64], options: &DescribeOptions) -> Description {
+    let len = values.len();
+    let has_nan = values.iter().any(|x| x.is_nan());
+    let values = values
+        .iter()
+        .copied()
+        .filter(|x| !x.is_nan())
+        .collect::<Vec<_>>();
+    if values.is_empty() || (has_nan && options.nan_policy == NanPolicy::Propagate) {
+        return Description {
+            count: if options.nan_policy == NanPolicy::Propagate {
+                len
+            } else {
+                values.len()
+            },
+            sum: f64::NAN,
+            mean: f64::NAN,
+            variance: f64::NAN,
+            std: f64::NAN,
+            sem: f64::NAN,
+            skewness: f64::NAN,
+            kurtosis: f64::NAN,
+            min: f64::NAN,
+            max: f64::NAN,
+            median: f64::NAN,
+            mad: f64::NAN,
+            geometric_mean: f64::NAN,
+            harmonic_mean: f64::NAN,
+            mode: f64::NAN,
+        };
+    }
+
+    let mut sum = KahanSum::default();
+    let mut log_sum = KahanSum::default();
+    let mut reciprocal_sum = KahanSum::default();
+    let (mut mean, mut m2, mut m3, mut m4) = (0., 0., 0., 0.);
+    for (i, &x) in values.iter().enumerate() {
+        sum.add(x);
+        log_sum.add(x.ln());
+        reciprocal_sum.add(1. / x);
+
+        let n = (i + 1) as f64;
+        let delta = x - mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * (n - 1.);
+        mean += delta_n;
+        m4 += term * delta_n2 * (n * n - 3. * n + 3.) + 6. * delta_n2 * m2 - 4. * delta_n * m3;
+        m3 += term * delta_n * (n - 2.) - 3. * delta_n * m2;
+        m2 += term;
+    }
+
+    let n = values.len() as f64;
+    let variance = m2 / (n - options.ddof);
+    let std = variance.sqrt();
+
+    let mut sorted_values = values;
+    sorted_values.sort_by(f64::total_cmp);
+    let median = percentile(&sorted_values, 50.);
+    let mut deviations = sorted_values
+        .iter()
+        .map(|x| (x - median).abs())
+        .collect::<Vec<_>>();
+    deviations.sort_by(f64::total_cmp);
+
+    // The longest run of equal values in the sorted values
+    let mut mode = (sorted_values[0], 0);
+    let mut start = 0;
+    for i in 1..=sorted_values.len() {
+        if i == sorted_values.len() || sorted_values[i] != sorted_values[start] {
+            if i - start > mode.1 {
+                mode = (sorted_values[start], i - start);
+            }
+            start = i;
+        }
+    }
+
+    // Both means are zero if any value is zero
+    let (geometric_mean, harmonic_mean) = match sorted_values[0] {
+        min if min < 0. => (f64::NAN, f64::NAN),
+        0. => (0., 0.),
+        _ => ((log_sum.value() / n).exp(), n / reciprocal_sum.value()),
+    };
+
+    Description {
+        count: sorted_values.len(),
+        sum: sum.value(),
+        mean,
+        variance,
+        std,
+        sem: std / n.sqrt(),
+        skewness: n.sqrt() * m3 / m2.powf(1.5),
+        kurtosis: n * m4 / (m2 * m2) - 3.,
+        min: sorted_values[0],
+        max: *sorted_values.last().unwrap(),
+        median,
+        mad: percentile(&deviations, 50.),
+        geometric_mean,
+        harmonic_mean,
+        mode: mode.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_describe() {
+        let d = describe(&[2., 8., 4., 1., 4.], &DescribeOptions::default());
+        assert_eq!(d.count, 5);
+        assert_eq!(d.sum, 19.);
+        assert_relative_eq!(d.mean, 3.8);
+        assert_relative_eq!(d.variance, 7.2);
+        assert_relative_eq!(d.std, 7.2f64.sqrt());
+        assert_relative_eq!(d.sem, (7.2f64 / 5.).sqrt());
+        // m2 = 5.76, m3 = 9.264, m4 = 76.6272
+        assert_relative_eq!(d.skewness, 9.264 / 5.76f64.powf(1.5), epsilon = 1e-12);
+        assert_relative_eq!(d.kurtosis, 76.6272 / 5.76 / 5.76 - 3., epsilon = 1e-12);
+        assert_eq!(
+            (d.min, d.max, d.median, d.mad, d.mode),
+            (1., 8., 4., 2., 4.)
+        );
+        assert_relative_eq!(d.geometric_mean, 256f64.powf(0.2), epsilon = 1e-12);
+        assert_relative_eq!(d.harmonic_mean, 5. / 2.125, epsilon = 1e-12);
+
+        let d = describe(
+            &[2., 8., 4., 1., 4.],
+            &DescribeOptions {
+                ddof: 0.,
+                ..Default::default()
+            },
+        );
+        assert_relative_eq!(d.variance, 5.76);
+    }
+
+    #[test]
+    fn test_stability() {
+        // A large offset ruins the naive sum of squares
+        let values = (0..1000).map(|i| 1e9 + (i % 4) as f64).collect::<Vec<_>>();
+        let d = describe(&values, &DescribeOptions::default());
+        assert_relative_eq!(d.mean, 1e9 + 1.5);
+        assert_relative_eq!(d.variance, 1.25 * 1000. / 999., max_relative = 1e-8);
+        assert_relative_eq!(d.skewness, 0., epsilon = 1e-9);
+        assert_relative_eq!(d.kurtosis, 1.64 - 3., epsilon = 1e-9);
+
+        let d = describe(&[1., 1e100, 1., -1e100], &DescribeOptions::default());
+        assert_eq!(d.sum, 2.);
+    }
+
+    #[test]
+    fn test_nan_policy() {
+        let values = [1., f64::NAN, 3.];
+        let d = describe(&values, &DescribeOptions::default());
+        assert!(d.mean.is_nan() && d.median.is_nan());
+        let d = describe(
+            &values,
+            &DescribeOptions {
+                nan_policy: NanPolicy::Omit,
+                ..Default::default()
+            },
+        );
+        assert_eq!((d.count, d.mean, d.mode), (2, 2., 1.));
+        let d = describe(
+            &[f64::NAN, f64::NAN],
+            &DescribeOptions {
+                nan_policy: NanPolicy::Omit,
+                ..Default::default()
+            },
+        );
+        assert_eq!(d.count, 0);
+        assert!(d.mean.is_nan());
+    }
+
+    #[test]
+    fn test_negative_values() {
+        let d = describe(&[-1., 2.], &DescribeOptions::default());
+        assert!(d.geometric_mean.is_nan() && d.harmonic_mean.is_nan());
+        assert_eq!(
+            describe(&[0., 2.], &DescribeOptions::default()).geometric_mean,
+            0.
+        );
+    }
+}
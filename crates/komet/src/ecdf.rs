@@ -0,0 +1,109 @@
+use crate::quantile::sort_weighted;
+
+#[derive(Debug, PartialEq)]
+pub struct Ecdf {
+    /// The distinct values in ascending order at which the step function
+    /// jumps.
+    pub x: Vec<f64>,
+    /// The value of the step function from each jump up to the next one.
+    pub y: Vec<f64>,
+}
+
+/// Computes the empirical cumulative distribution function `F(x)` of the
+/// given values with optional non-negative weights. NaN values are ignored.
+///
+/// The result contains one step per distinct value. In complementary mode,
+/// the survival function `1 - F(x)`, i.e., the proportion of values greater
+/// than `x`, is computed instead.
+///
+/// The weights need to be non-negative and finite with at least one positive
+/// weight, otherwise the result is not a valid distribution function.
+pub fn ecdf(values: &[f64], weights: Option<&[f64]>, complementary: bool) -> Ecdf {
+    let ones;
+    let weights = match weights {
+        Some(weights) => weights,
+        None => {
+            ones = vec![1.; values.len()];
+            &ones
+        }
+    };
+    let (sorted_values, sorted_weights) = sort_weighted(values, weights);
+
+    // The probability mass at each distinct value
+    let mut x = Vec::<f64>::new();
+    let mut mass = Vec::<f64>::new();
+    for (&value, &weight) in sorted_values.iter().zip(&sorted_weights) {
+        if value.is_nan() {
+            continue;
+        } else if x.last() == Some(&value) {
+            *mass.last_mut().unwrap() += weight;
+        } else {
+            x.push(value);
+            mass.push(weight);
+        }
+    }
+
+    let mut cumulative = 0.;
+    let mut y = if complementary {
+        // Accumulating from the top avoids cancellation in the upper tail
+        let mut y = mass
+            .iter()
+            .rev()
+            .map(|m| {
+                let y = cumulative;
+                cumulative += m;
+                y
+            })
+            .collect::<Vec<_>>();
+        y.reverse();
+        y
+    } else {
+        mass.iter()
+            .map(|m| {
+                cumulative += m;
+                cumulative
+            })
+            .collect()
+    };
+    // The total mass is the last cumulative sum
+    y.iter_mut().for_each(|y| *y /= cumulative);
+
+    Ecdf { x, y }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_ecdf() {
+        let result = ecdf(&[3., 1., 2., 2., f64::NAN], None, false);
+        assert_eq!(result.x, [1., 2., 3.]);
+        assert_eq!(result.y, [0.25, 0.75, 1.]);
+
+        let result = ecdf(&[3., 1., 2., 2.], None, true);
+        assert_eq!(result.x, [1., 2., 3.]);
+        assert_eq!(result.y, [0.75, 0.25, 0.]);
+    }
+
+    #[test]
+    fn test_weighted() {
+        let values = [3., 1., 2.];
+        let result = ecdf(&values, Some(&[2., 1., 1.]), false);
+        assert_eq!(result.y, [0.25, 0.5, 1.]);
+        let result = ecdf(&values, Some(&[2., 1., 1.]), true);
+        assert_eq!(result.y, [0.75, 0.5, 0.]);
+        assert_eq!(
+            ecdf(&values, Some(&[4.; 3]), false),
+            ecdf(&values, None, false)
+        );
+    }
+
+    #[test]
+    fn test_upper_tail() {
+        let values = (0..10_000).map(f64::from).collect::<Vec<_>>();
+        let result = ecdf(&values, None, true);
+        assert_abs_diff_eq!(result.y[9998], 1e-4, epsilon = 1e-18);
+    }
+}
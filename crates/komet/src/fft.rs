@@ -0,0 +1,716 @@
+use std::f64::consts::PI;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// A complex number.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    /// Returns the complex number `exp(i·phi)`.
+    pub fn from_angle(phi: f64) -> Complex {
+        Complex::new(phi.cos(), phi.sin())
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Mul<f64> for Complex {
+    type Output = Self;
+
+    fn mul(self, factor: f64) -> Self {
+        Complex::new(self.re * factor, self.im * factor)
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        let denominator = other.norm_sqr();
+        Complex::new(
+            (self.re * other.re + self.im * other.im) / denominator,
+            (self.im * other.re - self.re * other.im) / denominator,
+        )
+    }
+}
+
+impl Complex {
+    /// Returns the complex conjugate.
+    pub fn conj(self) -> Complex {
+        Complex::new(self.re, -self.im)
+    }
+
+    /// Returns the absolute value (modulus).
+    pub fn norm(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Returns the squared absolute value.
+    pub fn norm_sqr(self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Returns the argument (phase angle) in `(-π, π]`.
+    pub fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// Returns the principal square root, whose real part is non-negative.
+    pub fn sqrt(self) -> Complex {
+        let norm = self.norm();
+        Complex::new(
+            ((norm + self.re) / 2.).sqrt(),
+            ((norm - self.re) / 2.).sqrt().copysign(self.im),
+        )
+    }
+
+    /// Returns the product with the imaginary unit `i`.
+    fn mul_i(self) -> Complex {
+        Complex::new(-self.im, self.re)
+    }
+}
+
+/// How the discrete Fourier transform and its inverse are normalized.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FftNorm {
+    /// The inverse transform is scaled by `1/N`.
+    Backward,
+    /// Both transforms are scaled by `1/√N`.
+    Ortho,
+    /// The forward transform is scaled by `1/N`.
+    Forward,
+}
+
+impl FftNorm {
+    /// Returns the factor by which an unnormalized transform of the given
+    /// length is scaled.
+    pub fn factor(self, len: usize, inverse: bool) -> f64 {
+        match (self, inverse) {
+            (FftNorm::Backward, true) | (FftNorm::Forward, false) => 1. / len as f64,
+            (FftNorm::Ortho, _) => 1. / (len as f64).sqrt(),
+            _ => 1.,
+        }
+    }
+}
+
+/// A precomputed plan for unnormalized discrete Fourier transforms of a fixed
+/// length.
+///
+/// Lengths whose prime factors are 2, 3, and 5 are transformed by a
+/// recursive mixed-radix Cooley–Tukey algorithm with radix-2, 3, 4, and 5
+/// butterflies. Other lengths use Bluestein's algorithm which expresses the
+/// transform as a convolution of power-of-two length.
+#[derive(Debug, Clone)]
+pub struct Fft {
+    len: usize,
+    inverse: bool,
+    factors: Vec<usize>,
+    /// The roots of unity `exp(∓2πik/N)` for `k = 0, …, N - 1`.
+    twiddles: Vec<Complex>,
+    bluestein: Option<Box<Bluestein>>,
+}
+
+#[derive(Debug, Clone)]
+struct Bluestein {
+    /// The chirp `exp(±πik²/N)`.
+    chirp: Vec<Complex>,
+    /// The forward transform of the conjugated chirp, padded to the length
+    /// of the inner transforms.
+    kernel: Vec<Complex>,
+    forward: Fft,
+    inverse: Fft,
+}
+
+impl Fft {
+    /// Plans a transform of the given length. The inverse transform uses
+    /// positive exponents.
+    pub fn new(len: usize, inverse: bool) -> Fft {
+        let mut factors = Vec::new();
+        let mut rest = len;
+        for radix in [4, 2, 3, 5] {
+            while rest > 1 && rest.is_multiple_of(radix) {
+                factors.push(radix);
+                rest /= radix;
+            }
+        }
+
+        if rest > 1 {
+            return Fft {
+                len,
+                inverse,
+                factors: Vec::new(),
+                twiddles: Vec::new(),
+                bluestein: Some(Box::new(Bluestein::new(len, inverse))),
+            };
+        }
+
+        let sign = if inverse { 1. } else { -1. };
+        Fft {
+            len,
+            inverse,
+            factors,
+            twiddles: (0..len)
+                .map(|k| Complex::from_angle(sign * 2. * PI * k as f64 / len as f64))
+                .collect(),
+            bluestein: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Computes the unnormalized transform in place.
+    pub fn process(&self, values: &mut [Complex]) {
+        assert_eq!(values.len(), self.len);
+        if self.len <= 1 {
+            return;
+        }
+        if let Some(bluestein) = &self.bluestein {
+            bluestein.process(values);
+            return;
+        }
+        let input = values.to_vec();
+        self.work(values, &input, 0, 1, &self.factors);
+    }
+
+    /// Computes the transform of `input[offset + stride·j]` for
+    /// `j = 0, …, output.len() - 1` into `output` by decimation in time.
+    fn work(
+        &self,
+        output: &mut [Complex],
+        input: &[Complex],
+        offset: usize,
+        stride: usize,
+        factors: &[usize],
+    ) {
+        let radix = factors[0];
+        let m = output.len() / radix;
+        if m == 1 {
+            for (q, value) in output.iter_mut().enumerate() {
+                *value = input[offset + q * stride];
+            }
+        } else {
+            for (q, chunk) in output.chunks_exact_mut(m).enumerate() {
+                self.work(
+                    chunk,
+                    input,
+                    offset + q * stride,
+                    stride * radix,
+                    &factors[1..],
+                );
+            }
+        }
+
+        // Combine the sub-transforms with the twiddle factors
+        let mut scratch = [Complex::default(); 5];
+        for k in 0..m {
+            for q in 0..radix {
+                scratch[q] = output[q * m + k] * self.twiddles[q * k * stride];
+            }
+            let s = &mut scratch[..radix];
+            match radix {
+                2 => butterfly_2(s),
+                3 => butterfly_3(s, self.inverse),
+                4 => butterfly_4(s, self.inverse),
+                _ => butterfly_5(s, self.inverse),
+            }
+            for q in 0..radix {
+                output[q * m + k] = scratch[q];
+            }
+        }
+    }
+}
+
+fn butterfly_2(s: &mut [Complex]) {
+    let (a, b) = (s[0], s[1]);
+    s[0] = a + b;
+    s[1] = a - b;
+}
+
+fn butterfly_3(s: &mut [Complex], inverse: bool) {
+    let sign = if inverse { 1. } else { -1. };
+    let t1 = s[1] + s[2];
+    let t2 = s[0] - t1 * 0.5;
+    let t3 = (s[1] - s[2]).mul_i() * (sign * 0.75f64.sqrt());
+    s[0] = s[0] + t1;
+    s[1] = t2 + t3;
+    s[2] = t2 - t3;
+}
+
+fn butterfly_4(s: &mut [Complex], inverse: bool) {
+    let sign = if inverse { 1. } else { -1. };
+    let a0 = s[0] + s[2];
+    let a1 = s[0] - s[2];
+    let b0 = s[1] + s[3];
+    let b1 = (s[1] - s[3]).mul_i() * sign;
+    s[0] = a0 + b0;
+    s[1] = a1 + b1;
+    s[2] = a0 - b0;
+    s[3] = a1 - b1;
+}
+
+fn butterfly_5(s: &mut [Complex], inverse: bool) {
+    let sign = if inverse { 1. } else { -1. };
+    let (c1, c2) = ((0.4 * PI).cos(), (0.8 * PI).cos());
+    let (s1, s2) = (sign * (0.4 * PI).sin(), sign * (0.8 * PI).sin());
+    let t1 = s[1] + s[4];
+    let t2 = s[2] + s[3];
+    let t3 = s[1] - s[4];
+    let t4 = s[2] - s[3];
+    let a1 = s[0] + t1 * c1 + t2 * c2;
+    let b1 = (t3 * s1 + t4 * s2).mul_i();
+    let a2 = s[0] + t1 * c2 + t2 * c1;
+    let b2 = (t3 * s2 - t4 * s1).mul_i();
+    s[0] = s[0] + t1 + t2;
+    s[1] = a1 + b1;
+    s[2] = a2 + b2;
+    s[3] = a2 - b2;
+    s[4] = a1 - b1;
+}
+
+impl Bluestein {
+    fn new(len: usize, inverse: bool) -> Bluestein {
+        let sign = if inverse { 1. } else { -1. };
+        // Reducing k² modulo 2N keeps the angles accurate for large k
+        let chirp = (0..len as u64)
+            .map(|k| {
+                let k2 = (k * k) % (2 * len as u64);
+                Complex::from_angle(sign * PI * k2 as f64 / len as f64)
+            })
+            .collect::<Vec<_>>();
+
+        let padded = (2 * len - 1).next_power_of_two();
+        let forward = Fft::new(padded, false);
+        let mut kernel = vec![Complex::default(); padded];
+        kernel[0] = chirp[0].conj();
+        for k in 1..len {
+            kernel[k] = chirp[k].conj();
+            kernel[padded - k] = chirp[k].conj();
+        }
+        forward.process(&mut kernel);
+
+        Bluestein {
+            chirp,
+            kernel,
+            forward,
+            inverse: Fft::new(padded, true),
+        }
+    }
+
+    fn process(&self, values: &mut [Complex]) {
+        let padded = self.kernel.len();
+        let mut buffer = vec![Complex::default(); padded];
+        for ((b, &x), &c) in buffer.iter_mut().zip(values.iter()).zip(&self.chirp) {
+            *b = x * c;
+        }
+        self.forward.process(&mut buffer);
+        for (b, &k) in buffer.iter_mut().zip(&self.kernel) {
+            *b = *b * k;
+        }
+        self.inverse.process(&mut buffer);
+        for ((x, &b), &c) in values.iter_mut().zip(&buffer).zip(&self.chirp) {
+            *x = b * c * (1. / padded as f64);
+        }
+    }
+}
+
+/// Computes the discrete Fourier transform of complex values in place.
+pub fn fft(values: &mut [Complex], norm: FftNorm) {
+    transform(values, false, norm);
+}
+
+/// Computes the inverse discrete Fourier transform of complex values in
+/// place.
+pub fn ifft(values: &mut [Complex], norm: FftNorm) {
+    transform(values, true, norm);
+}
+
+fn transform(values: &mut [Complex], inverse: bool, norm: FftNorm) {
+    Fft::new(values.len(), inverse).process(values);
+    scale(values, norm.factor(values.len(), inverse));
+}
+
+fn scale(values: &mut [Complex], factor: f64) {
+    if factor != 1. {
+        values.iter_mut().for_each(|x| *x = *x * factor);
+    }
+}
+
+/// Computes the discrete Fourier transform of real values. Since the
+/// spectrum is Hermitian-symmetric, only the `N/2 + 1` bins of the
+/// non-negative frequencies are returned.
+///
+/// For an even length, the values are packed into a complex sequence of half
+/// the length whose transform is then split into the transforms of the even
+/// and odd values.
+pub fn rfft(values: &[f64], norm: FftNorm) -> Vec<Complex> {
+    let n = values.len();
+    let mut bins = if !n.is_multiple_of(2) || n < 2 {
+        let mut buffer = values
+            .iter()
+            .map(|&x| Complex::new(x, 0.))
+            .collect::<Vec<_>>();
+        Fft::new(n, false).process(&mut buffer);
+        buffer.truncate(n / 2 + 1);
+        buffer
+    } else {
+        let m = n / 2;
+        let mut z = values
+            .chunks_exact(2)
+            .map(|pair| Complex::new(pair[0], pair[1]))
+            .collect::<Vec<_>>();
+        Fft::new(m, false).process(&mut z);
+        (0..=m)
+            .map(|k| {
+                let a = z[k % m];
+                let b = z[(m - k) % m].conj();
+                let even = (a + b) * 0.5;
+                let odd = (b - a).mul_i() * 0.5;
+                even + Complex::from_angle(-PI * k as f64 / m as f64) * odd
+            })
+            .collect()
+    };
+    scale(&mut bins, norm.factor(n, false));
+    bins
+}
+
+/// Computes the `n` real values whose discrete Fourier transform has the
+/// given bins of the non-negative frequencies, i.e., the inverse of
+/// [`rfft`]. Missing bins are treated as zero, superfluous bins are ignored,
+/// and the imaginary parts of the zero and Nyquist frequency bins are
+/// discarded.
+pub fn irfft(bins: &[Complex], n: usize, norm: FftNorm) -> Vec<f64> {
+    let m = n / 2;
+    let mut half = (0..=m)
+        .map(|k| bins.get(k).copied().unwrap_or_default())
+        .collect::<Vec<_>>();
+    half[0].im = 0.;
+    if n.is_multiple_of(2) {
+        half[m].im = 0.;
+    }
+
+    let mut values = if !n.is_multiple_of(2) || n < 2 {
+        let mut buffer = (0..n)
+            .map(|k| if k <= m { half[k] } else { half[n - k].conj() })
+            .collect::<Vec<_>>();
+        Fft::new(n, true).process(&mut buffer);
+        buffer.into_iter().map(|x| x.re).collect::<Vec<_>>()
+    } else {
+        let mut z = (0..m)
+            .map(|k| {
+                let a = half[k];
+                let b = half[m - k].conj();
+                (a + b) + ((a - b) * Complex::from_angle(PI * k as f64 / m as f64)).mul_i()
+            })
+            .collect::<Vec<_>>();
+        Fft::new(m, true).process(&mut z);
+        z.into_iter().flat_map(|x| [x.re, x.im]).collect()
+    };
+    let factor = norm.factor(n, true);
+    values.iter_mut().for_each(|x| *x *= factor);
+    values
+}
+
+/// Computes the multi-dimensional (inverse) discrete Fourier transform of a
+/// grid with the given shape in row-major order along the given axes.
+///
+/// Before the transform along an axis, the grid is cropped or zero-padded
+/// to the respective entry of `lengths`. Returns the transformed grid and
+/// its shape. The normalization is based on the product of the transformed
+/// lengths.
+pub fn fftn(
+    values: &[Complex],
+    shape: &[usize],
+    axes: &[usize],
+    lengths: &[usize],
+    inverse: bool,
+    norm: FftNorm,
+) -> (Vec<Complex>, Vec<usize>) {
+    assert_eq!(values.len(), shape.iter().product::<usize>());
+    assert_eq!(axes.len(), lengths.len());
+    let mut values = values.to_vec();
+    let mut shape = shape.to_vec();
+    for (&axis, &len) in axes.iter().zip(lengths) {
+        if shape[axis] != len {
+            (values, shape) = resize_axis(&values, &shape, axis, len);
+        }
+        fft_axis(&mut values, &shape, axis, inverse);
+    }
+    let len = axes.iter().map(|&axis| shape[axis]).product::<usize>();
+    scale(&mut values, norm.factor(len, inverse));
+    (values, shape)
+}
+
+/// Crops or zero-pads a row-major grid along one axis to the given length.
+fn resize_axis(
+    values: &[Complex],
+    shape: &[usize],
+    axis: usize,
+    len: usize,
+) -> (Vec<Complex>, Vec<usize>) {
+    let inner = shape[axis + 1..].iter().product::<usize>();
+    let outer = shape[..axis].iter().product::<usize>();
+    let old_len = shape[axis];
+    let mut resized = vec![Complex::default(); outer * len * inner];
+    for i in 0..outer {
+        for j in 0..old_len.min(len) {
+            let source = (i * old_len + j) * inner;
+            let target = (i * len + j) * inner;
+            resized[target..target + inner].copy_from_slice(&values[source..source + inner]);
+        }
+    }
+    let mut shape = shape.to_vec();
+    shape[axis] = len;
+    (resized, shape)
+}
+
+/// Computes the unnormalized transform of a row-major grid along one axis in
+/// place.
+fn fft_axis(values: &mut [Complex], shape: &[usize], axis: usize, inverse: bool) {
+    let inner = shape[axis + 1..].iter().product::<usize>();
+    let len = shape[axis];
+    if len == 0 || inner == 0 {
+        return;
+    }
+    let fft = Fft::new(len, inverse);
+    let mut buffer = vec![Complex::default(); len];
+    for block in values.chunks_exact_mut(len * inner) {
+        for offset in 0..inner {
+            for (k, value) in buffer.iter_mut().enumerate() {
+                *value = block[k * inner + offset];
+            }
+            fft.process(&mut buffer);
+            for (k, value) in buffer.iter().enumerate() {
+                block[k * inner + offset] = *value;
+            }
+        }
+    }
+}
+
+/// Computes the circular convolution of two real sequences of the same
+/// length via FFT.
+pub(crate) fn circular_convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    assert_eq!(a.len(), b.len());
+    let n = a.len();
+    let mut a = a.iter().map(|&x| Complex::new(x, 0.)).collect::<Vec<_>>();
+    let mut b = b.iter().map(|&x| Complex::new(x, 0.)).collect::<Vec<_>>();
+    let forward = Fft::new(n, false);
+    forward.process(&mut a);
+    forward.process(&mut b);
+    for (x, &y) in a.iter_mut().zip(b.iter()) {
+        *x = *x * y;
+    }
+    Fft::new(n, true).process(&mut a);
+    a.iter().map(|x| x.re / n as f64).collect()
+}
+
+/// Computes the two-dimensional circular convolution of two real arrays with
+/// `rows × cols` elements in row-major order via FFT.
+pub(crate) fn circular_convolve_2d(a: &[f64], b: &[f64], rows: usize, cols: usize) -> Vec<f64> {
+    assert_eq!(a.len(), rows * cols);
+    assert_eq!(b.len(), rows * cols);
+
+    let transform = |values: &[f64], inverse: bool| {
+        let mut values = values
+            .iter()
+            .map(|&x| Complex::new(x, 0.))
+            .collect::<Vec<_>>();
+        fft_2d(&mut values, rows, cols, inverse);
+        values
+    };
+    let mut a = transform(a, false);
+    let b = transform(b, false);
+    for (x, &y) in a.iter_mut().zip(b.iter()) {
+        *x = *x * y;
+    }
+    fft_2d(&mut a, rows, cols, true);
+    a.iter().map(|x| x.re / (rows * cols) as f64).collect()
+}
+
+/// Computes an unnormalized two-dimensional discrete Fourier transform of
+/// `rows × cols` values in row-major order in place.
+fn fft_2d(values: &mut [Complex], rows: usize, cols: usize, inverse: bool) {
+    fft_axis(values, &[rows, cols], 1, inverse);
+    fft_axis(values, &[rows, cols], 0, inverse);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn naive_dft(values: &[Complex], inverse: bool) -> Vec<Complex> {
+        let n = values.len();
+        let sign = if inverse { 1. } else { -1. };
+        (0..n)
+            .map(|k| {
+                values
+                    .iter()
+                    .enumerate()
+                    .fold(Complex::default(), |sum, (j, &x)| {
+                        let angle = sign * 2. * PI * ((j * k) % n) as f64 / n as f64;
+                        sum + x * Complex::from_angle(angle)
+                    })
+            })
+            .collect()
+    }
+
+    fn test_values(n: usize) -> Vec<Complex> {
+        (0..n)
+            .map(|i| Complex::new((i * 7 % 11) as f64 - 5., (i * 3 % 5) as f64 * 0.5))
+            .collect()
+    }
+
+    fn assert_close(a: &[Complex], b: &[Complex], epsilon: f64) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert_abs_diff_eq!(x.re, y.re, epsilon = epsilon);
+            assert_abs_diff_eq!(x.im, y.im, epsilon = epsilon);
+        }
+    }
+
+    #[test]
+    fn test_fft() {
+        let mut values = [1., 2., 3., 4.].map(|x| Complex::new(x, 0.));
+        fft(&mut values, FftNorm::Backward);
+        let expected = [(10., 0.), (-2., 2.), (-2., 0.), (-2., -2.)];
+        for (value, (re, im)) in values.iter().zip(expected) {
+            assert_abs_diff_eq!(value.re, re, epsilon = 1e-12);
+            assert_abs_diff_eq!(value.im, im, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_lengths() {
+        // Mixed radix, Bluestein for primes, and mixed factorizations
+        let lengths = (0..=40).chain([45, 60, 64, 97, 100, 125, 128, 210, 243, 1000]);
+        for n in lengths {
+            let values = test_values(n);
+            for inverse in [false, true] {
+                let mut result = values.clone();
+                Fft::new(n, inverse).process(&mut result);
+                assert_close(&result, &naive_dft(&values, inverse), 1e-9 * n as f64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_norm() {
+        let values = test_values(12);
+        for norm in [FftNorm::Backward, FftNorm::Ortho, FftNorm::Forward] {
+            let mut result = values.clone();
+            fft(&mut result, norm);
+            ifft(&mut result, norm);
+            assert_close(&result, &values, 1e-12);
+        }
+        let mut result = values.clone();
+        fft(&mut result, FftNorm::Ortho);
+        let energy = |v: &[Complex]| v.iter().map(|x| x.re * x.re + x.im * x.im).sum::<f64>();
+        assert_abs_diff_eq!(energy(&result), energy(&values), epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_rfft() {
+        for n in [1, 2, 3, 4, 7, 10, 16, 21] {
+            let values = test_values(n).iter().map(|x| x.re).collect::<Vec<_>>();
+            let complex = values
+                .iter()
+                .map(|&x| Complex::new(x, 0.))
+                .collect::<Vec<_>>();
+            let bins = rfft(&values, FftNorm::Backward);
+            assert_close(&bins, &naive_dft(&complex, false)[..n / 2 + 1], 1e-10);
+
+            let result = irfft(&bins, n, FftNorm::Backward);
+            for (x, y) in result.iter().zip(&values) {
+                assert_abs_diff_eq!(x, y, epsilon = 1e-12);
+            }
+        }
+        let result = irfft(
+            &[Complex::new(4., 0.), Complex::new(1., -1.)],
+            4,
+            FftNorm::Backward,
+        );
+        assert_eq!(result, [1.5, 1.5, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_fftn() {
+        let values = [1., 2., 3., 4., 5., 6.].map(|x| Complex::new(x, 0.));
+        let (result, shape) = fftn(&values, &[2, 3], &[0, 1], &[2, 3], false, FftNorm::Backward);
+        assert_eq!(shape, [2, 3]);
+        assert_abs_diff_eq!(result[0].re, 21., epsilon = 1e-12);
+        assert_abs_diff_eq!(result[3].re, -9., epsilon = 1e-12);
+        assert_abs_diff_eq!(result[1].im, 3f64.sqrt(), epsilon = 1e-12);
+
+        let (back, _) = fftn(&result, &shape, &[0, 1], &[2, 3], true, FftNorm::Backward);
+        assert_close(&back, &values, 1e-12);
+
+        // Zero-padding of the last axis
+        let (result, shape) = fftn(&values, &[2, 3], &[1], &[4], false, FftNorm::Backward);
+        assert_eq!(shape, [2, 4]);
+        assert_close(
+            &result[..4],
+            &[(6., 0.), (-2., -2.), (2., 0.), (-2., 2.)].map(|(re, im)| Complex::new(re, im)),
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn test_circular_convolve_2d() {
+        let a = [1., 2., 0., 0., 0., 0., 0., 0.];
+        let b = [1., 0., 0., 0., 1., 0., 0., 0.];
+        let result = circular_convolve_2d(&a, &b, 2, 4);
+        for (value, expected) in result.iter().zip([1., 2., 0., 0., 1., 2., 0., 0.]) {
+            assert_abs_diff_eq!(*value, expected, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_circular_convolve() {
+        let result = circular_convolve(&[1., 2., 0., 0.], &[1., 0., 0., 1.]);
+        for (value, expected) in result.iter().zip([3., 2., 0., 1.]) {
+            assert_abs_diff_eq!(*value, expected, epsilon = 1e-12);
+        }
+        let result = circular_convolve(&[1., 2., 0.], &[1., 0., 1.]);
+        for (value, expected) in result.iter().zip([3., 2., 1.]) {
+            assert_abs_diff_eq!(*value, expected, epsilon = 1e-12);
+        }
+    }
+}
@@ -0,0 +1,911 @@
+use crate::fft::Complex;
+use crate::window::{window, Window};
+use std::f64::consts::PI;
+
+/// The band of frequencies passed by a filter. The cutoff frequencies are
+/// given in the units of the sample rate and lie between zero and the
+/// Nyquist frequency, i.e., half the sample rate.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FilterBand {
+    Lowpass(f64),
+    Highpass(f64),
+    /// Passes the frequencies between the lower and the upper cutoff.
+    Bandpass(f64, f64),
+    /// Blocks the frequencies between the lower and the upper cutoff.
+    Bandstop(f64, f64),
+}
+
+/// The family of an IIR filter, which determines the shape of its frequency
+/// response.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum IirFamily {
+    /// A maximally flat passband. The gain at the cutoff is `-3 dB`.
+    Butterworth,
+    /// A steeper roll-off at the cost of a passband with the given ripple in
+    /// decibels. The gain at the cutoff is the negative ripple.
+    ChebyshevI(f64),
+    /// A steeper roll-off at the cost of a stopband with the given
+    /// attenuation in decibels. The cutoff is the edge of the stopband.
+    ChebyshevII(f64),
+    /// A maximally flat group delay, i.e., the least distortion of the
+    /// waveform in the passband. The phase response is normalized such that
+    /// it matches a Butterworth filter at high frequencies. The poles lose
+    /// accuracy for orders above 15.
+    Bessel,
+}
+
+/// How a signal is extended at both ends before zero-phase filtering to
+/// reduce transients at the edges.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FilterPadding {
+    None,
+    /// Reflects the signal at its end points with a point symmetry, which
+    /// preserves its slope.
+    Odd,
+    /// Mirrors the signal at its end points.
+    Even,
+    /// Repeats the end points.
+    Constant,
+}
+
+/// A transfer function given by its zeros, poles and gain.
+struct Zpk {
+    zeros: Vec<Complex>,
+    poles: Vec<Complex>,
+    gain: f64,
+}
+
+impl Zpk {
+    /// The excess of poles over zeros.
+    fn degree(&self) -> usize {
+        self.poles.len() - self.zeros.len()
+    }
+}
+
+fn product(values: &[Complex]) -> Complex {
+    values
+        .iter()
+        .fold(Complex::new(1., 0.), |product, &x| product * x)
+}
+
+fn negated(values: &[Complex]) -> Vec<Complex> {
+    values.iter().map(|&x| Complex::default() - x).collect()
+}
+
+/// Designs a digital IIR filter of the given order as a cascade of
+/// second-order sections `[b0, b1, b2, 1, a1, a2]`, each with the transfer
+/// function `(b0 + b1·z⁻¹ + b2·z⁻²) / (1 + a1·z⁻¹ + a2·z⁻²)`, which is
+/// numerically more robust than a single transfer function of high order.
+///
+/// The analog lowpass prototype of the family is transformed to the band
+/// and discretized with the bilinear transform, where the cutoffs are
+/// pre-warped to retain their frequencies. Band-pass and band-stop filters
+/// have twice the given order.
+pub fn iir_filter(
+    family: IirFamily,
+    order: usize,
+    band: FilterBand,
+    sample_rate: f64,
+) -> Vec<[f64; 6]> {
+    let prototype = match family {
+        IirFamily::Butterworth => butterworth(order),
+        IirFamily::ChebyshevI(ripple) => chebyshev_1(order, ripple),
+        IirFamily::ChebyshevII(attenuation) => chebyshev_2(order, attenuation),
+        IirFamily::Bessel => bessel(order),
+    };
+
+    let warp = |frequency: f64| 2. * sample_rate * (PI * frequency / sample_rate).tan();
+    let analog = match band {
+        FilterBand::Lowpass(cutoff) => to_lowpass(prototype, warp(cutoff)),
+        FilterBand::Highpass(cutoff) => to_highpass(prototype, warp(cutoff)),
+        FilterBand::Bandpass(low, high) => {
+            let (low, high) = (warp(low), warp(high));
+            to_bandpass(prototype, (low * high).sqrt(), high - low)
+        }
+        FilterBand::Bandstop(low, high) => {
+            let (low, high) = (warp(low), warp(high));
+            to_bandstop(prototype, (low * high).sqrt(), high - low)
+        }
+    };
+
+    to_sos(bilinear(analog, sample_rate))
+}
+
+/// The angles `π·m/(2N)` for `m = -N+1, -N+3, …, N-1` at which the poles of
+/// the prototypes are placed.
+fn pole_angles(order: usize) -> impl Iterator<Item = f64> {
+    (0..order).map(move |k| PI * (2. * k as f64 + 1. - order as f64) / (2 * order) as f64)
+}
+
+fn butterworth(order: usize) -> Zpk {
+    Zpk {
+        zeros: Vec::new(),
+        poles: pole_angles(order)
+            .map(|theta| Complex::new(-theta.cos(), -theta.sin()))
+            .collect(),
+        gain: 1.,
+    }
+}
+
+fn chebyshev_1(order: usize, ripple: f64) -> Zpk {
+    let epsilon = (10f64.powf(ripple / 10.) - 1.).sqrt();
+    let mu = (1. / epsilon).asinh() / order as f64;
+    let poles = pole_angles(order)
+        .map(|theta| Complex::new(-mu.sinh() * theta.cos(), -mu.cosh() * theta.sin()))
+        .collect::<Vec<_>>();
+    let mut gain = product(&negated(&poles)).re;
+    // Even orders have the lower edge of the ripple at zero frequency
+    if order.is_multiple_of(2) {
+        gain /= (1. + epsilon * epsilon).sqrt();
+    }
+    Zpk {
+        zeros: Vec::new(),
+        poles,
+        gain,
+    }
+}
+
+fn chebyshev_2(order: usize, attenuation: f64) -> Zpk {
+    let delta = 1. / (10f64.powf(attenuation / 10.) - 1.).sqrt();
+    let mu = (1. / delta).asinh() / order as f64;
+    let zeros = pole_angles(order)
+        .filter(|theta| theta.abs() > 1e-12)
+        .map(|theta| Complex::new(0., 1. / theta.sin()))
+        .collect::<Vec<_>>();
+    let poles = pole_angles(order)
+        .map(|theta| {
+            Complex::new(1., 0.) / Complex::new(-mu.sinh() * theta.cos(), -mu.cosh() * theta.sin())
+        })
+        .collect::<Vec<_>>();
+    let gain = (product(&negated(&poles)) / product(&negated(&zeros))).re;
+    Zpk { zeros, poles, gain }
+}
+
+/// Computes the poles of the Bessel filter as the roots of the reverse
+/// Bessel polynomial `θₙ(s) = Σ aₖ·sᵏ` with `aₖ = (2n-k)! / (2ⁿ⁻ᵏ·k!·(n-k)!)`.
+/// The polynomial is scaled such that `a₀ = aₙ = 1`, which normalizes the
+/// phase like SciPy's `norm="phase"`. Since the roots are ill-conditioned,
+/// their accuracy decreases from about `1e-13` for order 8 to `1e-9` for
+/// order 15.
+fn bessel(order: usize) -> Zpk {
+    let mut coefficients = vec![1.; order + 1];
+    for k in (1..=order).rev() {
+        coefficients[k - 1] =
+            coefficients[k] * ((2 * order - k + 1) * k) as f64 / (2 * (order - k + 1)) as f64;
+    }
+    let scale = coefficients[0].powf(1. / order as f64);
+    for (k, c) in coefficients.iter_mut().enumerate() {
+        *c *= scale.powi(k as i32) / scale.powi(order as i32);
+    }
+
+    Zpk {
+        zeros: Vec::new(),
+        poles: polynomial_roots(&coefficients),
+        gain: 1.,
+    }
+}
+
+/// Finds the roots of a monic polynomial with the coefficients in ascending
+/// order by the Aberth–Ehrlich method.
+fn polynomial_roots(coefficients: &[f64]) -> Vec<Complex> {
+    let n = coefficients.len() - 1;
+    let evaluate = |x: Complex| {
+        let mut value = Complex::default();
+        let mut derivative = Complex::default();
+        for &c in coefficients.iter().rev() {
+            derivative = derivative * x + value;
+            value = value * x + Complex::new(c, 0.);
+        }
+        (value, derivative)
+    };
+
+    // The roots have magnitudes of about one, so the initial guesses are
+    // spread on the unit circle without symmetry to the real axis
+    let mut roots = (0..n)
+        .map(|k| Complex::from_angle(2. * PI * k as f64 / n as f64 + 0.4))
+        .collect::<Vec<_>>();
+    for _ in 0..500 {
+        let mut converged = true;
+        for i in 0..n {
+            let (value, derivative) = evaluate(roots[i]);
+            if value == Complex::default() {
+                continue;
+            }
+            let ratio = value / derivative;
+            let repulsion = (0..n)
+                .filter(|&j| j != i)
+                .fold(Complex::default(), |sum, j| {
+                    sum + Complex::new(1., 0.) / (roots[i] - roots[j])
+                });
+            let step = ratio / (Complex::new(1., 0.) - ratio * repulsion);
+            roots[i] = roots[i] - step;
+            if step.norm() > 1e-15 * roots[i].norm() {
+                converged = false;
+            }
+        }
+        if converged {
+            break;
+        }
+    }
+
+    for root in &mut roots {
+        if is_real(*root) {
+            root.im = 0.;
+        }
+    }
+    roots
+}
+
+fn to_lowpass(zpk: Zpk, cutoff: f64) -> Zpk {
+    let degree = zpk.degree();
+    Zpk {
+        zeros: zpk.zeros.iter().map(|&z| z * cutoff).collect(),
+        poles: zpk.poles.iter().map(|&p| p * cutoff).collect(),
+        gain: zpk.gain * cutoff.powi(degree as i32),
+    }
+}
+
+fn to_highpass(zpk: Zpk, cutoff: f64) -> Zpk {
+    let degree = zpk.degree();
+    let gain = zpk.gain * (product(&negated(&zpk.zeros)) / product(&negated(&zpk.poles))).re;
+    let invert = |x: &Complex| Complex::new(cutoff, 0.) / *x;
+    let mut zeros = zpk.zeros.iter().map(invert).collect::<Vec<_>>();
+    zeros.extend(std::iter::repeat_n(Complex::default(), degree));
+    Zpk {
+        zeros,
+        poles: zpk.poles.iter().map(invert).collect(),
+        gain,
+    }
+}
+
+/// Maps each root `x` to the two roots `x ± √(x² - ω₀²)`.
+fn split_roots(roots: &[Complex], center: f64) -> Vec<Complex> {
+    let center = Complex::new(center * center, 0.);
+    roots
+        .iter()
+        .flat_map(|&x| {
+            let offset = (x * x - center).sqrt();
+            [x + offset, x - offset]
+        })
+        .collect()
+}
+
+fn to_bandpass(zpk: Zpk, center: f64, width: f64) -> Zpk {
+    let degree = zpk.degree();
+    let scale = |roots: &[Complex]| roots.iter().map(|&x| x * (width / 2.)).collect::<Vec<_>>();
+    let mut zeros = split_roots(&scale(&zpk.zeros), center);
+    zeros.extend(std::iter::repeat_n(Complex::default(), degree));
+    Zpk {
+        zeros,
+        poles: split_roots(&scale(&zpk.poles), center),
+        gain: zpk.gain * width.powi(degree as i32),
+    }
+}
+
+fn to_bandstop(zpk: Zpk, center: f64, width: f64) -> Zpk {
+    let degree = zpk.degree();
+    let gain = zpk.gain * (product(&negated(&zpk.zeros)) / product(&negated(&zpk.poles))).re;
+    let invert = |roots: &[Complex]| {
+        roots
+            .iter()
+            .map(|&x| Complex::new(width / 2., 0.) / x)
+            .collect::<Vec<_>>()
+    };
+    let mut zeros = split_roots(&invert(&zpk.zeros), center);
+    for _ in 0..degree {
+        zeros.push(Complex::new(0., center));
+        zeros.push(Complex::new(0., -center));
+    }
+    Zpk {
+        zeros,
+        poles: split_roots(&invert(&zpk.poles), center),
+        gain,
+    }
+}
+
+/// Discretizes an analog filter with the bilinear transform
+/// `s = 2·fs·(z - 1)/(z + 1)`.
+fn bilinear(zpk: Zpk, sample_rate: f64) -> Zpk {
+    let degree = zpk.degree();
+    let fs2 = Complex::new(2. * sample_rate, 0.);
+    let shifted = |roots: &[Complex]| roots.iter().map(|&x| fs2 - x).collect::<Vec<_>>();
+    let gain = zpk.gain * (product(&shifted(&zpk.zeros)) / product(&shifted(&zpk.poles))).re;
+    let map = |x: &Complex| (fs2 + *x) / (fs2 - *x);
+    let mut zeros = zpk.zeros.iter().map(map).collect::<Vec<_>>();
+    zeros.extend(std::iter::repeat_n(Complex::new(-1., 0.), degree));
+    Zpk {
+        zeros,
+        poles: zpk.poles.iter().map(map).collect(),
+        gain,
+    }
+}
+
+fn is_real(x: Complex) -> bool {
+    x.im.abs() <= 1e-10 * x.norm().max(1.)
+}
+
+/// Removes the root that completes a second-order factor with the given
+/// root from the roots and returns the coefficients `[1, c1, c2]` of the
+/// factor. A complex root is completed by its conjugate and a real root by
+/// the real root with the smallest key.
+fn take_factor(roots: &mut Vec<Complex>, root: Complex, key: impl Fn(Complex) -> f64) -> [f64; 3] {
+    let nearest = |roots: &[Complex], target: Complex| {
+        (0..roots.len())
+            .min_by(|&i, &j| {
+                (roots[i] - target)
+                    .norm()
+                    .total_cmp(&(roots[j] - target).norm())
+            })
+            .unwrap()
+    };
+    if !is_real(root) {
+        roots.swap_remove(nearest(roots, root.conj()));
+        return [1., -2. * root.re, root.norm_sqr()];
+    }
+    let index = (0..roots.len())
+        .filter(|&i| is_real(roots[i]))
+        .min_by(|&i, &j| key(roots[i]).total_cmp(&key(roots[j])))
+        .unwrap_or_else(|| nearest(roots, root));
+    let other = roots.swap_remove(index);
+    [1., -(root.re + other.re), root.re * other.re]
+}
+
+/// Groups the zeros and poles into second-order sections like SciPy's
+/// `zpk2sos` with the pairing `"nearest"`: the poles closest to the unit
+/// circle are paired with the nearest zeros first and placed in the last
+/// section.
+fn to_sos(zpk: Zpk) -> Vec<[f64; 6]> {
+    let Zpk {
+        mut zeros,
+        mut poles,
+        gain,
+    } = zpk;
+    // Missing poles and zeros are placed at the origin
+    let len = zeros.len().max(poles.len()).next_multiple_of(2);
+    zeros.resize(len, Complex::default());
+    poles.resize(len, Complex::default());
+
+    let distance_to_circle = |x: Complex| (1. - x.norm()).abs();
+    let mut sections = Vec::with_capacity(len / 2);
+    while !poles.is_empty() {
+        let index = (0..poles.len())
+            .min_by(|&i, &j| distance_to_circle(poles[i]).total_cmp(&distance_to_circle(poles[j])))
+            .unwrap();
+        let pole = poles.swap_remove(index);
+        let a = take_factor(&mut poles, pole, distance_to_circle);
+
+        let index = (0..zeros.len())
+            .min_by(|&i, &j| {
+                (zeros[i] - pole)
+                    .norm()
+                    .total_cmp(&(zeros[j] - pole).norm())
+            })
+            .unwrap();
+        let zero = zeros.swap_remove(index);
+        let b = take_factor(&mut zeros, zero, |x| (x - pole).norm());
+
+        sections.push([b[0], b[1], b[2], a[0], a[1], a[2]]);
+    }
+    sections.reverse();
+
+    if let Some(first) = sections.first_mut() {
+        first[..3].iter_mut().for_each(|b| *b *= gain);
+    }
+    sections
+}
+
+/// Designs a linear-phase FIR filter with the given number of taps by the
+/// window method, i.e., as the windowed impulse response of the ideal
+/// filter. The coefficients are scaled to a gain of one at zero frequency,
+/// at the Nyquist frequency for high-pass filters or at the center of the
+/// passband for band-pass filters.
+///
+/// # Panics
+///
+/// Panics for high-pass and band-stop filters with an even number of taps,
+/// whose response vanishes at the Nyquist frequency which they should pass.
+pub fn fir_filter(taps: usize, band: FilterBand, window: Window, sample_rate: f64) -> Vec<f64> {
+    if matches!(band, FilterBand::Highpass(_) | FilterBand::Bandstop(..)) {
+        assert!(
+            taps % 2 == 1,
+            "high-pass and band-stop FIR filters need an odd number of taps"
+        );
+    }
+    let nyquist = sample_rate / 2.;
+    let bands = match band {
+        FilterBand::Lowpass(cutoff) => vec![(0., cutoff / nyquist)],
+        FilterBand::Highpass(cutoff) => vec![(cutoff / nyquist, 1.)],
+        FilterBand::Bandpass(low, high) => vec![(low / nyquist, high / nyquist)],
+        FilterBand::Bandstop(low, high) => vec![(0., low / nyquist), (high / nyquist, 1.)],
+    };
+    let sinc = |x: f64| {
+        if x == 0. {
+            1.
+        } else {
+            (PI * x).sin() / (PI * x)
+        }
+    };
+
+    let center = (taps as f64 - 1.) / 2.;
+    let weights = self::window(window, taps, false);
+    let mut coefficients = weights
+        .iter()
+        .enumerate()
+        .map(|(k, weight)| {
+            let m = k as f64 - center;
+            let response = bands
+                .iter()
+                .map(|&(left, right)| right * sinc(right * m) - left * sinc(left * m))
+                .sum::<f64>();
+            response * weight
+        })
+        .collect::<Vec<_>>();
+
+    let (left, right) = bands[0];
+    let frequency = if left == 0. {
+        0.
+    } else if right == 1. {
+        1.
+    } else {
+        (left + right) / 2.
+    };
+    let gain = coefficients
+        .iter()
+        .enumerate()
+        .map(|(k, c)| c * (PI * (k as f64 - center) * frequency).cos())
+        .sum::<f64>();
+    coefficients.iter_mut().for_each(|c| *c /= gain);
+    coefficients
+}
+
+/// Normalizes the coefficients of a transfer function to `a[0] = 1` and pads
+/// them with zeros to the same length.
+fn normalize(b: &[f64], a: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    assert!(
+        a.first().is_some_and(|&a0| a0 != 0.),
+        "the leading denominator coefficient a[0] must be non-zero"
+    );
+    let len = b.len().max(a.len());
+    let mut b = b.iter().map(|x| x / a[0]).collect::<Vec<_>>();
+    let mut a = a.iter().map(|x| x / a[0]).collect::<Vec<_>>();
+    b.resize(len, 0.);
+    a.resize(len, 0.);
+    (b, a)
+}
+
+/// Filters the values with normalized coefficients in the transposed direct
+/// form II, starting from and updating the given state.
+fn filter_with_state(b: &[f64], a: &[f64], values: &[f64], state: &mut [f64]) -> Vec<f64> {
+    let n = state.len();
+    values
+        .iter()
+        .map(|&x| {
+            let y = b[0] * x + state.first().copied().unwrap_or(0.);
+            for k in 0..n {
+                let next = if k + 1 < n { state[k + 1] } else { 0. };
+                state[k] = b[k + 1] * x - a[k + 1] * y + next;
+            }
+            y
+        })
+        .collect()
+}
+
+/// Computes the state of a filter with normalized coefficients after a
+/// constant input of one, i.e., the initial state without a transient for
+/// a step response.
+fn steady_state(b: &[f64], a: &[f64]) -> Vec<f64> {
+    let n = b.len() - 1;
+    let mut state = vec![0.; n];
+    if n == 0 {
+        return state;
+    }
+    state[0] = (1..=n).map(|k| b[k] - a[k] * b[0]).sum::<f64>() / a.iter().sum::<f64>();
+    let (mut a_sum, mut c_sum) = (1., 0.);
+    for k in 1..n {
+        a_sum += a[k];
+        c_sum += b[k] - a[k] * b[0];
+        state[k] = a_sum * state[0] - c_sum;
+    }
+    state
+}
+
+/// Filters the values with the transfer function
+/// `(b[0] + b[1]·z⁻¹ + …) / (a[0] + a[1]·z⁻¹ + …)`, where `a = [1]` yields
+/// an FIR filter. The filter starts at rest, so the output has a transient
+/// and is delayed with respect to the input.
+///
+/// # Panics
+///
+/// Panics if `a` is empty or `a[0]` is zero.
+pub fn lfilter(b: &[f64], a: &[f64], values: &[f64]) -> Vec<f64> {
+    let (b, a) = normalize(b, a);
+    let mut state = vec![0.; b.len() - 1];
+    filter_with_state(&b, &a, values, &mut state)
+}
+
+/// Filters the values with a cascade of second-order sections as designed
+/// by [`iir_filter`], see [`lfilter`].
+///
+/// # Panics
+///
+/// Panics if the coefficient `a0` (the fourth entry) of a section is zero.
+pub fn sosfilt(sos: &[[f64; 6]], values: &[f64]) -> Vec<f64> {
+    sos.iter().fold(values.to_vec(), |values, section| {
+        lfilter(&section[..3], &section[3..], &values)
+    })
+}
+
+/// Filters the values forward and backward with the transfer function, see
+/// [`lfilter`], which results in zero phase shift and the squared magnitude
+/// response. The signal is extended by three times the number of
+/// coefficients at both ends, but by less than its length, and both passes
+/// start in the steady state of the first value to suppress transients.
+///
+/// # Panics
+///
+/// Panics if `a` is empty or `a[0]` is zero.
+pub fn filtfilt(b: &[f64], a: &[f64], values: &[f64], padding: FilterPadding) -> Vec<f64> {
+    let (b, a) = normalize(b, a);
+    let state = steady_state(&b, &a);
+    zero_phase(values, padding, 3 * b.len(), |values| {
+        let mut state = state.iter().map(|z| z * values[0]).collect::<Vec<_>>();
+        filter_with_state(&b, &a, values, &mut state)
+    })
+}
+
+/// Filters the values forward and backward with a cascade of second-order
+/// sections, see [`filtfilt`].
+///
+/// # Panics
+///
+/// Panics if the coefficient `a0` (the fourth entry) of a section is zero.
+pub fn sosfiltfilt(sos: &[[f64; 6]], values: &[f64], padding: FilterPadding) -> Vec<f64> {
+    let sections = sos
+        .iter()
+        .map(|section| normalize(&section[..3], &section[3..]))
+        .collect::<Vec<_>>();
+
+    // The steady state of each section for the output of the previous ones
+    let mut scale = 1.;
+    let states = sections
+        .iter()
+        .map(|(b, a)| {
+            let state = steady_state(b, a)
+                .iter()
+                .map(|z| z * scale)
+                .collect::<Vec<_>>();
+            scale *= b.iter().sum::<f64>() / a.iter().sum::<f64>();
+            state
+        })
+        .collect::<Vec<_>>();
+
+    let trailing_zeros = |index: usize| sos.iter().filter(|s| s[index] == 0.).count();
+    let pad_len = 3 * (2 * sos.len() + 1 - trailing_zeros(2).min(trailing_zeros(5)));
+    zero_phase(values, padding, pad_len, |values| {
+        sections
+            .iter()
+            .zip(&states)
+            .fold(values.to_vec(), |output, ((b, a), state)| {
+                let mut state = state.iter().map(|z| z * values[0]).collect::<Vec<_>>();
+                filter_with_state(b, a, &output, &mut state)
+            })
+    })
+}
+
+/// Applies a filter forward and backward to the values extended at both
+/// ends.
+fn zero_phase(
+    values: &[f64],
+    padding: FilterPadding,
+    pad_len: usize,
+    filter: impl Fn(&[f64]) -> Vec<f64>,
+) -> Vec<f64> {
+    let n = values.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let pad_len = match padding {
+        FilterPadding::None => 0,
+        _ => pad_len.min(n - 1),
+    };
+    let extend = |edge: f64, x: f64| match padding {
+        FilterPadding::Odd => 2. * edge - x,
+        FilterPadding::Even => x,
+        FilterPadding::Constant | FilterPadding::None => edge,
+    };
+
+    let (first, last) = (values[0], values[n - 1]);
+    let extended = (1..=pad_len)
+        .rev()
+        .map(|k| extend(first, values[k]))
+        .chain(values.iter().copied())
+        .chain((1..=pad_len).map(|k| extend(last, values[n - 1 - k])))
+        .collect::<Vec<_>>();
+
+    let mut output = filter(&extended);
+    output.reverse();
+    let mut output = filter(&output);
+    output.reverse();
+    output.drain(..pad_len);
+    output.truncate(n);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    /// Evaluates the magnitude response of second-order sections at the
+    /// given frequency relative to the sample rate.
+    fn gain(sos: &[[f64; 6]], frequency: f64) -> f64 {
+        let z = Complex::from_angle(-2. * PI * frequency);
+        let z2 = z * z;
+        sos.iter()
+            .map(|s| {
+                let b = Complex::new(s[0], 0.) + z * s[1] + z2 * s[2];
+                let a = Complex::new(s[3], 0.) + z * s[4] + z2 * s[5];
+                (b / a).norm()
+            })
+            .product()
+    }
+
+    #[test]
+    fn test_butterworth() {
+        // Second order with a cutoff at a quarter of the sample rate has the
+        // coefficients of SciPy's butter(2, 0.5)
+        let sos = iir_filter(IirFamily::Butterworth, 2, FilterBand::Lowpass(25.), 100.);
+        assert_eq!(sos.len(), 1);
+        let expected = [
+            0.29289321881,
+            0.58578643763,
+            0.29289321881,
+            1.,
+            0.,
+            0.17157287525,
+        ];
+        for (a, b) in sos[0].iter().zip(expected) {
+            assert_abs_diff_eq!(a, &b, epsilon = 1e-10);
+        }
+
+        for order in [1, 4, 5] {
+            let sos = iir_filter(IirFamily::Butterworth, order, FilterBand::Lowpass(0.1), 1.);
+            assert_eq!(sos.len(), order.div_ceil(2));
+            assert_abs_diff_eq!(gain(&sos, 0.), 1., epsilon = 1e-12);
+            assert_abs_diff_eq!(gain(&sos, 0.1), 0.5f64.sqrt(), epsilon = 1e-12);
+            assert_abs_diff_eq!(gain(&sos, 0.5), 0., epsilon = 1e-12);
+
+            let sos = iir_filter(IirFamily::Butterworth, order, FilterBand::Highpass(0.1), 1.);
+            assert_abs_diff_eq!(gain(&sos, 0.), 0., epsilon = 1e-12);
+            assert_abs_diff_eq!(gain(&sos, 0.1), 0.5f64.sqrt(), epsilon = 1e-12);
+            assert_abs_diff_eq!(gain(&sos, 0.5), 1., epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_band_filters() {
+        let sos = iir_filter(
+            IirFamily::Butterworth,
+            3,
+            FilterBand::Bandpass(10., 20.),
+            100.,
+        );
+        assert_eq!(sos.len(), 3);
+        assert_abs_diff_eq!(gain(&sos, 0.), 0., epsilon = 1e-12);
+        assert_abs_diff_eq!(gain(&sos, 0.1), 0.5f64.sqrt(), epsilon = 1e-12);
+        assert_abs_diff_eq!(gain(&sos, 0.2), 0.5f64.sqrt(), epsilon = 1e-12);
+        assert_abs_diff_eq!(gain(&sos, 0.5), 0., epsilon = 1e-12);
+
+        let sos = iir_filter(
+            IirFamily::Butterworth,
+            2,
+            FilterBand::Bandstop(10., 20.),
+            100.,
+        );
+        assert_eq!(sos.len(), 2);
+        assert_abs_diff_eq!(gain(&sos, 0.), 1., epsilon = 1e-12);
+        assert_abs_diff_eq!(gain(&sos, 0.1), 0.5f64.sqrt(), epsilon = 1e-12);
+        assert_abs_diff_eq!(gain(&sos, 0.2), 0.5f64.sqrt(), epsilon = 1e-12);
+        assert_abs_diff_eq!(gain(&sos, 0.5), 1., epsilon = 1e-12);
+        // The zeros lie at the geometric center of the pre-warped band
+        let center = ((PI * 0.1).tan() * (PI * 0.2).tan()).sqrt().atan() / PI;
+        assert_abs_diff_eq!(gain(&sos, center), 0., epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_chebyshev() {
+        let ripple_gain = 10f64.powf(-1. / 20.);
+        for order in [3, 4] {
+            let sos = iir_filter(
+                IirFamily::ChebyshevI(1.),
+                order,
+                FilterBand::Lowpass(0.2),
+                1.,
+            );
+            let dc = if order == 3 { 1. } else { ripple_gain };
+            assert_abs_diff_eq!(gain(&sos, 0.), dc, epsilon = 1e-12);
+            assert_abs_diff_eq!(gain(&sos, 0.2), ripple_gain, epsilon = 1e-12);
+            assert!((0..200).all(|k| gain(&sos, k as f64 * 0.001) <= 1. + 1e-12));
+
+            let sos = iir_filter(
+                IirFamily::ChebyshevII(40.),
+                order,
+                FilterBand::Lowpass(0.2),
+                1.,
+            );
+            assert_abs_diff_eq!(gain(&sos, 0.), 1., epsilon = 1e-12);
+            assert_abs_diff_eq!(gain(&sos, 0.2), 0.01, epsilon = 1e-12);
+            assert!((200..500).all(|k| gain(&sos, k as f64 * 0.001) <= 0.01 + 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_bessel() {
+        // The roots of u³ + 6/c·u² + 15/c²·u + 1 with c = ∛15 as in SciPy's
+        // besselap(3)
+        let poles = bessel(3).poles;
+        let mut expected = [
+            Complex::new(-0.94160002653, 0.),
+            Complex::new(-0.74564038585, 0.71136662497),
+            Complex::new(-0.74564038585, -0.71136662497),
+        ];
+        for pole in poles {
+            let index = (0..3)
+                .min_by(|&i, &j| {
+                    (expected[i] - pole)
+                        .norm()
+                        .total_cmp(&(expected[j] - pole).norm())
+                })
+                .unwrap();
+            assert_abs_diff_eq!((expected[index] - pole).norm(), 0., epsilon = 1e-8);
+            expected[index] = Complex::new(f64::INFINITY, 0.);
+        }
+
+        // The poles of the highest supported order reproduce the polynomial
+        let poles = bessel(15).poles;
+        let coefficients = poles.iter().fold(vec![Complex::new(1., 0.)], |c, &p| {
+            let mut result = vec![Complex::default(); c.len() + 1];
+            for (k, &x) in c.iter().enumerate() {
+                result[k + 1] = result[k + 1] + x;
+                result[k] = result[k] - x * p;
+            }
+            result
+        });
+        assert_abs_diff_eq!(coefficients[0].re, 1., epsilon = 1e-9);
+        // a₁₄ = 15·16/2 scaled by a₀^(-1/15) with a₀ = 29!!
+        assert_abs_diff_eq!(coefficients[14].re, 10.626751968206028, epsilon = 1e-9);
+        assert!(coefficients.iter().all(|c| c.im.abs() < 1e-9 * c.norm()));
+
+        for order in [1, 2, 10, 15] {
+            let sos = iir_filter(IirFamily::Bessel, order, FilterBand::Lowpass(0.05), 1.);
+            assert_abs_diff_eq!(gain(&sos, 0.), 1., epsilon = 1e-8);
+            assert_abs_diff_eq!(gain(&sos, 0.5), 0., epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_fir_filter() {
+        let coefficients = fir_filter(21, FilterBand::Lowpass(10.), Window::Hamming, 100.);
+        assert_eq!(coefficients.len(), 21);
+        assert_abs_diff_eq!(coefficients.iter().sum::<f64>(), 1., epsilon = 1e-12);
+        for k in 0..10 {
+            assert_abs_diff_eq!(coefficients[k], coefficients[20 - k], epsilon = 1e-15);
+        }
+
+        let response = |coefficients: &[f64], frequency: f64| {
+            coefficients
+                .iter()
+                .enumerate()
+                .fold(Complex::default(), |sum, (k, &c)| {
+                    sum + Complex::from_angle(-2. * PI * frequency * k as f64) * c
+                })
+                .norm()
+        };
+        let coefficients = fir_filter(31, FilterBand::Highpass(0.2), Window::Hann, 1.);
+        assert_abs_diff_eq!(response(&coefficients, 0.5), 1., epsilon = 1e-12);
+        assert!(response(&coefficients, 0.) < 1e-3);
+
+        let coefficients = fir_filter(41, FilterBand::Bandpass(0.1, 0.3), Window::Hamming, 1.);
+        assert_abs_diff_eq!(response(&coefficients, 0.2), 1., epsilon = 1e-12);
+        assert!(response(&coefficients, 0.) < 1e-2);
+        assert!(response(&coefficients, 0.5) < 1e-2);
+
+        let coefficients = fir_filter(41, FilterBand::Bandstop(0.1, 0.3), Window::Hamming, 1.);
+        assert_abs_diff_eq!(response(&coefficients, 0.), 1., epsilon = 1e-12);
+        assert!(response(&coefficients, 0.2) < 1e-2);
+    }
+
+    #[test]
+    fn test_lfilter() {
+        // A moving average and an exponential smoothing
+        assert_eq!(
+            lfilter(&[1., 1.], &[2.], &[2., 4., 6., 8.]),
+            [1., 3., 5., 7.]
+        );
+        assert_eq!(lfilter(&[1.], &[1., -0.5], &[1., 0., 0.]), [1., 0.5, 0.25]);
+
+        let values = (0..50).map(|k| (k as f64 * 0.7).sin()).collect::<Vec<_>>();
+        let sos = iir_filter(IirFamily::ChebyshevI(0.5), 5, FilterBand::Lowpass(0.2), 1.);
+        let result = sosfilt(&sos, &values);
+
+        // The cascade equals the product of the sections as a single
+        // transfer function
+        let multiply = |p: &[f64], q: &[f64]| {
+            let mut result = vec![0.; p.len() + q.len() - 1];
+            for (i, x) in p.iter().enumerate() {
+                for (j, y) in q.iter().enumerate() {
+                    result[i + j] += x * y;
+                }
+            }
+            result
+        };
+        let (b, a) = sos.iter().fold((vec![1.], vec![1.]), |(b, a), s| {
+            (multiply(&b, &s[..3]), multiply(&a, &s[3..]))
+        });
+        for (x, y) in result.iter().zip(lfilter(&b, &a, &values)) {
+            assert_abs_diff_eq!(*x, y, epsilon = 1e-12);
+        }
+        assert!(sosfilt(&sos, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_filtfilt() {
+        // A constant signal passes without transients
+        let sos = iir_filter(IirFamily::Butterworth, 4, FilterBand::Lowpass(0.1), 1.);
+        for padding in [
+            FilterPadding::None,
+            FilterPadding::Odd,
+            FilterPadding::Even,
+            FilterPadding::Constant,
+        ] {
+            for x in sosfiltfilt(&sos, &[3.; 20], padding) {
+                assert_abs_diff_eq!(x, 3., epsilon = 1e-10);
+            }
+        }
+        for x in filtfilt(&[0.2, 0.3], &[1., -0.5], &[3.; 5], FilterPadding::Odd) {
+            assert_abs_diff_eq!(x, 3., epsilon = 1e-12);
+        }
+
+        // A slow sinusoid passes without phase shift while a fast one is
+        // removed
+        let values = (0..400)
+            .map(|k| {
+                let t = k as f64;
+                (2. * PI * 0.01 * t).sin() + 0.5 * (2. * PI * 0.3 * t).sin()
+            })
+            .collect::<Vec<_>>();
+        let result = sosfiltfilt(&sos, &values, FilterPadding::Odd);
+        assert_eq!(result.len(), 400);
+        for (k, x) in result.iter().enumerate().take(350).skip(50) {
+            assert_abs_diff_eq!(*x, (2. * PI * 0.01 * k as f64).sin(), epsilon = 1e-2);
+        }
+
+        // The same filter as a single transfer function and as an FIR filter
+        let coefficients = fir_filter(31, FilterBand::Lowpass(0.1), Window::Hamming, 1.);
+        let result = filtfilt(&coefficients, &[1.], &values, FilterPadding::Even);
+        for (k, x) in result.iter().enumerate().take(350).skip(50) {
+            assert_abs_diff_eq!(*x, (2. * PI * 0.01 * k as f64).sin(), epsilon = 1e-2);
+        }
+
+        assert!(filtfilt(&[1.], &[1.], &[], FilterPadding::Odd).is_empty());
+        assert_eq!(sosfiltfilt(&sos, &[2.], FilterPadding::Odd).len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "the leading denominator coefficient a[0] must be non-zero")]
+    fn test_empty_denominator() {
+        lfilter(&[1.], &[], &[1., 2.]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the leading denominator coefficient a[0] must be non-zero")]
+    fn test_zero_leading_denominator() {
+        filtfilt(&[1.], &[0., 1.], &[1., 2.], FilterPadding::Odd);
+    }
+
+    #[test]
+    #[should_panic(expected = "high-pass and band-stop FIR filters need an odd number of taps")]
+    fn test_even_highpass_taps() {
+        fir_filter(30, FilterBand::Highpass(0.2), Window::Hann, 1.);
+    }
+}
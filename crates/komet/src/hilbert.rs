@@ -0,0 +1,136 @@
+use crate::fft::{fft, ifft, Complex, FftNorm};
+use crate::spectrum::{angle, magnitude, unwrap};
+use std::f64::consts::PI;
+
+/// Computes the analytic signal `x + i·H(x)` of real values, whose imaginary
+/// part is the Hilbert transform `H(x)`. The spectrum of the analytic signal
+/// is obtained by removing the negative frequencies and doubling the
+/// positive ones, so the signal is treated as periodic.
+pub fn hilbert(values: &[f64]) -> Vec<Complex> {
+    let n = values.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut result = values
+        .iter()
+        .map(|&x| Complex::new(x, 0.))
+        .collect::<Vec<_>>();
+    fft(&mut result, FftNorm::Backward);
+
+    // The zero frequency and the Nyquist frequency of an even length have no
+    // negative counterpart and are kept as they are
+    for x in &mut result[1..n.div_ceil(2)] {
+        *x = *x * 2.;
+    }
+    for x in &mut result[n / 2 + 1..] {
+        *x = Complex::default();
+    }
+
+    ifft(&mut result, FftNorm::Backward);
+    result
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AnalyticSignal {
+    /// The analytic signal, see [`hilbert`].
+    pub values: Vec<Complex>,
+    /// The instantaneous amplitude, i.e., the magnitude of the analytic
+    /// signal.
+    pub envelope: Vec<f64>,
+    /// The unwrapped instantaneous phase in radians.
+    pub phase: Vec<f64>,
+    /// The instantaneous frequency in cycles per unit of time, i.e., the
+    /// derivative of the phase divided by `2π`, computed with central
+    /// differences in the interior and one-sided differences at the ends.
+    pub frequency: Vec<f64>,
+}
+
+/// Computes the analytic signal of real values sampled at the given rate
+/// together with the instantaneous amplitude, phase and frequency, e.g., for
+/// demodulating an amplitude- or frequency-modulated signal.
+pub fn analytic_signal(values: &[f64], sample_rate: f64) -> AnalyticSignal {
+    let analytic = hilbert(values);
+    let envelope = magnitude(&analytic);
+    let phase = unwrap(&angle(&analytic));
+
+    let n = phase.len();
+    let scale = sample_rate / (2. * PI);
+    let frequency = (0..n)
+        .map(|k| {
+            if n < 2 {
+                0.
+            } else if k == 0 {
+                (phase[1] - phase[0]) * scale
+            } else if k == n - 1 {
+                (phase[k] - phase[k - 1]) * scale
+            } else {
+                (phase[k + 1] - phase[k - 1]) / 2. * scale
+            }
+        })
+        .collect();
+
+    AnalyticSignal {
+        values: analytic,
+        envelope,
+        phase,
+        frequency,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_hilbert() {
+        // The Hilbert transform of a cosine is a sine
+        for n in [16, 15] {
+            let values = (0..n)
+                .map(|k| (2. * PI * 3. * k as f64 / n as f64).cos())
+                .collect::<Vec<_>>();
+            let result = hilbert(&values);
+            for (k, x) in result.iter().enumerate() {
+                let t = 2. * PI * 3. * k as f64 / n as f64;
+                assert_abs_diff_eq!(x.re, t.cos(), epsilon = 1e-12);
+                assert_abs_diff_eq!(x.im, t.sin(), epsilon = 1e-12);
+            }
+        }
+
+        // The transform of the Nyquist frequency and a constant vanishes
+        let result = hilbert(&[3., -1., 3., -1.]);
+        for (x, expected) in result.iter().zip([3., -1., 3., -1.]) {
+            assert_abs_diff_eq!(x.re, expected, epsilon = 1e-12);
+            assert_abs_diff_eq!(x.im, 0., epsilon = 1e-12);
+        }
+        assert!(hilbert(&[]).is_empty());
+        assert_eq!(hilbert(&[2.]), [Complex::new(2., 0.)]);
+    }
+
+    #[test]
+    fn test_analytic_signal() {
+        // An amplitude-modulated carrier whose frequencies are whole cycles
+        let n = 200;
+        let sample_rate = 100.;
+        let values = (0..n)
+            .map(|k| {
+                let t = k as f64 / sample_rate;
+                (1. + 0.5 * (2. * PI * t).cos()) * (2. * PI * 10. * t).cos()
+            })
+            .collect::<Vec<_>>();
+        let result = analytic_signal(&values, sample_rate);
+        assert_eq!(result.values.len(), n);
+        for k in 0..n {
+            let t = k as f64 / sample_rate;
+            assert_abs_diff_eq!(
+                result.envelope[k],
+                1. + 0.5 * (2. * PI * t).cos(),
+                epsilon = 1e-12
+            );
+            assert_abs_diff_eq!(result.phase[k], 2. * PI * 10. * t, epsilon = 1e-10);
+            assert_abs_diff_eq!(result.frequency[k], 10., epsilon = 1e-8);
+        }
+
+        assert_eq!(analytic_signal(&[1.], 1.).frequency, [0.]);
+    }
+}
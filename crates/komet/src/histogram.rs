@@ -1,19 +1,114 @@
-pub fn histogram(values: &[f64], edges: &[f64]) -> Vec<u64> {
-    let mut counts = vec![0u64; edges.len() - 1];
-    for &value in values {
-        if value < edges[0] {
-            continue;
-        }
+/// The result of sorting values into bins.
+#[derive(Debug, PartialEq)]
+pub struct Histogram {
+    /// The number of values in each bin.
+    pub counts: Vec<u64>,
+    /// The number of values below the first edge.
+    pub underflow: u64,
+    /// The number of values above the last edge.
+    pub overflow: u64,
+    /// The number of NaN values.
+    pub nan: u64,
+}
+
+/// The location of a value relative to a set of bin edges.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum Bin {
+    Underflow,
+    Index(usize),
+    Overflow,
+    NaN,
+}
+
+/// Finds the bin a value falls into. By default, the lower edge of a bin is
+/// included while the upper edge is excluded, except for the last bin. If
+/// `right` is set, the upper edge is included instead and the lower edge is
+/// excluded, except for the first bin. The edges are expected to be sorted in
+/// ascending order.
+pub(crate) fn locate(value: f64, edges: &[f64], right: bool) -> Bin {
+    let last = edges.len() - 1;
+    if value.is_nan() {
+        Bin::NaN
+    } else if value < edges[0] {
+        Bin::Underflow
+    } else if value > edges[last] {
+        Bin::Overflow
+    } else if right {
+        Bin::Index(edges.partition_point(|&edge| edge < value).max(1) - 1)
+    } else if value == edges[last] {
+        Bin::Index(last - 1)
+    } else {
+        Bin::Index(edges.partition_point(|&edge| edge <= value) - 1)
+    }
+}
+
+/// Computes a histogram by counting how many values fall into each of the
+/// bins defined by `edges`. If n+1 edges are given, the values are sorted
+/// into n bins `[e_i, e_(i+1))` where the last bin also includes its upper
+/// edge.
+///
+/// Values outside the edges are counted as underflow and overflow. If `fold`
+/// is set, they are additionally counted in the first and last bin,
+/// respectively. NaN values are never sorted into a bin. With fewer than two
+/// edges, there are no bins and the histogram is empty.
+pub fn histogram(values: &[f64], edges: &[f64], fold: bool) -> Histogram {
+    let mut histogram = Histogram {
+        counts: vec![0u64; edges.len().saturating_sub(1)],
+        underflow: 0,
+        overflow: 0,
+        nan: 0,
+    };
+    if edges.len() < 2 {
+        return histogram;
+    }
+    let last = edges.len() - 2;
 
-        for (i, &edge) in edges[1..].iter().enumerate() {
-            if value < edge || (value == edge && i == edges.len() - 2) {
-                counts[i] += 1;
-                break;
+    for &value in values {
+        match locate(value, edges, false) {
+            Bin::Index(i) => histogram.counts[i] += 1,
+            Bin::Underflow => {
+                histogram.underflow += 1;
+                if fold {
+                    histogram.counts[0] += 1;
+                }
+            }
+            Bin::Overflow => {
+                histogram.overflow += 1;
+                if fold {
+                    histogram.counts[last] += 1;
+                }
             }
+            Bin::NaN => histogram.nan += 1,
         }
     }
 
-    counts
+    histogram
+}
+
+/// Determines the index of the bin each value falls into, following the same
+/// edge semantics as [`histogram`]. If `right` is set, bins include their
+/// upper edge instead of their lower edge, i.e., `(e_i, e_(i+1)]`, where the
+/// first bin also includes its lower edge.
+///
+/// If n+1 edges are given, values below the first edge are assigned the index
+/// -1 and values above the last edge are assigned the index n. NaN values are
+/// assigned the index n+1.
+///
+/// # Panics
+///
+/// Panics if fewer than two edges are given.
+pub fn digitize(values: &[f64], edges: &[f64], right: bool) -> Vec<i64> {
+    assert!(edges.len() >= 2, "digitize requires at least two edges");
+    let num_bins = edges.len() as i64 - 1;
+    values
+        .iter()
+        .map(|&value| match locate(value, edges, right) {
+            Bin::Index(i) => i as i64,
+            Bin::Underflow => -1,
+            Bin::Overflow => num_bins,
+            Bin::NaN => num_bins + 1,
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -22,18 +117,94 @@ mod tests {
 
     #[test]
     fn test_histogram() {
-        assert_eq!(histogram(&[2., 4., 4.], &[1., 3., 5.]), [1, 2]);
+        assert_eq!(
+            histogram(&[2., 4., 4.], &[1., 3., 5.], false).counts,
+            [1, 2]
+        );
+    }
+
+    #[test]
+    fn test_too_few_edges() {
+        for edges in [&[][..], &[1.]] {
+            let result = histogram(&[0., 1., 2.], edges, true);
+            assert!(result.counts.is_empty());
+            assert_eq!((result.underflow, result.overflow, result.nan), (0, 0, 0));
+        }
     }
 
     #[test]
     fn test_upper_edge() {
-        assert_eq!(histogram(&[0., 2., 4.], &[0., 2., 4.]), [1, 2]);
+        assert_eq!(
+            histogram(&[0., 2., 4.], &[0., 2., 4.], false).counts,
+            [1, 2]
+        );
     }
     #[test]
     fn test_different_lengths() {
         assert_eq!(
-            histogram(&[1.0, 2.0, 3.0], &[1.0, 1.666, 2.3333, 3.0]),
+            histogram(&[1.0, 2.0, 3.0], &[1.0, 1.666, 2.3333, 3.0], false).counts,
             [1, 1, 1]
         );
     }
+
+    #[test]
+    fn test_out_of_range() {
+        assert_eq!(
+            histogram(&[-1., 0., f64::NAN, 2., 5., 6., -3.], &[0., 2., 4.], false),
+            Histogram {
+                counts: vec![1, 1],
+                underflow: 2,
+                overflow: 2,
+                nan: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_fold() {
+        assert_eq!(
+            histogram(&[-1., 0., f64::NAN, 2., 5., 6., -3.], &[0., 2., 4.], true),
+            Histogram {
+                counts: vec![3, 3],
+                underflow: 2,
+                overflow: 2,
+                nan: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_locate() {
+        let edges = [0., 1., 1., 3.];
+        assert_eq!(locate(-0.5, &edges, false), Bin::Underflow);
+        assert_eq!(locate(0., &edges, false), Bin::Index(0));
+        assert_eq!(locate(1., &edges, false), Bin::Index(2));
+        assert_eq!(locate(3., &edges, false), Bin::Index(2));
+        assert_eq!(locate(3.5, &edges, false), Bin::Overflow);
+        assert_eq!(locate(f64::NAN, &edges, false), Bin::NaN);
+    }
+
+    #[test]
+    fn test_locate_right() {
+        let edges = [0., 1., 1., 3.];
+        assert_eq!(locate(-0.5, &edges, true), Bin::Underflow);
+        assert_eq!(locate(0., &edges, true), Bin::Index(0));
+        assert_eq!(locate(1., &edges, true), Bin::Index(0));
+        assert_eq!(locate(2., &edges, true), Bin::Index(2));
+        assert_eq!(locate(3., &edges, true), Bin::Index(2));
+        assert_eq!(locate(3.5, &edges, true), Bin::Overflow);
+    }
+
+    #[test]
+    fn test_digitize() {
+        let values = [-1., 0., 0.5, 1., 2., 2.5, f64::NAN];
+        assert_eq!(
+            digitize(&values, &[0., 1., 2.], false),
+            [-1, 0, 0, 1, 1, 2, 3]
+        );
+        assert_eq!(
+            digitize(&values, &[0., 1., 2.], true),
+            [-1, 0, 0, 0, 1, 2, 3]
+        );
+    }
 }
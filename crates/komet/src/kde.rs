@@ -0,0 +1,594 @@
+use crate::fft::{circular_convolve, Complex, Fft};
+use crate::quantile::{percentile, sort_weighted, weighted_quantile_sorted, QuantileMethod};
+use std::f64::consts::PI;
+
+/// Above this number of kernel evaluations (data points times grid points),
+/// the density is computed by binned FFT convolution instead of direct
+/// summation.
+const DIRECT_LIMIT: usize = 1 << 20;
+
+/// A kernel function for kernel density estimation.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Kernel {
+    Gaussian,
+    Epanechnikov,
+    Uniform,
+    Triangular,
+    Biweight,
+    Triweight,
+    Cosine,
+}
+
+impl Kernel {
+    /// Evaluates the kernel in its canonical form which is supported on
+    /// `[-1, 1]`, except for the Gaussian kernel which is the standard
+    /// normal distribution.
+    fn canonical(self, u: f64) -> f64 {
+        if self != Kernel::Gaussian && u.abs() > 1. {
+            return 0.;
+        }
+        match self {
+            Kernel::Gaussian => (-0.5 * u * u).exp() / (2. * PI).sqrt(),
+            Kernel::Epanechnikov => 0.75 * (1. - u * u),
+            Kernel::Uniform => 0.5,
+            Kernel::Triangular => 1. - u.abs(),
+            Kernel::Biweight => 15. / 16. * (1. - u * u).powi(2),
+            Kernel::Triweight => 35. / 32. * (1. - u * u).powi(3),
+            Kernel::Cosine => PI / 4. * (PI / 2. * u).cos(),
+        }
+    }
+
+    /// The standard deviation of the canonical kernel.
+    fn std(self) -> f64 {
+        match self {
+            Kernel::Gaussian => 1.,
+            Kernel::Epanechnikov => (1. / 5f64).sqrt(),
+            Kernel::Uniform => (1. / 3f64).sqrt(),
+            Kernel::Triangular => (1. / 6f64).sqrt(),
+            Kernel::Biweight => (1. / 7f64).sqrt(),
+            Kernel::Triweight => (1. / 9f64).sqrt(),
+            Kernel::Cosine => (1. - 8. / (PI * PI)).sqrt(),
+        }
+    }
+
+    /// Evaluates the kernel scaled to have the standard deviation `bandwidth`.
+    pub(crate) fn evaluate(self, x: f64, bandwidth: f64) -> f64 {
+        let scale = bandwidth / self.std();
+        self.canonical(x / scale) / scale
+    }
+
+    /// The radius beyond which the kernel with standard deviation `bandwidth`
+    /// vanishes. The Gaussian kernel is cut off at eight standard deviations.
+    pub(crate) fn support(self, bandwidth: f64) -> f64 {
+        match self {
+            Kernel::Gaussian => 8. * bandwidth,
+            _ => bandwidth / self.std(),
+        }
+    }
+}
+
+/// A rule for selecting the bandwidth of a kernel density estimate.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Bandwidth {
+    /// Scott's normal reference rule `1.06·σ·n^(-1/5)`.
+    Scott,
+    /// Silverman's rule of thumb `0.9·min(σ, IQR/1.34)·n^(-1/5)`.
+    Silverman,
+    /// The Improved Sheather–Jones algorithm by Botev et al. (2010) which
+    /// does not assume normally distributed data.
+    Isj,
+    /// A fixed bandwidth.
+    Fixed(f64),
+}
+
+/// Options for [`kde`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct KdeOptions<'a> {
+    pub kernel: Kernel,
+    pub bandwidth: Bandwidth,
+    /// Optional weights for the data points.
+    pub weights: Option<&'a [f64]>,
+    /// A lower boundary of the data at which the density is reflected.
+    pub lower_bound: Option<f64>,
+    /// An upper boundary of the data at which the density is reflected.
+    pub upper_bound: Option<f64>,
+}
+
+impl Default for KdeOptions<'_> {
+    fn default() -> Self {
+        KdeOptions {
+            kernel: Kernel::Gaussian,
+            bandwidth: Bandwidth::Scott,
+            weights: None,
+            lower_bound: None,
+            upper_bound: None,
+        }
+    }
+}
+
+/// Returns the weighted mean, the (bias-corrected) weighted standard
+/// deviation and the effective sample size `(Σw)²/Σw²`.
+fn weighted_moments(values: &[f64], weights: Option<&[f64]>) -> (f64, f64, f64) {
+    let weight = |i: usize| weights.map_or(1., |w| w[i]);
+    let total = (0..values.len()).map(weight).sum::<f64>();
+    let total_squared = (0..values.len()).map(|i| weight(i).powi(2)).sum::<f64>();
+    let n_eff = total * total / total_squared;
+
+    let mean = values
+        .iter()
+        .enumerate()
+        .map(|(i, x)| weight(i) * x)
+        .sum::<f64>()
+        / total;
+    let variance = values
+        .iter()
+        .enumerate()
+        .map(|(i, x)| weight(i) * (x - mean).powi(2))
+        .sum::<f64>()
+        / total
+        * n_eff
+        / (n_eff - 1.);
+    (mean, variance.sqrt(), n_eff)
+}
+
+/// Computes the interquartile range, taking into account optional weights.
+fn iqr(values: &[f64], weights: Option<&[f64]>) -> f64 {
+    match weights {
+        None => {
+            let mut sorted = values.to_vec();
+            sorted.sort_by(f64::total_cmp);
+            percentile(&sorted, 75.) - percentile(&sorted, 25.)
+        }
+        Some(weights) => {
+            let (sorted_values, sorted_weights) = sort_weighted(values, weights);
+            let quantile = |p| {
+                weighted_quantile_sorted(&sorted_values, &sorted_weights, p, QuantileMethod::Linear)
+            };
+            quantile(0.75) - quantile(0.25)
+        }
+    }
+}
+
+/// Selects a bandwidth for a kernel density estimate of `values`. The
+/// bandwidth is the standard deviation of the kernel.
+///
+/// If the values have no spread, i.e., for a single value or identical
+/// values, the rules fall back to the magnitude of the values (or one for
+/// zeros) in place of the standard deviation, so the bandwidth is positive.
+///
+/// # Panics
+///
+/// Panics if `values` is empty.
+pub fn bandwidth(values: &[f64], weights: Option<&[f64]>, rule: Bandwidth) -> f64 {
+    assert!(!values.is_empty(), "bandwidth requires at least one value");
+    let (_, std, n) = weighted_moments(values, weights);
+    let fallback = || if values[0] != 0. { values[0].abs() } else { 1. };
+    let silverman = || {
+        let spread = std.min(iqr(values, weights) / 1.34);
+        let spread = if spread > 0. {
+            spread
+        } else if std > 0. {
+            std
+        } else {
+            fallback()
+        };
+        0.9 * spread * n.powf(-0.2)
+    };
+    match rule {
+        Bandwidth::Fixed(h) => h,
+        Bandwidth::Scott => 1.06 * if std > 0. { std } else { fallback() } * n.powf(-0.2),
+        Bandwidth::Silverman => silverman(),
+        Bandwidth::Isj => isj(values, weights, n).unwrap_or_else(silverman),
+    }
+}
+
+/// Computes the coefficients `a_k = 2·Σ x_j·cos(πk(2j+1)/2N)` of the
+/// type-II discrete cosine transform for `k ≥ 1`. The length of `values` needs
+/// to be a power of two.
+fn dct(values: &[f64]) -> Vec<f64> {
+    let n = values.len();
+    let mut reordered = values
+        .iter()
+        .step_by(2)
+        .chain(values.iter().skip(1).step_by(2).rev())
+        .map(|&x| Complex::new(x, 0.))
+        .collect::<Vec<_>>();
+    Fft::new(n, false).process(&mut reordered);
+    (1..n)
+        .map(|k| (Complex::from_angle(-PI * k as f64 / (2 * n) as f64) * reordered[k]).re * 2.)
+        .collect()
+}
+
+/// The fixed-point equation `t = ξγ^[l](t)` of the Improved Sheather–Jones
+/// algorithm, rearranged so that its root is the optimal squared bandwidth.
+fn isj_fixed_point(t: f64, n: f64, squared_indices: &[f64], coefficients: &[f64]) -> f64 {
+    let functional = |s: i32, time: f64| {
+        2. * PI.powi(2 * s)
+            * squared_indices
+                .iter()
+                .zip(coefficients)
+                .map(|(&i, &a)| i.powi(s) * a * (-i * PI * PI * time).exp())
+                .sum::<f64>()
+    };
+
+    let l = 7;
+    let mut f = functional(l, t);
+    for s in (2..l).rev() {
+        let k0 = (1..2 * s).step_by(2).product::<i32>() as f64 / (2. * PI).sqrt();
+        let c = (1. + 0.5f64.powf(s as f64 + 0.5)) / 3.;
+        let time = (2. * c * k0 / n / f).powf(2. / (3. + 2. * s as f64));
+        f = functional(s, time);
+    }
+    t - (2. * n * PI.sqrt() * f).powf(-0.4)
+}
+
+/// Selects the bandwidth with the Improved Sheather–Jones algorithm. Returns
+/// `None` if no bandwidth could be determined.
+fn isj(values: &[f64], weights: Option<&[f64]>, n: f64) -> Option<f64> {
+    let grid_size = 1 << 14;
+    let min = values.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+    let max = values.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+    let range = max - min;
+    if range <= 0. || !range.is_finite() {
+        return None;
+    }
+    let lower = min - range / 10.;
+    let range = 1.2 * range;
+
+    let mut histogram = vec![0.; grid_size];
+    for (i, &x) in values.iter().enumerate() {
+        let bin = (((x - lower) / range) * grid_size as f64) as usize;
+        histogram[bin.min(grid_size - 1)] += weights.map_or(1., |w| w[i]);
+    }
+    let total = histogram.iter().sum::<f64>();
+    histogram.iter_mut().for_each(|h| *h /= total);
+
+    let coefficients = dct(&histogram)
+        .iter()
+        .map(|a| (a / 2.).powi(2))
+        .collect::<Vec<_>>();
+    let squared_indices = (1..grid_size).map(|k| (k * k) as f64).collect::<Vec<_>>();
+    let equation = |t| isj_fixed_point(t, n, &squared_indices, &coefficients);
+
+    // Find the root by bisection, widening the search interval if needed
+    let mut low = 0.;
+    let mut high = 0.1;
+    while equation(high) < 0. {
+        high *= 2.;
+        if high > 10. {
+            return None;
+        }
+    }
+    for _ in 0..50 {
+        let mid = 0.5 * (low + high);
+        if equation(mid) < 0. {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Some(high.sqrt() * range)
+}
+
+/// Evaluates the density by summing the kernels of all points directly.
+fn direct_density(points: &[(f64, f64)], grid: &[f64], kernel: Kernel, h: f64) -> Vec<f64> {
+    grid.iter()
+        .map(|&x| {
+            points
+                .iter()
+                .map(|&(p, w)| w * kernel.evaluate(x - p, h))
+                .sum()
+        })
+        .collect()
+}
+
+/// Evaluates the density by linearly binning the points onto a fine regular
+/// mesh, convolving with the kernel via FFT and interpolating the result at
+/// the grid points.
+fn binned_density(points: &[(f64, f64)], grid: &[f64], kernel: Kernel, h: f64) -> Vec<f64> {
+    let radius = kernel.support(h);
+    let lower = grid.iter().fold(f64::INFINITY, |a, &b| a.min(b)) - radius;
+    let upper = grid.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b)) + radius;
+
+    let bins = ((upper - lower) / (h / 16.)).ceil().clamp(1024., 65536.) as usize;
+    let dx = (upper - lower) / (bins - 1) as f64;
+    let kernel_bins = ((radius / dx).ceil() as usize).min(bins - 1);
+    let size = (bins + kernel_bins).next_power_of_two();
+
+    let mut counts = vec![0.; size];
+    for &(p, w) in points {
+        if !(lower..=upper).contains(&p) {
+            continue;
+        }
+        let t = (p - lower) / dx;
+        let i = (t.floor() as usize).min(bins - 1);
+        let fraction = t - i as f64;
+        counts[i] += w * (1. - fraction);
+        if i + 1 < bins {
+            counts[i + 1] += w * fraction;
+        }
+    }
+
+    let mut kernel_values = vec![0.; size];
+    for j in 0..=kernel_bins {
+        let value = kernel.evaluate(j as f64 * dx, h);
+        kernel_values[j] = value;
+        if j > 0 {
+            kernel_values[size - j] = value;
+        }
+    }
+
+    let density = circular_convolve(&counts, &kernel_values);
+    grid.iter()
+        .map(|&x| {
+            let t = (x - lower) / dx;
+            let i = (t.floor() as usize).min(bins - 2);
+            let fraction = t - i as f64;
+            density[i] * (1. - fraction) + density[i + 1] * fraction
+        })
+        .collect()
+}
+
+/// Computes a kernel density estimate of `values` and evaluates it at the
+/// points of `grid`.
+///
+/// The density is normalized to integrate to one. If boundaries are given,
+/// the data is reflected at them to avoid a loss of probability mass beyond
+/// the boundary, and the density is zero outside of them. For large inputs,
+/// the density is computed with binned FFT convolution. NaN values make the
+/// density NaN.
+///
+/// # Panics
+///
+/// Panics if `values` is empty.
+pub fn kde(values: &[f64], grid: &[f64], options: &KdeOptions) -> Vec<f64> {
+    assert!(!values.is_empty(), "kde requires at least one value");
+    let h = bandwidth(values, options.weights, options.bandwidth);
+    let weight = |i: usize| options.weights.map_or(1., |w| w[i]);
+    let total = (0..values.len()).map(weight).sum::<f64>();
+
+    let mut points = values
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| (x, weight(i)))
+        .collect::<Vec<_>>();
+    let reflected = [options.lower_bound, options.upper_bound]
+        .iter()
+        .flatten()
+        .flat_map(|&bound| points.iter().map(move |&(x, w)| (2. * bound - x, w)))
+        .collect::<Vec<_>>();
+    points.extend(reflected);
+
+    let density = if points.len() * grid.len() <= DIRECT_LIMIT {
+        direct_density(&points, grid, options.kernel, h)
+    } else {
+        binned_density(&points, grid, options.kernel, h)
+    };
+
+    density
+        .iter()
+        .zip(grid)
+        .map(|(&d, &x)| {
+            let inside = options.lower_bound.is_none_or(|a| x >= a)
+                && options.upper_bound.is_none_or(|b| x <= b);
+            // Clip negative rounding errors of the FFT but keep NaN
+            if inside && (d >= 0. || d.is_nan()) {
+                d / total
+            } else {
+                0.
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    /// Generates pseudo-random standard normally distributed values.
+    pub(crate) fn normal_sample(n: usize, seed: u64) -> Vec<f64> {
+        let mut state = seed;
+        let mut uniform = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+        };
+        (0..n)
+            .map(|_| (-2. * uniform().ln()).sqrt() * (2. * PI * uniform()).cos())
+            .collect()
+    }
+
+    fn linspace(start: f64, end: f64, n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| start + (end - start) * i as f64 / (n - 1) as f64)
+            .collect()
+    }
+
+    fn integrate(grid: &[f64], density: &[f64]) -> f64 {
+        grid.windows(2)
+            .zip(density.windows(2))
+            .map(|(x, y)| 0.5 * (x[1] - x[0]) * (y[0] + y[1]))
+            .sum()
+    }
+
+    #[test]
+    fn test_single_point() {
+        let options = KdeOptions {
+            bandwidth: Bandwidth::Fixed(1.),
+            ..Default::default()
+        };
+        assert_abs_diff_eq!(
+            kde(&[0.], &[0.], &options)[0],
+            1. / (2. * PI).sqrt(),
+            epsilon = 1e-15
+        );
+    }
+
+    #[test]
+    fn test_kernels_normalized() {
+        let grid = linspace(-5., 5., 20001);
+        for kernel in [
+            Kernel::Gaussian,
+            Kernel::Epanechnikov,
+            Kernel::Uniform,
+            Kernel::Triangular,
+            Kernel::Biweight,
+            Kernel::Triweight,
+            Kernel::Cosine,
+        ] {
+            let options = KdeOptions {
+                kernel,
+                bandwidth: Bandwidth::Fixed(0.5),
+                ..Default::default()
+            };
+            let density = kde(&[-1., 0., 1.5], &grid, &options);
+            assert_abs_diff_eq!(integrate(&grid, &density), 1., epsilon = 1e-3);
+
+            let variance = grid
+                .iter()
+                .zip(&density)
+                .map(|(x, d)| x * x * d * (grid[1] - grid[0]))
+                .sum::<f64>();
+            // The variance of the density is the data variance plus h²
+            let data_variance = (1. + 0. + 2.25) / 3. - (0.5f64 / 3.).powi(2);
+            let mean = 0.5 / 3.;
+            assert_abs_diff_eq!(variance - mean * mean, data_variance + 0.25, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_binned_matches_direct() {
+        let values = normal_sample(1000, 1);
+        let grid = linspace(-4., 4., 101);
+        let points = values.iter().map(|&x| (x, 1.)).collect::<Vec<_>>();
+        for kernel in [Kernel::Gaussian, Kernel::Epanechnikov] {
+            let direct = direct_density(&points, &grid, kernel, 0.3);
+            let binned = binned_density(&points, &grid, kernel, 0.3);
+            for (a, b) in direct.iter().zip(&binned) {
+                assert_abs_diff_eq!(a / 1000., b / 1000., epsilon = 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reflection() {
+        let options = KdeOptions {
+            bandwidth: Bandwidth::Fixed(1.),
+            lower_bound: Some(0.),
+            ..Default::default()
+        };
+        let density = kde(&[0.], &[-1., 0., 1.], &options);
+        assert_eq!(density[0], 0.);
+        assert_abs_diff_eq!(density[1], 2. / (2. * PI).sqrt(), epsilon = 1e-15);
+
+        let grid = linspace(0., 10., 2001);
+        let density = kde(&[0.2, 0.5, 1.], &grid, &options);
+        assert_abs_diff_eq!(integrate(&grid, &density), 1., epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_weights() {
+        let options = KdeOptions {
+            bandwidth: Bandwidth::Fixed(1.),
+            weights: Some(&[1., 3.]),
+            ..Default::default()
+        };
+        let weighted = kde(&[0., 1.], &[0.5, 2.], &options);
+        let options = KdeOptions {
+            bandwidth: Bandwidth::Fixed(1.),
+            ..Default::default()
+        };
+        let repeated = kde(&[0., 1., 1., 1.], &[0.5, 2.], &options);
+        assert_abs_diff_eq!(weighted[0], repeated[0], epsilon = 1e-15);
+        assert_abs_diff_eq!(weighted[1], repeated[1], epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_bandwidth_rules() {
+        let values = (1..=10).map(f64::from).collect::<Vec<_>>();
+        let std = (55f64 / 6.).sqrt();
+        assert_abs_diff_eq!(
+            bandwidth(&values, None, Bandwidth::Scott),
+            1.06 * std * 10f64.powf(-0.2),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            bandwidth(&values, None, Bandwidth::Silverman),
+            0.9 * std * 10f64.powf(-0.2),
+            epsilon = 1e-12
+        );
+        assert_eq!(bandwidth(&values, None, Bandwidth::Fixed(0.3)), 0.3);
+    }
+
+    #[test]
+    fn test_bandwidth_without_spread() {
+        for rule in [Bandwidth::Scott, Bandwidth::Silverman, Bandwidth::Isj] {
+            for values in [&[2.][..], &[-2., -2., -2.], &[0., 0.]] {
+                let h = bandwidth(values, None, rule);
+                assert!(h > 0. && h.is_finite(), "{rule:?} {values:?}: {h}");
+                let density = kde(values, &[values[0]], &KdeOptions::default());
+                assert!(density[0] > 0. && density[0].is_finite());
+            }
+        }
+        assert_abs_diff_eq!(
+            bandwidth(&[2.], None, Bandwidth::Scott),
+            1.06 * 2.,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_nan() {
+        let options = KdeOptions {
+            bandwidth: Bandwidth::Fixed(1.),
+            ..Default::default()
+        };
+        assert!(kde(&[0., 1., f64::NAN], &[0., 1.], &options)
+            .iter()
+            .all(|d| d.is_nan()));
+    }
+
+    #[test]
+    #[should_panic(expected = "kde requires at least one value")]
+    fn test_empty() {
+        kde(&[], &[0.], &KdeOptions::default());
+    }
+
+    #[test]
+    fn test_isj() {
+        // For normally distributed data, ISJ should be close to the normal
+        // reference rule.
+        let values = normal_sample(2000, 7);
+        let isj = bandwidth(&values, None, Bandwidth::Isj);
+        let scott = bandwidth(&values, None, Bandwidth::Scott);
+        assert!((isj / scott - 1.).abs() < 0.25, "{isj} vs. {scott}");
+
+        // For bimodal data, ISJ should choose a much smaller bandwidth.
+        let bimodal = values
+            .iter()
+            .enumerate()
+            .map(|(i, x)| x + if i % 2 == 0 { -6. } else { 6. })
+            .collect::<Vec<_>>();
+        let isj = bandwidth(&bimodal, None, Bandwidth::Isj);
+        let scott = bandwidth(&bimodal, None, Bandwidth::Scott);
+        assert!(isj < 0.5 * scott, "{isj} vs. {scott}");
+    }
+
+    #[test]
+    fn test_dct() {
+        let values = [1., 2., 0., -1.];
+        let n = values.len();
+        let expected = (1..n).map(|k| {
+            2. * values
+                .iter()
+                .enumerate()
+                .map(|(j, x)| x * (PI * k as f64 * (2 * j + 1) as f64 / (2 * n) as f64).cos())
+                .sum::<f64>()
+        });
+        for (a, b) in dct(&values).iter().zip(expected) {
+            assert_abs_diff_eq!(*a, b, epsilon = 1e-12);
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use crate::fft::circular_convolve_2d;
+use std::f64::consts::PI;
+
+/// The Gaussian kernel is cut off at this many standard deviations along
+/// each axis.
+const CUTOFF: f64 = 5.;
+
+/// A rule for selecting the bandwidth matrix of a two-dimensional kernel
+/// density estimate.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BandwidthMatrix {
+    /// Scott's rule: the sample covariance scaled by `n^(-1/3)`.
+    Scott,
+    /// Silverman's rule: the sample covariance scaled by `(n(d+2)/4)^(-2/(d+4))`
+    /// which coincides with Scott's rule in two dimensions.
+    Silverman,
+    /// A fixed bandwidth matrix, i.e., the covariance matrix of the kernel.
+    Fixed([[f64; 2]; 2]),
+}
+
+/// Selects the bandwidth matrix for a two-dimensional kernel density estimate
+/// of the points `(x, y)`. The bandwidth matrix is the covariance matrix of
+/// the Gaussian kernel.
+///
+/// Returns `None` if the matrix is not positive definite, e.g., for a
+/// fixed matrix that is singular or for points whose covariance is singular
+/// because they lie on a line or there are fewer than three of them.
+pub fn bandwidth_matrix(
+    x: &[f64],
+    y: &[f64],
+    weights: Option<&[f64]>,
+    rule: BandwidthMatrix,
+) -> Option<[[f64; 2]; 2]> {
+    let weight = |i: usize| weights.map_or(1., |w| w[i]);
+    let total = (0..x.len()).map(weight).sum::<f64>();
+    let total_squared = (0..x.len()).map(|i| weight(i).powi(2)).sum::<f64>();
+    let n = total * total / total_squared;
+
+    let mean = |values: &[f64]| (0..x.len()).map(|i| weight(i) * values[i]).sum::<f64>() / total;
+    let (mean_x, mean_y) = (mean(x), mean(y));
+    let covariance = |a: &[f64], mean_a: f64, b: &[f64], mean_b: f64| {
+        (0..x.len())
+            .map(|i| weight(i) * (a[i] - mean_a) * (b[i] - mean_b))
+            .sum::<f64>()
+            / total
+            * n
+            / (n - 1.)
+    };
+    let cov_xx = covariance(x, mean_x, x, mean_x);
+    let cov_xy = covariance(x, mean_x, y, mean_y);
+    let cov_yy = covariance(y, mean_y, y, mean_y);
+
+    let factor = match rule {
+        BandwidthMatrix::Fixed(matrix) => return positive_definite(matrix),
+        BandwidthMatrix::Scott => n.powf(-1. / 3.),
+        BandwidthMatrix::Silverman => (n * (2. + 2.) / 4.).powf(-2. / (2. + 4.)),
+    };
+    positive_definite([
+        [factor * cov_xx, factor * cov_xy],
+        [factor * cov_xy, factor * cov_yy],
+    ])
+}
+
+/// Returns the symmetric matrix if it is positive definite, treating a
+/// determinant that vanishes up to rounding errors as zero.
+fn positive_definite(matrix: [[f64; 2]; 2]) -> Option<[[f64; 2]; 2]> {
+    let [[a, b], [c, d]] = matrix;
+    let det = a * d - b * c;
+    (a > 0. && d > 0. && det > 1e-12 * a * d && det.is_finite()).then_some(matrix)
+}
+
+/// Computes a two-dimensional Gaussian kernel density estimate of the points
+/// `(x, y)` and evaluates it on the regular grid spanned by `x_grid` and
+/// `y_grid`, which both need to be ascending, evenly spaced and contain at
+/// least two points.
+///
+/// The points are linearly binned onto the grid and convolved with the kernel
+/// via FFT. The result is arranged in a flat array in the same layout that
+/// [`contour`](crate::contour) expects for z values, i.e., row by row with
+/// one row per y coordinate. The kernel is cut off at five standard
+/// deviations, but at most at the extent of the grid, so points that lie
+/// farther than that outside of the grid are ignored.
+///
+/// Returns `None` if the bandwidth matrix is not positive definite, see
+/// [`bandwidth_matrix`].
+pub fn kde_2d(
+    x: &[f64],
+    y: &[f64],
+    weights: Option<&[f64]>,
+    x_grid: &[f64],
+    y_grid: &[f64],
+    bandwidth: BandwidthMatrix,
+) -> Option<Vec<f64>> {
+    assert_eq!(x.len(), y.len());
+    let h = bandwidth_matrix(x, y, weights, bandwidth)?;
+    let weight = |i: usize| weights.map_or(1., |w| w[i]);
+    let total = (0..x.len()).map(weight).sum::<f64>();
+
+    let (nx, ny) = (x_grid.len(), y_grid.len());
+    let dx = (x_grid[nx - 1] - x_grid[0]) / (nx - 1) as f64;
+    let dy = (y_grid[ny - 1] - y_grid[0]) / (ny - 1) as f64;
+
+    // Extend the grid by the kernel radius in both directions, but by no more
+    // than the size of the grid to bound the memory for wide kernels
+    let kx = ((CUTOFF * h[0][0].sqrt() / dx).ceil() as usize).clamp(1, nx);
+    let ky = ((CUTOFF * h[1][1].sqrt() / dy).ceil() as usize).clamp(1, ny);
+    let cols = nx + 2 * kx;
+    let rows = ny + 2 * ky;
+    let x0 = x_grid[0] - kx as f64 * dx;
+    let y0 = y_grid[0] - ky as f64 * dy;
+    let size_x = (cols + kx).next_power_of_two();
+    let size_y = (rows + ky).next_power_of_two();
+
+    let mut counts = vec![0.; size_x * size_y];
+    for i in 0..x.len() {
+        let tx = (x[i] - x0) / dx;
+        let ty = (y[i] - y0) / dy;
+        if !(0. ..(cols - 1) as f64).contains(&tx) || !(0. ..(rows - 1) as f64).contains(&ty) {
+            continue;
+        }
+        let (ix, iy) = (tx as usize, ty as usize);
+        let (fx, fy) = (tx - ix as f64, ty - iy as f64);
+        let w = weight(i);
+        counts[iy * size_x + ix] += w * (1. - fx) * (1. - fy);
+        counts[iy * size_x + ix + 1] += w * fx * (1. - fy);
+        counts[(iy + 1) * size_x + ix] += w * (1. - fx) * fy;
+        counts[(iy + 1) * size_x + ix + 1] += w * fx * fy;
+    }
+
+    let det = h[0][0] * h[1][1] - h[0][1] * h[1][0];
+    let inverse = [
+        [h[1][1] / det, -h[0][1] / det],
+        [-h[1][0] / det, h[0][0] / det],
+    ];
+    let normalization = 1. / (2. * PI * det.sqrt());
+    let mut kernel = vec![0.; size_x * size_y];
+    for j in -(ky as isize)..=ky as isize {
+        for i in -(kx as isize)..=kx as isize {
+            let (u, v) = (i as f64 * dx, j as f64 * dy);
+            let exponent =
+                inverse[0][0] * u * u + 2. * inverse[0][1] * u * v + inverse[1][1] * v * v;
+            let row = j.rem_euclid(size_y as isize) as usize;
+            let col = i.rem_euclid(size_x as isize) as usize;
+            kernel[row * size_x + col] = normalization * (-0.5 * exponent).exp();
+        }
+    }
+
+    let density = circular_convolve_2d(&counts, &kernel, size_y, size_x);
+    let z = (0..ny)
+        .flat_map(|j| (0..nx).map(move |i| (j, i)))
+        .map(|(j, i)| (density[(j + ky) * size_x + i + kx] / total).max(0.))
+        .collect();
+    Some(z)
+}
+
+/// Converts probability mass fractions into the density levels that enclose
+/// them. For each fraction `p` (e.g. `0.68` or `0.95`), the returned level is
+/// the largest density such that the region where the density exceeds it
+/// contains the fraction `p` of the total mass.
+///
+/// The density values `z` are expected to be evaluated on a regular grid,
+/// for example, by [`kde_2d`]. Without density values, the levels are NaN.
+pub fn density_levels(z: &[f64], fractions: &[f64]) -> Vec<f64> {
+    if z.is_empty() {
+        return vec![f64::NAN; fractions.len()];
+    }
+    let mut sorted = z.to_vec();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    let total = sorted.iter().sum::<f64>();
+
+    let mut cumulative = Vec::with_capacity(sorted.len());
+    let mut sum = 0.;
+    for &value in &sorted {
+        sum += value;
+        cumulative.push(sum / total);
+    }
+
+    fractions
+        .iter()
+        .map(|&p| {
+            let index = cumulative.partition_point(|&c| c < p);
+            sorted[index.min(sorted.len() - 1)]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn linspace(start: f64, end: f64, n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| start + (end - start) * i as f64 / (n - 1) as f64)
+            .collect()
+    }
+
+    fn direct(x: &[f64], y: &[f64], h: [[f64; 2]; 2], px: f64, py: f64) -> f64 {
+        let det = h[0][0] * h[1][1] - h[0][1] * h[1][0];
+        x.iter()
+            .zip(y)
+            .map(|(&xi, &yi)| {
+                let (u, v) = (px - xi, py - yi);
+                let exponent = (h[1][1] * u * u - 2. * h[0][1] * u * v + h[0][0] * v * v) / det;
+                (-0.5 * exponent).exp() / (2. * PI * det.sqrt())
+            })
+            .sum::<f64>()
+            / x.len() as f64
+    }
+
+    #[test]
+    fn test_single_point_on_grid() {
+        let h = [[1., 0.5], [0.5, 2.]];
+        let x_grid = linspace(-3., 3., 25);
+        let y_grid = linspace(-4., 4., 33);
+        let z = kde_2d(
+            &[0.],
+            &[0.],
+            None,
+            &x_grid,
+            &y_grid,
+            BandwidthMatrix::Fixed(h),
+        )
+        .unwrap();
+        assert_eq!(z.len(), 25 * 33);
+        for (j, &py) in y_grid.iter().enumerate() {
+            for (i, &px) in x_grid.iter().enumerate() {
+                assert_abs_diff_eq!(
+                    z[j * 25 + i],
+                    direct(&[0.], &[0.], h, px, py),
+                    epsilon = 1e-12
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_direct() {
+        let x = [0.1, 0.7, -1.3, 2.2, 0.4];
+        let y = [1.0, -0.3, 0.2, 0.9, -1.1];
+        let h = bandwidth_matrix(&x, &y, None, BandwidthMatrix::Scott).unwrap();
+        let x_grid = linspace(-6., 7., 131);
+        let y_grid = linspace(-6., 6., 121);
+        let z = kde_2d(&x, &y, None, &x_grid, &y_grid, BandwidthMatrix::Scott).unwrap();
+        for (j, &py) in y_grid.iter().enumerate().step_by(10) {
+            for (i, &px) in x_grid.iter().enumerate().step_by(10) {
+                assert_abs_diff_eq!(z[j * 131 + i], direct(&x, &y, h, px, py), epsilon = 2e-3);
+            }
+        }
+        let mass = z.iter().sum::<f64>() * 0.1 * 0.1;
+        assert_abs_diff_eq!(mass, 1., epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_bandwidth_matrix() {
+        let x = [0., 1., 2., 3.];
+        let y = [1., 0., 3., 2.];
+        let factor = 4f64.powf(-1. / 3.);
+        let expected = [
+            [factor * 5. / 3., factor * 1.],
+            [factor * 1., factor * 5. / 3.],
+        ];
+        let scott = bandwidth_matrix(&x, &y, None, BandwidthMatrix::Scott).unwrap();
+        let silverman = bandwidth_matrix(&x, &y, None, BandwidthMatrix::Silverman).unwrap();
+        for i in 0..2 {
+            for j in 0..2 {
+                assert_abs_diff_eq!(scott[i][j], expected[i][j], epsilon = 1e-12);
+                assert_abs_diff_eq!(silverman[i][j], expected[i][j], epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_singular_bandwidth_matrix() {
+        let scott = BandwidthMatrix::Scott;
+        // Collinear, constant and too few points
+        assert_eq!(
+            bandwidth_matrix(&[0., 1., 2.], &[0., 2., 4.], None, scott),
+            None
+        );
+        assert_eq!(
+            bandwidth_matrix(&[1., 1., 1.], &[0., 2., 5.], None, scott),
+            None
+        );
+        assert_eq!(bandwidth_matrix(&[0., 1.], &[0., 2.], None, scott), None);
+        assert_eq!(bandwidth_matrix(&[0.], &[0.], None, scott), None);
+        let singular = BandwidthMatrix::Fixed([[1., 1.], [1., 1.]]);
+        assert_eq!(bandwidth_matrix(&[0.], &[0.], None, singular), None);
+
+        let grid = linspace(-1., 1., 5);
+        assert_eq!(
+            kde_2d(&[0., 1.], &[0., 1.], None, &grid, &grid, scott),
+            None
+        );
+    }
+
+    #[test]
+    fn test_wide_kernel() {
+        // The kernel is truncated at the extent of the grid
+        let grid = linspace(-1., 1., 3);
+        let h = [[1e6, 0.], [0., 1e6]];
+        let z = kde_2d(&[0.], &[0.], None, &grid, &grid, BandwidthMatrix::Fixed(h)).unwrap();
+        for (&value, (i, j)) in z
+            .iter()
+            .zip((0..3).flat_map(|j| (0..3).map(move |i| (i, j))))
+        {
+            let expected = direct(&[0.], &[0.], h, grid[i], grid[j]);
+            assert_abs_diff_eq!(value, expected, epsilon = 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_density_levels() {
+        assert_eq!(
+            density_levels(&[1., 4., 3., 2.], &[0.4, 0.7, 1.]),
+            [4., 3., 1.]
+        );
+        assert!(density_levels(&[], &[0.5, 0.9]).iter().all(|x| x.is_nan()));
+
+        // For a standard normal distribution, the 1σ ellipse contains
+        // 1 - exp(-1/2) of the mass.
+        let grid = linspace(-6., 6., 121);
+        let z = kde_2d(
+            &[0.],
+            &[0.],
+            None,
+            &grid,
+            &grid,
+            BandwidthMatrix::Fixed([[1., 0.], [0., 1.]]),
+        )
+        .unwrap();
+        let level = density_levels(&z, &[1. - (-0.5f64).exp()])[0];
+        assert_abs_diff_eq!(level, (-0.5f64).exp() / (2. * PI), epsilon = 2e-3);
+    }
+}
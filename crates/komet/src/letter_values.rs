@@ -0,0 +1,168 @@
+use crate::special::normal_quantile;
+
+/// A rule for choosing the number of letter values of a letter-value plot
+/// as proposed by Hofmann, Wickham, and Kafadar (2017).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LetterValueDepth {
+    /// `⌊log₂ n⌋ - 3` letter values, so that about 5–8 values lie beyond
+    /// the last level, similar to a box plot of a normal sample.
+    Tukey,
+    /// As many letter values as necessary for the given proportion of the
+    /// values (e.g., 0.007) to lie beyond the last level.
+    Proportion(f64),
+    /// Only letter values whose confidence intervals at the given
+    /// significance level (e.g., 0.05) do not overlap with those of the
+    /// neighboring letter values.
+    Trustworthy(f64),
+    /// The maximum number of `⌊log₂ n⌋ + 1` letter values.
+    Full,
+    /// A fixed number of letter values.
+    Fixed(usize),
+}
+
+/// A pair of letter values at the same depth.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LetterValue {
+    /// The depth, i.e., the rank counted from the nearer end of the sorted
+    /// values, which is an integer or half-integer.
+    pub depth: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LetterValues {
+    /// The letter values from the median (whose lower and upper values
+    /// coincide) outwards over the fourths, eighths, and so on.
+    pub levels: Vec<LetterValue>,
+    /// The values beyond the last level.
+    pub outliers: Vec<f64>,
+}
+
+/// Computes the statistics needed to draw a letter-value plot (also known as
+/// boxen plot) of the given values. The letter values are the median, the
+/// fourths, the eighths, and so on, computed from order statistics at the
+/// depths `d₁ = (n + 1)/2` and `dᵢ₊₁ = (1 + ⌊dᵢ⌋)/2`.
+///
+/// # Panics
+///
+/// Panics if `values` is empty.
+pub fn letter_values(values: &[f64], depth: LetterValueDepth) -> LetterValues {
+    assert!(
+        !values.is_empty(),
+        "letter values require at least one value"
+    );
+    let mut sorted_values = values.to_vec();
+    sorted_values.sort_by(f64::total_cmp);
+
+    let n = values.len();
+    let log_n = (n as f64).log2().floor();
+    let max_levels = log_n as usize + 1;
+    let levels = match depth {
+        LetterValueDepth::Tukey => log_n - 3.,
+        LetterValueDepth::Proportion(p) => log_n - (n as f64 * p).log2().floor() + 1.,
+        LetterValueDepth::Trustworthy(alpha) => {
+            let z = normal_quantile(1. - alpha / 2.);
+            ((n as f64).log2() - (2. * z * z).log2()).floor() + 1.
+        }
+        LetterValueDepth::Full => max_levels as f64,
+        LetterValueDepth::Fixed(k) => k as f64,
+    };
+    let levels = (levels.max(1.) as usize).min(max_levels);
+
+    // The order statistic at a (half-)integer depth counted from one end
+    let at_depth = |depth: f64, values: &mut dyn Iterator<Item = &f64>| {
+        let skipped = depth.floor() as usize - 1;
+        let mut values = values.skip(skipped);
+        let value = *values.next().unwrap();
+        if depth.fract() == 0. {
+            value
+        } else {
+            0.5 * (value + values.next().unwrap())
+        }
+    };
+
+    let mut depth = (n as f64 + 1.) / 2.;
+    let levels = (0..levels)
+        .map(|_| {
+            let level = LetterValue {
+                depth,
+                lower: at_depth(depth, &mut sorted_values.iter()),
+                upper: at_depth(depth, &mut sorted_values.iter().rev()),
+            };
+            depth = (1. + depth.floor()) / 2.;
+            level
+        })
+        .collect::<Vec<_>>();
+
+    let last = levels.last().unwrap();
+    LetterValues {
+        outliers: values
+            .iter()
+            .filter(|&&x| x < last.lower || x > last.upper)
+            .cloned()
+            .collect(),
+        levels,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depths() {
+        let values = (1..=100).map(f64::from).collect::<Vec<_>>();
+        let result = letter_values(&values, LetterValueDepth::Full);
+        let depths = result.levels.iter().map(|l| l.depth).collect::<Vec<_>>();
+        assert_eq!(depths, [50.5, 25.5, 13., 7., 4., 2.5, 1.5]);
+        for level in &result.levels {
+            assert_eq!(level.lower, level.depth);
+            assert_eq!(level.upper, 101. - level.depth);
+        }
+        assert_eq!(result.outliers, [1., 100.]);
+    }
+
+    #[test]
+    fn test_rules() {
+        let values = (0..1000)
+            .map(|i| (i * 37 % 1000) as f64)
+            .collect::<Vec<_>>();
+        let count = |depth| letter_values(&values, depth).levels.len();
+        assert_eq!(count(LetterValueDepth::Tukey), 6);
+        assert_eq!(count(LetterValueDepth::Proportion(0.007)), 8);
+        assert_eq!(count(LetterValueDepth::Trustworthy(0.05)), 8);
+        assert_eq!(count(LetterValueDepth::Full), 10);
+        assert_eq!(count(LetterValueDepth::Fixed(3)), 3);
+        assert_eq!(count(LetterValueDepth::Fixed(30)), 10);
+
+        let result = letter_values(&values, LetterValueDepth::Tukey);
+        let last = result.levels.last().unwrap();
+        assert_eq!((last.depth, last.lower, last.upper), (16.5, 15.5, 983.5));
+        assert_eq!(result.outliers.len(), 32);
+        assert!(result
+            .outliers
+            .iter()
+            .all(|&x| x < last.lower || x > last.upper));
+    }
+
+    #[test]
+    fn test_small() {
+        let result = letter_values(&[3., 1., 2.], LetterValueDepth::Tukey);
+        assert_eq!(
+            result.levels,
+            [LetterValue {
+                depth: 2.,
+                lower: 2.,
+                upper: 2.
+            }]
+        );
+        assert_eq!(result.outliers, [3., 1.]);
+    }
+
+    #[test]
+    #[should_panic(expected = "letter values require at least one value")]
+    fn test_empty() {
+        letter_values(&[], LetterValueDepth::Tukey);
+    }
+}
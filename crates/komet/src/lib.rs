@@ -1,9 +1,63 @@
+mod binned_statistic;
 mod boxplot;
 mod contour;
+mod convolve;
+mod describe;
+mod ecdf;
+mod fft;
+mod filter;
+mod hilbert;
 mod histogram;
+mod kde;
+mod kde_2d;
+mod letter_values;
 mod linear_system;
+mod medcouple;
+mod psd;
+mod qq;
+mod quantile;
+mod random;
+mod special;
+mod spectrum;
+mod stft;
+mod streaming;
+mod violin;
+mod window;
 
-pub use boxplot::boxplot;
+pub use binned_statistic::{binned_statistic, binned_statistic_2d, Statistic};
+pub use boxplot::{
+    boxplot, median_confidence_interval, weighted_boxplot, BoxplotOptions, BoxplotStats, Notch,
+    Whiskers,
+};
 pub use contour::contour;
-pub use histogram::histogram;
+pub use convolve::{
+    convolve, convolve_2d, correlate, correlate_2d, cross_correlation, ConvolveMethod,
+    ConvolveMode, CrossCorrelation,
+};
+pub use describe::{describe, DescribeOptions, Description, NanPolicy};
+pub use ecdf::{ecdf, Ecdf};
+pub use fft::{fft, fftn, ifft, irfft, rfft, Complex, Fft, FftNorm};
+pub use filter::{
+    filtfilt, fir_filter, iir_filter, lfilter, sosfilt, sosfiltfilt, FilterBand, FilterPadding,
+    IirFamily,
+};
+pub use hilbert::{analytic_signal, hilbert, AnalyticSignal};
+pub use histogram::{digitize, histogram, Histogram};
+pub use kde::{bandwidth, kde, Bandwidth, KdeOptions, Kernel};
+pub use kde_2d::{bandwidth_matrix, density_levels, kde_2d, BandwidthMatrix};
+pub use letter_values::{letter_values, LetterValue, LetterValueDepth, LetterValues};
 pub use linear_system::thomas_algorithm;
+pub use medcouple::medcouple;
+pub use psd::{periodogram, welch, Detrend, Psd, PsdOptions, PsdScaling};
+pub use qq::{qq, Distribution, QqOptions, QqPlot, ReferenceLine};
+pub use quantile::{
+    quantile_sorted, quantiles, select_quantiles, weighted_quantile_sorted, weighted_quantiles,
+    QuantileMethod,
+};
+pub use spectrum::{
+    angle, decibels, fftfreq, magnitude, power, rfftfreq, unwrap, DecibelReference,
+};
+pub use stft::{istft, spectrogram, stft, Spectrogram, SpectrogramScale, Stft, StftOptions};
+pub use streaming::P2Quantile;
+pub use violin::{violins, Violin, ViolinOptions, ViolinScale};
+pub use window::{window, Window};
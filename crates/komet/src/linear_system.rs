@@ -6,26 +6,42 @@
 /// Returns the solutions x of the system of linear equations.
 pub fn thomas_algorithm(a: &[&[f64]], b: &[f64]) -> Vec<f64> {
     let n = b.len();
+    let lower = (1..n).map(|i| a[i][i - 1]).collect::<Vec<_>>();
+    let diag = (0..n).map(|i| a[i][i]).collect::<Vec<_>>();
+    let upper = (1..n).map(|i| a[i - 1][i]).collect::<Vec<_>>();
+    thomas_algorithm_bands(&lower, &diag, &upper, b)
+}
+
+/// Like [`thomas_algorithm`] but with the matrix A given by its three
+/// diagonals, i.e., `lower[i] = A[i+1][i]`, `diag[i] = A[i][i]` and
+/// `upper[i] = A[i][i+1]`, so that large systems need no dense matrix.
+pub(crate) fn thomas_algorithm_bands(
+    lower: &[f64],
+    diag: &[f64],
+    upper: &[f64],
+    b: &[f64],
+) -> Vec<f64> {
+    let n = b.len();
 
     if n == 1 {
-        return vec![b[0] / a[0][0]];
+        return vec![b[0] / diag[0]];
     }
 
     let mut beta = vec![0.0; n];
     let mut gamma = vec![0.0; n];
     let mut y = vec![0.0; n];
 
-    beta[0] = a[0][0];
-    gamma[0] = a[0][1] / beta[0];
+    beta[0] = diag[0];
+    gamma[0] = upper[0] / beta[0];
     y[0] = b[0] / beta[0];
 
     for i in 1..n {
-        let d_i = a[i][i];
-        let e_i = a[i][i - 1];
+        let d_i = diag[i];
+        let e_i = lower[i - 1];
         beta[i] = d_i - e_i * gamma[i - 1];
 
         if i < n - 1 {
-            let c_i = a[i][i + 1];
+            let c_i = upper[i];
             gamma[i] = c_i / beta[i]
         }
 
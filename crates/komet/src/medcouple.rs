@@ -0,0 +1,190 @@
+use crate::quantile::{quantile_sorted, QuantileMethod};
+use std::cmp::Ordering;
+
+/// The kernel of the medcouple for a value `a` above and a value `b` below
+/// the median (both centered at the median). Pairs that both coincide with
+/// the median are resolved by the sign of `p - 1 - i - j`.
+fn kernel(plus: &[f64], minus: &[f64], i: usize, j: usize) -> f64 {
+    let (a, b) = (plus[i], minus[j]);
+    if a == b {
+        match (plus.len() - 1).cmp(&(i + j)) {
+            Ordering::Less => -1.,
+            Ordering::Equal => 0.,
+            Ordering::Greater => 1.,
+        }
+    } else {
+        (a + b) / (a - b)
+    }
+}
+
+/// Returns the k-th largest (counting from zero) entry of the kernel matrix
+/// with the algorithm by Johnson and Mizera. The matrix is decreasing along
+/// rows and columns, so only a window `left[i]..=right[i]` of each row has to
+/// be tracked, which shrinks around the k-th value in each iteration.
+fn kth_largest(plus: &[f64], minus: &[f64], k: usize) -> f64 {
+    let (p, q) = (plus.len(), minus.len());
+    let h = |i: usize, j: usize| kernel(plus, minus, i, j);
+
+    let mut left = vec![0isize; p];
+    let mut right = vec![q as isize - 1; p];
+    let mut left_total = 0;
+    let mut right_total = p * q;
+
+    while right_total - left_total > p {
+        // Weighted median of the row medians of the remaining windows
+        let mut medians = (0..p)
+            .filter(|&i| left[i] <= right[i])
+            .map(|i| {
+                let j = (left[i] + right[i]) as usize / 2;
+                (h(i, j), (right[i] - left[i] + 1) as usize)
+            })
+            .collect::<Vec<_>>();
+        medians.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let total_weight = medians.iter().map(|m| m.1).sum::<usize>();
+        let mut cumulative = 0;
+        let candidate = medians
+            .iter()
+            .find(|m| {
+                cumulative += m.1;
+                2 * cumulative >= total_weight
+            })
+            .unwrap()
+            .0;
+
+        // The last column with h > candidate in each row
+        let mut greater = vec![0isize; p];
+        let mut j = 0;
+        for i in (0..p).rev() {
+            while j < q && h(i, j) > candidate {
+                j += 1;
+            }
+            greater[i] = j as isize - 1;
+        }
+        // The first column with h < candidate in each row
+        let mut less = vec![0isize; p];
+        let mut j = q as isize - 1;
+        for (i, less) in less.iter_mut().enumerate() {
+            while j >= 0 && h(i, j as usize) < candidate {
+                j -= 1;
+            }
+            *less = j + 1;
+        }
+
+        let greater_total = greater.iter().map(|&j| (j + 1) as usize).sum::<usize>();
+        let less_total = less.iter().map(|&j| j as usize).sum::<usize>();
+        if k < greater_total {
+            right = greater;
+            right_total = greater_total;
+        } else if k >= less_total {
+            left = less;
+            left_total = less_total;
+        } else {
+            return candidate;
+        }
+    }
+
+    let mut remaining = (0..p)
+        .flat_map(|i| (left[i]..=right[i]).map(move |j| (i, j as usize)))
+        .map(|(i, j)| h(i, j))
+        .collect::<Vec<_>>();
+    remaining.sort_by(|a, b| b.total_cmp(a));
+    remaining[k - left_total]
+}
+
+/// Computes the medcouple, a robust measure of skewness between -1 and 1
+/// introduced by Brys, Hubert, and Struyf (2004). It is the median of
+/// `((xj - m) + (xi - m)) / (xj - xi)` over all pairs `xi <= m <= xj` where
+/// `m` is the median of the values.
+///
+/// The computation takes `O(n log n)` time.
+pub fn medcouple(values: &[f64]) -> f64 {
+    let mut sorted_values = values.to_vec();
+    sorted_values.sort_by(f64::total_cmp);
+    let median = quantile_sorted(&sorted_values, 0.5, QuantileMethod::Linear);
+    sorted_values.reverse();
+    let scale = 2.
+        * sorted_values
+            .iter()
+            .fold(0f64, |a, &x| a.max((x - median).abs()));
+    if scale == 0. {
+        return 0.;
+    }
+
+    let centered = sorted_values
+        .iter()
+        .map(|&x| (x - median) / scale)
+        .collect::<Vec<_>>();
+    let plus = centered
+        .iter()
+        .copied()
+        .filter(|&z| z >= 0.)
+        .collect::<Vec<_>>();
+    let minus = centered
+        .iter()
+        .copied()
+        .filter(|&z| z <= 0.)
+        .collect::<Vec<_>>();
+
+    let n = plus.len() * minus.len();
+    0.5 * (kth_largest(&plus, &minus, (n - 1) / 2) + kth_largest(&plus, &minus, n / 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+    use approx::assert_abs_diff_eq;
+
+    fn naive(values: &[f64]) -> f64 {
+        let mut sorted_values = values.to_vec();
+        sorted_values.sort_by(f64::total_cmp);
+        let median = quantile_sorted(&sorted_values, 0.5, QuantileMethod::Linear);
+        sorted_values.reverse();
+        let plus = sorted_values
+            .iter()
+            .map(|x| x - median)
+            .filter(|&z| z >= 0.)
+            .collect::<Vec<_>>();
+        let minus = sorted_values
+            .iter()
+            .map(|x| x - median)
+            .filter(|&z| z <= 0.)
+            .collect::<Vec<_>>();
+        let mut kernels = (0..plus.len())
+            .flat_map(|i| (0..minus.len()).map(move |j| (i, j)))
+            .map(|(i, j)| kernel(&plus, &minus, i, j))
+            .collect::<Vec<_>>();
+        kernels.sort_by(f64::total_cmp);
+        quantile_sorted(&kernels, 0.5, QuantileMethod::Linear)
+    }
+
+    #[test]
+    fn test_small() {
+        assert_eq!(medcouple(&[1., 2., 3., 4., 10.]), 0.);
+        assert_eq!(medcouple(&[1., 2., 3., 4., 5.]), 0.);
+        assert_eq!(medcouple(&[2., 2., 2.]), 0.);
+        assert_abs_diff_eq!(medcouple(&[1., 2., 3., 10.]), naive(&[1., 2., 3., 10.]));
+    }
+
+    #[test]
+    fn test_matches_naive() {
+        let mut rng = Rng::new(7);
+        for n in [2, 5, 10, 37, 100, 301] {
+            // Skewed values with many ties
+            let values = (0..n)
+                .map(|_| (rng.below(50) as f64).powi(2))
+                .collect::<Vec<_>>();
+            assert_abs_diff_eq!(medcouple(&values), naive(&values), epsilon = 1e-12);
+            let mirrored = values.iter().map(|x| -x).collect::<Vec<_>>();
+            assert_abs_diff_eq!(medcouple(&mirrored), -medcouple(&values), epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_skewed() {
+        let values = (1..200)
+            .map(|i| (i as f64 / 200.).ln().abs())
+            .collect::<Vec<_>>();
+        assert!(medcouple(&values) > 0.2);
+    }
+}
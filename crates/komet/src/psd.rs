@@ -0,0 +1,273 @@
+use crate::fft::{Complex, Fft};
+use crate::spectrum::{fftfreq, rfftfreq};
+use crate::window::{window, Window};
+
+/// How each segment is detrended before its spectrum is computed.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Detrend {
+    None,
+    /// Subtracts the mean.
+    Constant,
+    /// Subtracts the least-squares line.
+    Linear,
+}
+
+/// How the power of a spectral estimate is scaled.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PsdScaling {
+    /// The power spectral density in units² per unit of frequency, e.g.,
+    /// V²/Hz, whose integral over the frequencies is the mean power.
+    Density,
+    /// The power spectrum in units², e.g., V², for which a sinusoid of
+    /// amplitude `A` at the center of a frequency bin has a peak of `A²/2`
+    /// in a one-sided spectrum.
+    Spectrum,
+}
+
+/// Options for [`welch`] and [`periodogram`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PsdOptions {
+    /// The number of samples per unit of time, which determines the unit of
+    /// the frequencies.
+    pub sample_rate: f64,
+    /// The window applied to each segment in its periodic form.
+    pub window: Window,
+    /// The number of samples per segment. By default, segments have 256
+    /// samples or the length of the signal if it is shorter. Ignored by
+    /// [`periodogram`].
+    pub segment_length: Option<usize>,
+    /// The number of samples shared by consecutive segments. By default,
+    /// segments overlap by half their length. Ignored by [`periodogram`].
+    pub overlap: Option<usize>,
+    pub detrend: Detrend,
+    /// Whether to return only the non-negative frequencies, in which case the
+    /// power of the negative frequencies is added to their positive
+    /// counterparts.
+    pub one_sided: bool,
+    pub scaling: PsdScaling,
+}
+
+impl Default for PsdOptions {
+    fn default() -> Self {
+        PsdOptions {
+            sample_rate: 1.,
+            window: Window::Hann,
+            segment_length: None,
+            overlap: None,
+            detrend: Detrend::Constant,
+            one_sided: true,
+            scaling: PsdScaling::Density,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Psd {
+    /// The frequencies of the bins. Two-sided spectra use the order of the
+    /// discrete Fourier transform, i.e., the non-negative frequencies are
+    /// followed by the negative ones.
+    pub frequencies: Vec<f64>,
+    /// The power at each frequency.
+    pub power: Vec<f64>,
+}
+
+/// Estimates the power spectrum of the given values with Welch's method,
+/// i.e., by averaging the periodograms of overlapping windowed segments.
+/// Samples after the last complete segment are ignored.
+pub fn welch(values: &[f64], options: &PsdOptions) -> Psd {
+    let len = options.segment_length.unwrap_or(256).min(values.len());
+    if len == 0 {
+        return Psd {
+            frequencies: Vec::new(),
+            power: Vec::new(),
+        };
+    }
+    let overlap = options.overlap.unwrap_or(len / 2).min(len - 1);
+
+    let weights = window(options.window, len, true);
+    let scale = match options.scaling {
+        PsdScaling::Density => {
+            1. / (options.sample_rate * weights.iter().map(|w| w * w).sum::<f64>())
+        }
+        PsdScaling::Spectrum => 1. / weights.iter().sum::<f64>().powi(2),
+    };
+
+    let plan = Fft::new(len, false);
+    let bins = if options.one_sided { len / 2 + 1 } else { len };
+    let mut power = vec![0.; bins];
+    let mut buffer = vec![Complex::default(); len];
+    let mut segments = 0;
+    for start in (0..=values.len() - len).step_by(len - overlap) {
+        let segment = detrend(&values[start..start + len], options.detrend);
+        for ((x, value), weight) in buffer.iter_mut().zip(segment).zip(&weights) {
+            *x = Complex::new(value * weight, 0.);
+        }
+        plan.process(&mut buffer);
+        for (p, x) in power.iter_mut().zip(&buffer) {
+            *p += x.norm_sqr();
+        }
+        segments += 1;
+    }
+
+    let factor = scale / segments as f64;
+    power.iter_mut().for_each(|p| *p *= factor);
+    if options.one_sided {
+        // Neither the zero frequency nor the Nyquist frequency of an even
+        // length have a negative counterpart
+        let end = if len.is_multiple_of(2) {
+            bins - 1
+        } else {
+            bins
+        };
+        power[1..end].iter_mut().for_each(|p| *p *= 2.);
+    }
+
+    let frequencies = if options.one_sided {
+        rfftfreq(len, 1. / options.sample_rate)
+    } else {
+        fftfreq(len, 1. / options.sample_rate)
+    };
+
+    Psd { frequencies, power }
+}
+
+/// Estimates the power spectrum of the given values from the periodogram of
+/// the entire signal as a single segment. For the classical periodogram,
+/// [`Window::Rectangular`] is used.
+pub fn periodogram(values: &[f64], options: &PsdOptions) -> Psd {
+    welch(
+        values,
+        &PsdOptions {
+            segment_length: Some(values.len()),
+            overlap: Some(0),
+            ..*options
+        },
+    )
+}
+
+/// Removes a constant or linear trend from the values.
+pub(crate) fn detrend(values: &[f64], detrend: Detrend) -> Vec<f64> {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    match detrend {
+        Detrend::None => values.to_vec(),
+        Detrend::Constant => values.iter().map(|x| x - mean).collect(),
+        Detrend::Linear => {
+            // The positions are centered so that the line passes through the
+            // mean at the center
+            let center = (n - 1.) / 2.;
+            let (mut sxy, mut sxx) = (0., 0.);
+            for (k, y) in values.iter().enumerate() {
+                let x = k as f64 - center;
+                sxy += x * (y - mean);
+                sxx += x * x;
+            }
+            let slope = if sxx > 0. { sxy / sxx } else { 0. };
+            values
+                .iter()
+                .enumerate()
+                .map(|(k, y)| y - mean - slope * (k as f64 - center))
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use std::f64::consts::PI;
+
+    fn sine(amplitude: f64, frequency: f64, sample_rate: f64, len: usize) -> Vec<f64> {
+        (0..len)
+            .map(|k| amplitude * (2. * PI * frequency * k as f64 / sample_rate).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_periodogram() {
+        let values = sine(2., 10., 100., 100);
+        let options = PsdOptions {
+            sample_rate: 100.,
+            window: Window::Rectangular,
+            scaling: PsdScaling::Spectrum,
+            ..Default::default()
+        };
+        let result = periodogram(&values, &options);
+        assert_eq!(result.frequencies.len(), 51);
+        assert_abs_diff_eq!(result.frequencies[10], 10.);
+        assert_abs_diff_eq!(result.frequencies[50], 50.);
+        assert_abs_diff_eq!(result.power[10], 2., epsilon = 1e-12);
+        assert_abs_diff_eq!(result.power.iter().sum::<f64>(), 2., epsilon = 1e-12);
+
+        // The integral of the density is the mean power, here with bins that
+        // are 2 Hz wide
+        let result = periodogram(
+            &sine(2., 20., 200., 100),
+            &PsdOptions {
+                sample_rate: 200.,
+                scaling: PsdScaling::Density,
+                ..options
+            },
+        );
+        assert_abs_diff_eq!(result.frequencies[10], 20.);
+        assert_abs_diff_eq!(result.power[10], 1., epsilon = 1e-12);
+        assert_abs_diff_eq!(result.power.iter().sum::<f64>() * 2., 2., epsilon = 1e-12);
+
+        let result = periodogram(
+            &values,
+            &PsdOptions {
+                one_sided: false,
+                ..options
+            },
+        );
+        assert_eq!(result.frequencies.len(), 100);
+        assert_abs_diff_eq!(result.frequencies[49], 49.);
+        assert_abs_diff_eq!(result.frequencies[50], -50.);
+        assert_abs_diff_eq!(result.power[10], 1., epsilon = 1e-12);
+        assert_abs_diff_eq!(result.power[90], 1., epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_welch() {
+        // With a step of one period, all segments are identical
+        let values = sine(1., 1., 8., 64);
+        let options = PsdOptions {
+            segment_length: Some(16),
+            overlap: Some(8),
+            ..Default::default()
+        };
+        let result = welch(&values, &options);
+        let expected = periodogram(&values[..16], &options);
+        assert_eq!(result.frequencies, expected.frequencies);
+        for (a, b) in result.power.iter().zip(&expected.power) {
+            assert_abs_diff_eq!(a, b, epsilon = 1e-12);
+        }
+
+        // Odd lengths have no Nyquist bin
+        let result = welch(
+            &values,
+            &PsdOptions {
+                segment_length: Some(15),
+                ..options
+            },
+        );
+        assert_eq!(result.frequencies.len(), 8);
+        assert!(welch(&[], &options).power.is_empty());
+    }
+
+    #[test]
+    fn test_detrend() {
+        let values = (0..32).map(|k| 3. + 0.5 * k as f64).collect::<Vec<_>>();
+        let options = PsdOptions {
+            detrend: Detrend::Linear,
+            ..Default::default()
+        };
+        let result = welch(&values, &options);
+        assert!(result.power.iter().all(|p| p.abs() < 1e-20));
+
+        assert_eq!(detrend(&[1., 2., 6.], Detrend::Constant), [-2., -1., 3.]);
+        assert_eq!(detrend(&[1., 2., 6.], Detrend::None), [1., 2., 6.]);
+        assert_eq!(detrend(&[5.], Detrend::Linear), [0.]);
+    }
+}
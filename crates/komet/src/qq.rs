@@ -0,0 +1,266 @@
+use crate::quantile::{quantile_sorted, QuantileMethod};
+use crate::special::normal_quantile;
+use std::f64::consts::PI;
+
+/// A theoretical distribution for Q-Q plots in its standard form, i.e., with
+/// location 0 and scale 1. Location and scale of the sample are captured by
+/// the reference line.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Distribution {
+    Normal,
+    Exponential,
+    /// The uniform distribution on `[0, 1]`.
+    Uniform,
+    /// The log-normal distribution with the given shape `σ`, i.e., the
+    /// standard deviation of the logarithm.
+    LogNormal(f64),
+    Logistic,
+    Laplace,
+    /// The Gumbel distribution of maxima.
+    Gumbel,
+    Cauchy,
+    /// The Weibull distribution with the given shape `k`.
+    Weibull(f64),
+}
+
+impl Distribution {
+    /// Computes the quantile function (inverse cumulative distribution
+    /// function) for `p` between 0 and 1.
+    pub fn quantile(&self, p: f64) -> f64 {
+        match *self {
+            Distribution::Normal => normal_quantile(p),
+            Distribution::Exponential => -(-p).ln_1p(),
+            Distribution::Uniform => p,
+            Distribution::LogNormal(sigma) => (sigma * normal_quantile(p)).exp(),
+            Distribution::Logistic => (p / (1. - p)).ln(),
+            Distribution::Laplace if p < 0.5 => (2. * p).ln(),
+            Distribution::Laplace => -(2. - 2. * p).ln(),
+            Distribution::Gumbel => -(-p.ln()).ln(),
+            Distribution::Cauchy => (PI * (p - 0.5)).tan(),
+            Distribution::Weibull(k) => (-(-p).ln_1p()).powf(1. / k),
+        }
+    }
+}
+
+/// A method for fitting the reference line of a Q-Q plot.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ReferenceLine {
+    /// The least-squares fit of the sample on the theoretical quantiles.
+    LeastSquares,
+    /// The line through the first and third quartiles, which is robust
+    /// against outliers.
+    Quartiles,
+}
+
+/// Options for [`qq`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct QqOptions {
+    pub distribution: Distribution,
+    /// The offset `a` of the plotting positions `(i - a)/(n + 1 - 2a)` at
+    /// which the theoretical quantiles are evaluated for the ranks
+    /// `i = 1, …, n`, e.g., 0.5 (Hazen), 0.375 (Blom), or 0 (Weibull). The
+    /// offset needs to lie in `[0, 1)`, so that the positions lie strictly
+    /// between 0 and 1.
+    pub plotting_position: f64,
+    pub line: ReferenceLine,
+}
+
+impl Default for QqOptions {
+    fn default() -> Self {
+        QqOptions {
+            distribution: Distribution::Normal,
+            plotting_position: 0.5,
+            line: ReferenceLine::LeastSquares,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct QqPlot {
+    /// The theoretical quantiles in ascending order.
+    pub theoretical: Vec<f64>,
+    /// The sorted sample values.
+    pub sample: Vec<f64>,
+    /// The slope of the reference line `sample = slope·theoretical +
+    /// intercept`, which estimates the scale of the sample.
+    pub slope: f64,
+    /// The intercept of the reference line, which estimates the location of
+    /// the sample.
+    pub intercept: f64,
+    /// The correlation coefficient between the theoretical and the sample
+    /// quantiles as a measure of the goodness of fit.
+    pub r: f64,
+}
+
+/// Computes the theoretical and sample quantiles for a Q-Q plot of the given
+/// values against a distribution, together with a fitted reference line. NaN
+/// values are ignored.
+///
+/// # Panics
+///
+/// Panics if there are no values other than NaN or if the plotting position
+/// does not lie in `[0, 1)`.
+pub fn qq(values: &[f64], options: &QqOptions) -> QqPlot {
+    assert!(
+        (0. ..1.).contains(&options.plotting_position),
+        "plotting position needs to lie in [0, 1)"
+    );
+    let mut sample = values
+        .iter()
+        .copied()
+        .filter(|x| !x.is_nan())
+        .collect::<Vec<_>>();
+    assert!(!sample.is_empty(), "qq requires at least one value");
+    sample.sort_by(f64::total_cmp);
+
+    let n = sample.len() as f64;
+    let a = options.plotting_position;
+    let theoretical = (1..=sample.len())
+        .map(|i| {
+            options
+                .distribution
+                .quantile((i as f64 - a) / (n + 1. - 2. * a))
+        })
+        .collect::<Vec<_>>();
+
+    let mean_x = theoretical.iter().sum::<f64>() / n;
+    let mean_y = sample.iter().sum::<f64>() / n;
+    let (mut sxx, mut syy, mut sxy) = (0., 0., 0.);
+    for (x, y) in theoretical.iter().zip(&sample) {
+        sxx += (x - mean_x) * (x - mean_x);
+        syy += (y - mean_y) * (y - mean_y);
+        sxy += (x - mean_x) * (y - mean_y);
+    }
+
+    let (slope, intercept) = match options.line {
+        ReferenceLine::LeastSquares => {
+            let slope = sxy / sxx;
+            (slope, mean_y - slope * mean_x)
+        }
+        ReferenceLine::Quartiles => {
+            let y1 = quantile_sorted(&sample, 0.25, QuantileMethod::Linear);
+            let y3 = quantile_sorted(&sample, 0.75, QuantileMethod::Linear);
+            let x1 = options.distribution.quantile(0.25);
+            let x3 = options.distribution.quantile(0.75);
+            let slope = (y3 - y1) / (x3 - x1);
+            (slope, y1 - slope * x1)
+        }
+    };
+
+    QqPlot {
+        theoretical,
+        sample,
+        slope,
+        intercept,
+        r: sxy / (sxx * syy).sqrt(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_quantile_functions() {
+        let distributions = [
+            (Distribution::Normal, 0.),
+            (Distribution::Exponential, 2f64.ln()),
+            (Distribution::Uniform, 0.5),
+            (Distribution::LogNormal(2.), 1.),
+            (Distribution::Logistic, 0.),
+            (Distribution::Laplace, 0.),
+            (Distribution::Gumbel, -(2f64.ln().ln())),
+            (Distribution::Cauchy, 0.),
+            (Distribution::Weibull(2.), 2f64.ln().sqrt()),
+        ];
+        for (distribution, median) in distributions {
+            assert_abs_diff_eq!(distribution.quantile(0.5), median, epsilon = 1e-12);
+            // The quantile functions are increasing
+            assert!(distribution.quantile(0.3) < distribution.quantile(0.7));
+        }
+        assert_abs_diff_eq!(
+            Distribution::Normal.quantile(0.975),
+            1.959964,
+            epsilon = 1e-6
+        );
+        assert_abs_diff_eq!(Distribution::Laplace.quantile(0.75), 2f64.ln());
+        assert_abs_diff_eq!(Distribution::Cauchy.quantile(0.75), 1., epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_exact_fit() {
+        // Values at the plotting positions lie exactly on the line
+        let options = QqOptions {
+            distribution: Distribution::Exponential,
+            ..Default::default()
+        };
+        let values = (1..=20)
+            .rev()
+            .map(|i| 3. + 2. * Distribution::Exponential.quantile((i as f64 - 0.5) / 20.))
+            .collect::<Vec<_>>();
+        let result = qq(&values, &options);
+        assert_eq!(result.sample.len(), 20);
+        assert!(result.sample.windows(2).all(|w| w[0] <= w[1]));
+        assert_abs_diff_eq!(result.slope, 2., epsilon = 1e-12);
+        assert_abs_diff_eq!(result.intercept, 3., epsilon = 1e-12);
+        assert_abs_diff_eq!(result.r, 1., epsilon = 1e-12);
+
+        let result = qq(
+            &values,
+            &QqOptions {
+                line: ReferenceLine::Quartiles,
+                ..options
+            },
+        );
+        assert!(result.slope > 1.8 && result.slope < 2.2);
+    }
+
+    #[test]
+    fn test_uniform() {
+        let result = qq(
+            &[4., 1., 3., 2.],
+            &QqOptions {
+                distribution: Distribution::Uniform,
+                plotting_position: 0.,
+                line: ReferenceLine::Quartiles,
+            },
+        );
+        assert_eq!(result.theoretical, [0.2, 0.4, 0.6, 0.8]);
+        assert_eq!(result.sample, [1., 2., 3., 4.]);
+        // The quartiles of the sample are 1.75 and 3.25
+        assert_abs_diff_eq!(result.slope, 3., epsilon = 1e-12);
+        assert_abs_diff_eq!(result.intercept, 1., epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_nan() {
+        let options = QqOptions::default();
+        let result = qq(&[2., f64::NAN, 1.], &options);
+        assert_eq!(result, qq(&[1., 2.], &options));
+    }
+
+    #[test]
+    #[should_panic(expected = "qq requires at least one value")]
+    fn test_empty() {
+        qq(
+            &[f64::NAN],
+            &QqOptions {
+                line: ReferenceLine::Quartiles,
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "plotting position needs to lie in [0, 1)")]
+    fn test_plotting_position_out_of_range() {
+        qq(
+            &[1., 2.],
+            &QqOptions {
+                plotting_position: 1.,
+                ..Default::default()
+            },
+        );
+    }
+}
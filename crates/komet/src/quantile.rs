@@ -0,0 +1,424 @@
+/// A method for computing sample quantiles.
+///
+/// The first nine methods are the definitions by Hyndman and Fan (1996)
+/// (types 1 to 9, named as in NumPy). The remaining four methods are NumPy's
+/// discontinuous variants of the linear method.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum QuantileMethod {
+    /// Type 1: inverse of the empirical distribution function.
+    InvertedCdf,
+    /// Type 2: like type 1 but averaging at discontinuities.
+    AveragedInvertedCdf,
+    /// Type 3: the nearest even order statistic.
+    ClosestObservation,
+    /// Type 4: linear interpolation of the empirical distribution function.
+    InterpolatedInvertedCdf,
+    /// Type 5: piecewise linear with knots at the midpoints of the steps.
+    Hazen,
+    /// Type 6: linear interpolation of the expectations of the order
+    /// statistics (used by SAS and Minitab).
+    Weibull,
+    /// Type 7: linear interpolation of the modes of the order statistics
+    /// (default of R and NumPy).
+    Linear,
+    /// Type 8: approximately median-unbiased regardless of the distribution.
+    MedianUnbiased,
+    /// Type 9: approximately unbiased for normally distributed data.
+    NormalUnbiased,
+    /// The lower of the two values that [`Linear`](Self::Linear) interpolates.
+    Lower,
+    /// The higher of the two values that [`Linear`](Self::Linear) interpolates.
+    Higher,
+    /// The nearer of the two values that [`Linear`](Self::Linear) interpolates.
+    Nearest,
+    /// The mean of the two values that [`Linear`](Self::Linear) interpolates.
+    Midpoint,
+}
+
+/// Computes the p-th quantile (with p between 0 and 1) of `n` values given
+/// by their order statistics. `order_statistic(k)` returns the k-th smallest
+/// value (counting from one) for any positive integer `k`, clamping it to the
+/// valid range.
+fn quantile_ranked(
+    n: f64,
+    p: f64,
+    method: QuantileMethod,
+    mut order_statistic: impl FnMut(f64) -> f64,
+) -> f64 {
+    // Linear interpolation at a zero-based rational index
+    let mut interpolate = |index: f64| {
+        let lower = index.floor();
+        let t = index - lower;
+        if t == 0. {
+            order_statistic(lower + 1.)
+        } else {
+            order_statistic(lower + 1.) * (1. - t) + order_statistic(lower + 2.) * t
+        }
+    };
+    // Hyndman–Fan types 4 to 9 interpolate at the one-based index
+    // (n + 1 - α - β)·p + α.
+    let mut continuous = |alpha: f64, beta: f64| {
+        interpolate(((n + 1. - alpha - beta) * p + alpha - 1.).clamp(0., n - 1.))
+    };
+    let index = (n - 1.) * p;
+
+    match method {
+        QuantileMethod::InvertedCdf => order_statistic((n * p).ceil()),
+        QuantileMethod::AveragedInvertedCdf => {
+            let j = (n * p).floor();
+            if n * p == j {
+                0.5 * (order_statistic(j) + order_statistic(j + 1.))
+            } else {
+                order_statistic(j + 1.)
+            }
+        }
+        QuantileMethod::ClosestObservation => {
+            let j = (n * p - 0.5).floor();
+            if n * p - 0.5 == j && j % 2. == 0. {
+                order_statistic(j)
+            } else {
+                order_statistic(j + 1.)
+            }
+        }
+        QuantileMethod::InterpolatedInvertedCdf => continuous(0., 1.),
+        QuantileMethod::Hazen => continuous(0.5, 0.5),
+        QuantileMethod::Weibull => continuous(0., 0.),
+        QuantileMethod::Linear => continuous(1., 1.),
+        QuantileMethod::MedianUnbiased => continuous(1. / 3., 1. / 3.),
+        QuantileMethod::NormalUnbiased => continuous(3. / 8., 3. / 8.),
+        QuantileMethod::Lower => interpolate(index.floor()),
+        QuantileMethod::Higher => interpolate(index.ceil()),
+        QuantileMethod::Nearest => interpolate(index.round_ties_even()),
+        QuantileMethod::Midpoint => 0.5 * (interpolate(index.floor()) + interpolate(index.ceil())),
+    }
+}
+
+/// Computes the p-th quantile (with p between 0 and 1) of values that are
+/// sorted in ascending order.
+pub fn quantile_sorted(values: &[f64], p: f64, method: QuantileMethod) -> f64 {
+    assert!(!values.is_empty());
+    quantile_ranked(values.len() as f64, p, method, |k| {
+        values[(k.max(1.) as usize).min(values.len()) - 1]
+    })
+}
+
+/// Computes the p-th weighted quantile (with p between 0 and 1) of values
+/// that are sorted in ascending order.
+///
+/// The weights are normalized to sum to the number of values with a positive
+/// weight and each value then occupies a range of ranks as wide as its
+/// weight. The quantile is computed from these ranks like
+/// [`quantile_sorted`] does for unweighted values, so unit weights give the
+/// same results and integer weights act like repeated values up to the
+/// normalization.
+///
+/// The weights need to be non-negative and finite. Returns NaN if no weight
+/// is positive, e.g., for empty values.
+pub fn weighted_quantile_sorted(
+    values: &[f64],
+    weights: &[f64],
+    p: f64,
+    method: QuantileMethod,
+) -> f64 {
+    assert_eq!(values.len(), weights.len());
+    let n = weights.iter().filter(|&&w| w > 0.).count() as f64;
+    if n == 0. {
+        return f64::NAN;
+    }
+    let total = weights.iter().sum::<f64>();
+    let mut cumulative = Vec::with_capacity(weights.len());
+    let mut sum = 0.;
+    for &w in weights {
+        sum += w * n / total;
+        cumulative.push(sum);
+    }
+    // Tolerate rounding errors in the cumulative weights
+    let eps = 1e-9 * n;
+    quantile_ranked(n, p, method, |k| {
+        let k = k.clamp(1., n);
+        let i = cumulative.partition_point(|&c| c < k - eps);
+        values[i.min(values.len() - 1)]
+    })
+}
+
+/// Finds order statistics of unsorted values by selection instead of a full
+/// sort. Each selection partitions the values in place with introselect
+/// (quickselect with a median-of-medians fallback), taking `O(n)` time. The
+/// partitions are remembered, so that later selections only need to search
+/// the part between the closest previously selected indices.
+pub(crate) struct Selection<'a> {
+    values: &'a mut [f64],
+    /// The indices at which the values are already in their sorted position,
+    /// in ascending order.
+    pivots: Vec<usize>,
+}
+
+impl<'a> Selection<'a> {
+    pub fn new(values: &'a mut [f64]) -> Self {
+        Selection {
+            values,
+            pivots: Vec::new(),
+        }
+    }
+
+    /// Returns the value at the given (zero-based) index in sorted order.
+    pub fn nth(&mut self, index: usize) -> f64 {
+        if let Err(position) = self.pivots.binary_search(&index) {
+            let start = position.checked_sub(1).map_or(0, |i| self.pivots[i] + 1);
+            let end = self
+                .pivots
+                .get(position)
+                .copied()
+                .unwrap_or(self.values.len());
+            self.values[start..end].select_nth_unstable_by(index - start, f64::total_cmp);
+            self.pivots.insert(position, index);
+        }
+        self.values[index]
+    }
+
+    /// Computes the p-th quantile (with p between 0 and 1).
+    pub fn quantile(&mut self, p: f64, method: QuantileMethod) -> f64 {
+        let n = self.values.len();
+        assert!(n > 0, "quantiles require at least one value");
+        quantile_ranked(n as f64, p, method, |k| {
+            self.nth((k.max(1.) as usize).min(n) - 1)
+        })
+    }
+}
+
+/// Computes the q-th percentiles (with q between 0 and 100) of the given
+/// values.
+///
+/// # Panics
+///
+/// Panics if `values` is empty and `percentiles` is not.
+pub fn quantiles(values: &[f64], percentiles: &[f64], method: QuantileMethod) -> Vec<f64> {
+    select_quantiles(&mut values.to_vec(), percentiles, method)
+}
+
+/// Computes the q-th percentiles (with q between 0 and 100) of the given
+/// values like [`quantiles`] but without copying them. The values are
+/// reordered in the process.
+///
+/// Instead of sorting the values, each quantile is found by selection in
+/// `O(n)` expected time.
+///
+/// # Panics
+///
+/// Panics if `values` is empty and `percentiles` is not.
+pub fn select_quantiles(
+    values: &mut [f64],
+    percentiles: &[f64],
+    method: QuantileMethod,
+) -> Vec<f64> {
+    let mut selection = Selection::new(values);
+    percentiles
+        .iter()
+        .map(|&q| selection.quantile(q / 100., method))
+        .collect()
+}
+
+/// Computes the weighted q-th percentiles (with q between 0 and 100) of the
+/// given values, see [`weighted_quantile_sorted`].
+pub fn weighted_quantiles(
+    values: &[f64],
+    weights: &[f64],
+    percentiles: &[f64],
+    method: QuantileMethod,
+) -> Vec<f64> {
+    let (sorted_values, sorted_weights) = sort_weighted(values, weights);
+    percentiles
+        .iter()
+        .map(|&q| weighted_quantile_sorted(&sorted_values, &sorted_weights, q / 100., method))
+        .collect()
+}
+
+/// Sorts values in ascending order together with their weights.
+pub(crate) fn sort_weighted(values: &[f64], weights: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    assert_eq!(values.len(), weights.len());
+    let mut pairs = values
+        .iter()
+        .copied()
+        .zip(weights.iter().copied())
+        .collect::<Vec<_>>();
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+    pairs.into_iter().unzip()
+}
+
+/// Computes the q-th percentile of sorted values by linear interpolation
+/// between the closest ranks.
+pub(crate) fn percentile(values: &[f64], q: f64) -> f64 {
+    quantile_sorted(values, q / 100., QuantileMethod::Linear)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_methods() {
+        let values = [1., 2., 4., 7., 11.];
+        let expected = [
+            (QuantileMethod::InvertedCdf, 2.),
+            (QuantileMethod::AveragedInvertedCdf, 2.),
+            (QuantileMethod::ClosestObservation, 2.),
+            (QuantileMethod::InterpolatedInvertedCdf, 1.5),
+            (QuantileMethod::Hazen, 2.),
+            (QuantileMethod::Weibull, 1.8),
+            (QuantileMethod::Linear, 2.4),
+            (QuantileMethod::MedianUnbiased, 1. + 14. / 15.),
+            (QuantileMethod::NormalUnbiased, 1.95),
+            (QuantileMethod::Lower, 2.),
+            (QuantileMethod::Higher, 4.),
+            (QuantileMethod::Nearest, 2.),
+            (QuantileMethod::Midpoint, 3.),
+        ];
+        for (method, expected) in expected {
+            assert_abs_diff_eq!(
+                quantile_sorted(&values, 0.3, method),
+                expected,
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_discontinuities() {
+        let values = [1., 2., 4., 7.];
+        let median = |method| quantile_sorted(&values, 0.5, method);
+        assert_eq!(median(QuantileMethod::InvertedCdf), 2.);
+        assert_eq!(median(QuantileMethod::AveragedInvertedCdf), 3.);
+        assert_eq!(median(QuantileMethod::ClosestObservation), 2.);
+        assert_eq!(
+            quantile_sorted(&values, 0.25, QuantileMethod::ClosestObservation),
+            1.
+        );
+        assert_eq!(
+            quantile_sorted(&values, 0.375, QuantileMethod::ClosestObservation),
+            2.
+        );
+    }
+
+    #[test]
+    fn test_extremes() {
+        let values = [1., 2., 4., 7.];
+        for method in [
+            QuantileMethod::InvertedCdf,
+            QuantileMethod::AveragedInvertedCdf,
+            QuantileMethod::ClosestObservation,
+            QuantileMethod::InterpolatedInvertedCdf,
+            QuantileMethod::Weibull,
+            QuantileMethod::Linear,
+            QuantileMethod::NormalUnbiased,
+            QuantileMethod::Nearest,
+        ] {
+            assert_eq!(quantile_sorted(&values, 0., method), 1.);
+            assert_eq!(quantile_sorted(&values, 1., method), 7.);
+        }
+    }
+
+    #[test]
+    fn test_weighted() {
+        let values = [1., 2., 4., 7., 11.];
+        let methods = [
+            QuantileMethod::InvertedCdf,
+            QuantileMethod::AveragedInvertedCdf,
+            QuantileMethod::ClosestObservation,
+            QuantileMethod::InterpolatedInvertedCdf,
+            QuantileMethod::Hazen,
+            QuantileMethod::Weibull,
+            QuantileMethod::Linear,
+            QuantileMethod::MedianUnbiased,
+            QuantileMethod::NormalUnbiased,
+            QuantileMethod::Lower,
+            QuantileMethod::Higher,
+            QuantileMethod::Nearest,
+            QuantileMethod::Midpoint,
+        ];
+        // Unit weights, scaled or not, reproduce the unweighted quantiles
+        for method in methods {
+            for p in [0., 0.1, 0.25, 0.3, 0.5, 0.8, 1.] {
+                let expected = quantile_sorted(&values, p, method);
+                for w in [1., 0.2, 7.] {
+                    assert_abs_diff_eq!(
+                        weighted_quantile_sorted(&values, &[w; 5], p, method),
+                        expected,
+                        epsilon = 1e-12
+                    );
+                }
+            }
+        }
+
+        // Values with zero weight are ignored
+        assert_eq!(
+            weighted_quantiles(
+                &[3., 100., 1., 2., -5.],
+                &[1., 0., 1., 1., 0.],
+                &[0., 50., 100.],
+                QuantileMethod::Linear
+            ),
+            [1., 2., 3.]
+        );
+
+        // Doubling a weight shifts the median towards the value
+        let median = |weights: &[f64]| {
+            weighted_quantile_sorted(&[1., 2., 3., 4.], weights, 0.5, QuantileMethod::InvertedCdf)
+        };
+        assert_eq!(median(&[1., 1., 1., 1.]), 2.);
+        assert_eq!(median(&[1., 1., 1., 5.]), 4.);
+
+        // Without positive weights, the quantile is undefined
+        for method in methods {
+            assert!(weighted_quantile_sorted(&[1., 2.], &[0., 0.], 0.5, method).is_nan());
+            assert!(weighted_quantile_sorted(&[], &[], 0.5, method).is_nan());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "quantiles require at least one value")]
+    fn test_empty() {
+        quantiles(&[], &[50.], QuantileMethod::Linear);
+    }
+
+    #[test]
+    fn test_selection() {
+        let mut values = (0..1000)
+            .map(|i| ((i * 7919) % 1000) as f64)
+            .collect::<Vec<_>>();
+        let mut sorted_values = values.clone();
+        sorted_values.sort_by(f64::total_cmp);
+        let percentiles = [50., 25., 75., 0., 100., 33.3, 99.9, 25.];
+        for method in [
+            QuantileMethod::Linear,
+            QuantileMethod::InvertedCdf,
+            QuantileMethod::Midpoint,
+        ] {
+            let expected = percentiles
+                .iter()
+                .map(|&q| quantile_sorted(&sorted_values, q / 100., method))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                select_quantiles(&mut values, &percentiles, method),
+                expected
+            );
+        }
+
+        let mut selection = Selection::new(&mut values);
+        for i in [500, 3, 999, 0, 501, 250] {
+            assert_eq!(selection.nth(i), i as f64);
+        }
+    }
+
+    #[test]
+    fn test_quantiles() {
+        assert_eq!(
+            quantiles(
+                &[4., 1., 3., 2.],
+                &[0., 25., 50., 100.],
+                QuantileMethod::Linear
+            ),
+            [1., 1.75, 2.5, 4.]
+        );
+    }
+}
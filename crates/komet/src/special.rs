@@ -0,0 +1,84 @@
+/// Computes the quantile function (inverse cumulative distribution function)
+/// of the standard normal distribution for `p` between 0 and 1.
+///
+/// This uses the rational approximation by Acklam which has a relative error
+/// below 1.15e-9.
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.38357751867269e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let polynomial = |coefficients: &[f64], x: f64| coefficients.iter().fold(0., |a, &c| a * x + c);
+    let tail = |p: f64| {
+        let q = (-2. * p.ln()).sqrt();
+        polynomial(&C, q) / (polynomial(&D, q) * q + 1.)
+    };
+
+    if p <= 0. {
+        f64::NEG_INFINITY
+    } else if p >= 1. {
+        f64::INFINITY
+    } else if p < P_LOW {
+        tail(p)
+    } else if p > 1. - P_LOW {
+        -tail(1. - p)
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        polynomial(&A, r) * q / (polynomial(&B, r) * r + 1.)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_normal_quantile() {
+        assert_eq!(normal_quantile(0.5), 0.);
+        assert_relative_eq!(
+            normal_quantile(0.975),
+            1.959963984540054,
+            max_relative = 1e-9
+        );
+        assert_relative_eq!(normal_quantile(0.8413447460685429), 1., max_relative = 1e-9);
+        assert_relative_eq!(
+            normal_quantile(1e-5),
+            -4.264890793922825,
+            max_relative = 1e-9
+        );
+        assert_relative_eq!(
+            normal_quantile(0.01),
+            -normal_quantile(0.99),
+            max_relative = 1e-12
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use crate::fft::Complex;
+use std::f64::consts::PI;
+
+/// Returns the frequencies of the bins of a discrete Fourier transform of
+/// `n` values with the given sample spacing, in the order of
+/// [`fft`](crate::fft), i.e., the non-negative frequencies are followed by
+/// the negative ones.
+pub fn fftfreq(n: usize, spacing: f64) -> Vec<f64> {
+    let scale = 1. / (n as f64 * spacing);
+    (0..n)
+        .map(|k| {
+            if k < n.div_ceil(2) {
+                k as f64 * scale
+            } else {
+                (k as f64 - n as f64) * scale
+            }
+        })
+        .collect()
+}
+
+/// Returns the non-negative frequencies of the `n/2 + 1` bins of a discrete
+/// Fourier transform of `n` real values with the given sample spacing as
+/// computed by [`rfft`](crate::rfft).
+pub fn rfftfreq(n: usize, spacing: f64) -> Vec<f64> {
+    let scale = 1. / (n as f64 * spacing);
+    (0..=n / 2).map(|k| k as f64 * scale).collect()
+}
+
+/// Computes the magnitudes `|X|` of complex values.
+pub fn magnitude(values: &[Complex]) -> Vec<f64> {
+    values.iter().map(|x| x.norm()).collect()
+}
+
+/// Computes the powers `|X|²` of complex values.
+pub fn power(values: &[Complex]) -> Vec<f64> {
+    values.iter().map(|x| x.norm_sqr()).collect()
+}
+
+/// Computes the phase angles of complex values in `(-π, π]`.
+pub fn angle(values: &[Complex]) -> Vec<f64> {
+    values.iter().map(|x| x.arg()).collect()
+}
+
+/// The reference of a decibel scale, i.e., the power at 0 dB.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DecibelReference {
+    Fixed(f64),
+    /// The largest power, such that all values are at most 0 dB.
+    Max,
+}
+
+/// Converts powers to decibels `10·log₁₀(P/P₀)` relative to the reference
+/// power `P₀`. For amplitudes, the decibels of their squares are computed.
+/// Zero powers yield negative infinity.
+pub fn decibels(power: &[f64], reference: DecibelReference) -> Vec<f64> {
+    let reference = match reference {
+        DecibelReference::Fixed(reference) => reference,
+        DecibelReference::Max => power.iter().fold(0f64, |a, &b| a.max(b)),
+    };
+    power
+        .iter()
+        .map(|p| 10. * (p / reference).log10())
+        .collect()
+}
+
+/// Unwraps phase angles by adding multiples of `2π` wherever consecutive
+/// angles differ by at least `π`, so that the phase is continuous.
+pub fn unwrap(phases: &[f64]) -> Vec<f64> {
+    let mut correction = 0.;
+    let mut previous = None;
+    phases
+        .iter()
+        .map(|&phase| {
+            if let Some(previous) = previous.replace(phase) {
+                let difference = phase - previous;
+                if difference.abs() >= PI {
+                    let mut wrapped = (difference + PI).rem_euclid(2. * PI) - PI;
+                    // Jumps by exactly π keep their direction
+                    if wrapped == -PI && difference > 0. {
+                        wrapped = PI;
+                    }
+                    correction += wrapped - difference;
+                }
+            }
+            phase + correction
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_frequencies() {
+        assert_eq!(fftfreq(4, 0.5), [0., 0.5, -1., -0.5]);
+        assert_eq!(fftfreq(5, 1.), [0., 0.2, 0.4, -0.4, -0.2]);
+        assert_eq!(rfftfreq(4, 0.5), [0., 0.5, 1.]);
+        assert_eq!(rfftfreq(5, 1.), [0., 0.2, 0.4]);
+        assert!(fftfreq(0, 1.).is_empty());
+    }
+
+    #[test]
+    fn test_magnitude_and_phase() {
+        let values = [
+            Complex::new(3., 4.),
+            Complex::new(0., -2.),
+            Complex::new(-1., 0.),
+        ];
+        assert_eq!(magnitude(&values), [5., 2., 1.]);
+        assert_eq!(power(&values), [25., 4., 1.]);
+        assert_eq!(angle(&values), [(4f64).atan2(3.), -PI / 2., PI]);
+
+        let db = decibels(&[100., 1., 0.01, 0.], DecibelReference::Fixed(1.));
+        assert_eq!(db[..3], [20., 0., -20.]);
+        assert_eq!(db[3], f64::NEG_INFINITY);
+        assert_eq!(decibels(&[100., 10.], DecibelReference::Max), [0., -10.]);
+    }
+
+    #[test]
+    fn test_unwrap() {
+        let phases = (0..20).map(|k| 0.8 * k as f64).collect::<Vec<_>>();
+        let wrapped = phases
+            .iter()
+            .map(|phase| Complex::from_angle(*phase).arg())
+            .collect::<Vec<_>>();
+        for (a, b) in unwrap(&wrapped).iter().zip(&phases) {
+            assert_abs_diff_eq!(a, b, epsilon = 1e-12);
+        }
+
+        let phases = (0..20).map(|k| -1.1 * k as f64 + 0.5).collect::<Vec<_>>();
+        let wrapped = phases
+            .iter()
+            .map(|phase| Complex::from_angle(*phase).arg())
+            .collect::<Vec<_>>();
+        for (a, b) in unwrap(&wrapped).iter().zip(&phases) {
+            assert_abs_diff_eq!(a, b, epsilon = 1e-12);
+        }
+        assert!(unwrap(&[]).is_empty());
+    }
+}
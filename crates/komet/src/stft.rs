@@ -0,0 +1,355 @@
+use crate::fft::{irfft, Complex, Fft, FftNorm};
+use crate::spectrum::{decibels, magnitude, power, DecibelReference};
+use crate::window::{window, Window};
+
+/// Options for [`stft`], [`istft`], and [`spectrogram`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StftOptions {
+    /// The number of samples per unit of time, which determines the units of
+    /// the times and the frequencies.
+    pub sample_rate: f64,
+    /// The window applied to each segment in its periodic form.
+    pub window: Window,
+    /// The number of samples per segment.
+    pub segment_length: usize,
+    /// The number of samples between the starts of consecutive segments. By
+    /// default, segments overlap by half their length.
+    pub hop: Option<usize>,
+    /// The length of the transform of each segment. Segments are padded with
+    /// zeros to this length, which interpolates the spectrum. By default, no
+    /// padding is applied.
+    pub fft_length: Option<usize>,
+    /// Whether the signal is padded with zeros by half a segment at both ends
+    /// and at the end to a whole number of segments, so that the first
+    /// segment is centered at the first sample and every sample is covered
+    /// by a segment.
+    pub padding: bool,
+}
+
+impl Default for StftOptions {
+    fn default() -> Self {
+        StftOptions {
+            sample_rate: 1.,
+            window: Window::Hann,
+            segment_length: 256,
+            hop: None,
+            fft_length: None,
+            padding: true,
+        }
+    }
+}
+
+impl StftOptions {
+    fn hop(&self) -> usize {
+        self.hop.unwrap_or(self.segment_length / 2).max(1)
+    }
+
+    fn fft_length(&self) -> usize {
+        self.fft_length
+            .unwrap_or(self.segment_length)
+            .max(self.segment_length)
+    }
+
+    /// The number of samples by which the signal is padded at the start.
+    fn offset(&self) -> usize {
+        if self.padding {
+            self.segment_length / 2
+        } else {
+            0
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Stft {
+    /// The times of the segment centers.
+    pub times: Vec<f64>,
+    /// The non-negative frequencies of the bins.
+    pub frequencies: Vec<f64>,
+    /// The spectra of the segments as a grid with one row per frequency and
+    /// one column per time in row-major order, i.e., in the layout of
+    /// [`contour`](crate::contour) with the times as `x` and the frequencies
+    /// as `y`. The spectra are scaled by the inverse sum of the window, so
+    /// that a sinusoid of amplitude `A` at the center of a frequency bin has
+    /// a magnitude of `A/2`.
+    pub values: Vec<Complex>,
+}
+
+/// Computes the short-time Fourier transform of real values, i.e., the
+/// spectra of overlapping windowed segments of the signal.
+pub fn stft(values: &[f64], options: &StftOptions) -> Stft {
+    let len = options.segment_length;
+    let hop = options.hop();
+    let fft_length = options.fft_length();
+    let offset = options.offset();
+
+    let padded_len = values.len() + 2 * offset;
+    let segments = if len == 0 || padded_len < len {
+        0
+    } else if options.padding {
+        (padded_len - len).div_ceil(hop) + 1
+    } else {
+        (padded_len - len) / hop + 1
+    };
+    let bins = if len == 0 { 0 } else { fft_length / 2 + 1 };
+
+    let weights = window(options.window, len, true);
+    let scale = 1. / weights.iter().sum::<f64>();
+    let sample = |i: usize| {
+        i.checked_sub(offset)
+            .and_then(|i| values.get(i))
+            .copied()
+            .unwrap_or(0.)
+    };
+
+    let plan = Fft::new(fft_length, false);
+    let mut result = vec![Complex::default(); bins * segments];
+    let mut buffer = vec![Complex::default(); fft_length];
+    for t in 0..segments {
+        let start = t * hop;
+        buffer.fill(Complex::default());
+        for (k, weight) in weights.iter().enumerate() {
+            buffer[k] = Complex::new(sample(start + k) * weight * scale, 0.);
+        }
+        plan.process(&mut buffer);
+        for (f, &x) in buffer[..bins].iter().enumerate() {
+            result[f * segments + t] = x;
+        }
+    }
+
+    let center = len as f64 / 2. - offset as f64;
+    Stft {
+        times: (0..segments)
+            .map(|t| ((t * hop) as f64 + center) / options.sample_rate)
+            .collect(),
+        frequencies: (0..bins)
+            .map(|f| f as f64 * options.sample_rate / fft_length as f64)
+            .collect(),
+        values: result,
+    }
+}
+
+/// Reconstructs a signal from its short-time Fourier transform computed with
+/// [`stft`] and the same options by the weighted overlap-add method. The
+/// grid of spectra has one row per frequency as returned by [`stft`].
+///
+/// Without a given length, the signal extends to the end of the last segment
+/// or, with padding, to half a segment before it. Samples that are not
+/// covered by any segment with a non-zero window weight are zero.
+pub fn istft(values: &[Complex], options: &StftOptions, len: Option<usize>) -> Vec<f64> {
+    let segment_length = options.segment_length;
+    let hop = options.hop();
+    let fft_length = options.fft_length();
+    let offset = options.offset();
+
+    let bins = fft_length / 2 + 1;
+    let segments = if segment_length == 0 {
+        0
+    } else {
+        values.len() / bins
+    };
+    let padded_len = if segments == 0 {
+        0
+    } else {
+        (segments - 1) * hop + segment_length
+    };
+
+    let weights = window(options.window, segment_length, true);
+    let scale = weights.iter().sum::<f64>();
+
+    let mut signal = vec![0.; padded_len];
+    let mut norm = vec![0.; padded_len];
+    let mut spectrum = vec![Complex::default(); bins];
+    for t in 0..segments {
+        for (f, x) in spectrum.iter_mut().enumerate() {
+            *x = values[f * segments + t];
+        }
+        let segment = irfft(&spectrum, fft_length, FftNorm::Backward);
+        let start = t * hop;
+        for (k, weight) in weights.iter().enumerate() {
+            signal[start + k] += segment[k] * scale * weight;
+            norm[start + k] += weight * weight;
+        }
+    }
+    for (x, norm) in signal.iter_mut().zip(norm) {
+        if norm > 1e-10 {
+            *x /= norm;
+        }
+    }
+
+    let end = len.map_or(padded_len.saturating_sub(offset), |len| len + offset);
+    signal.resize(end.max(offset), 0.);
+    signal.drain(..offset.min(signal.len()));
+    signal
+}
+
+/// How the magnitudes of a spectrogram are represented.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SpectrogramScale {
+    /// The magnitude `|X|`.
+    Magnitude,
+    /// The power `|X|²`.
+    Power,
+    /// The power in decibels `10·log₁₀(|X|²)`. Values more than 120 dB below
+    /// the maximum are clipped so that silent parts remain finite.
+    Decibels,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Spectrogram {
+    /// The times of the segment centers.
+    pub times: Vec<f64>,
+    /// The non-negative frequencies of the bins.
+    pub frequencies: Vec<f64>,
+    /// The grid of magnitudes with one row per frequency, see [`Stft`].
+    pub values: Vec<f64>,
+}
+
+/// Computes a spectrogram, i.e., the magnitudes of the short-time Fourier
+/// transform, of real values.
+pub fn spectrogram(values: &[f64], options: &StftOptions, scale: SpectrogramScale) -> Spectrogram {
+    let stft = stft(values, options);
+    let values = match scale {
+        SpectrogramScale::Magnitude => magnitude(&stft.values),
+        SpectrogramScale::Power => power(&stft.values),
+        SpectrogramScale::Decibels => {
+            let power = power(&stft.values);
+            let floor = power.iter().fold(0f64, |a, &b| a.max(b)) * 1e-12;
+            let power = power
+                .iter()
+                .map(|p| p.max(floor).max(f64::MIN_POSITIVE))
+                .collect::<Vec<_>>();
+            decibels(&power, DecibelReference::Fixed(1.))
+        }
+    };
+
+    Spectrogram {
+        times: stft.times,
+        frequencies: stft.frequencies,
+        values,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_stft() {
+        // A sinusoid at the center of the bin 4 of segments with 32 samples
+        let values = (0..256)
+            .map(|k| 3. * (2. * PI * k as f64 / 8.).cos())
+            .collect::<Vec<_>>();
+        let options = StftOptions {
+            sample_rate: 8.,
+            segment_length: 32,
+            padding: false,
+            ..Default::default()
+        };
+        let result = stft(&values, &options);
+        assert_eq!(result.times.len(), 15);
+        assert_eq!(result.frequencies.len(), 17);
+        assert_eq!(result.values.len(), 15 * 17);
+        assert_abs_diff_eq!(result.times[0], 2.);
+        assert_abs_diff_eq!(result.times[1], 4.);
+        assert_abs_diff_eq!(result.frequencies[4], 1.);
+        assert_abs_diff_eq!(result.frequencies[16], 4.);
+        for t in 0..15 {
+            let x = result.values[4 * 15 + t];
+            assert_abs_diff_eq!(x.norm(), 1.5, epsilon = 1e-12);
+        }
+
+        // Padding centers the first segment at the first sample
+        let result = stft(
+            &values,
+            &StftOptions {
+                padding: true,
+                ..options
+            },
+        );
+        assert_eq!(result.times.len(), 17);
+        assert_abs_diff_eq!(result.times[0], 0.);
+        assert_abs_diff_eq!(*result.times.last().unwrap(), 32.);
+
+        // Zero-padding of the segments interpolates the spectrum
+        let result = stft(
+            &values,
+            &StftOptions {
+                fft_length: Some(64),
+                ..options
+            },
+        );
+        assert_eq!(result.frequencies.len(), 33);
+        assert_abs_diff_eq!(result.frequencies[8], 1.);
+    }
+
+    #[test]
+    fn test_istft() {
+        let values = (0..100)
+            .map(|k| (k as f64 * 0.3).sin() + 0.01 * (k * k) as f64)
+            .collect::<Vec<_>>();
+        for (window, hop, fft_length) in [
+            (Window::Hann, None, None),
+            (Window::Hann, Some(4), Some(20)),
+            (Window::Hamming, Some(5), None),
+            (Window::Rectangular, Some(16), None),
+        ] {
+            let options = StftOptions {
+                window,
+                segment_length: 16,
+                hop,
+                fft_length,
+                ..Default::default()
+            };
+            let result = istft(&stft(&values, &options).values, &options, Some(100));
+            assert_eq!(result.len(), 100);
+            for (a, b) in result.iter().zip(&values) {
+                assert_abs_diff_eq!(a, b, epsilon = 1e-10);
+            }
+        }
+
+        // Without a given length, the signal extends to the end of the last
+        // segment
+        let options = StftOptions {
+            segment_length: 16,
+            ..Default::default()
+        };
+        let result = istft(&stft(&values, &options).values, &options, None);
+        assert_eq!(result.len(), 104);
+        assert!(istft(&[], &options, None).is_empty());
+    }
+
+    #[test]
+    fn test_spectrogram() {
+        let values = (0..64)
+            .map(|k| {
+                if k < 32 {
+                    0.
+                } else {
+                    2. * (PI * k as f64 / 2.).sin()
+                }
+            })
+            .collect::<Vec<_>>();
+        let options = StftOptions {
+            segment_length: 16,
+            padding: false,
+            ..Default::default()
+        };
+        let magnitude = spectrogram(&values, &options, SpectrogramScale::Magnitude);
+        let power = spectrogram(&values, &options, SpectrogramScale::Power);
+        let decibels = spectrogram(&values, &options, SpectrogramScale::Decibels);
+        assert_eq!(magnitude.times, power.times);
+        assert_eq!(magnitude.values.len(), 9 * 7);
+
+        // The last segment contains a sinusoid with a frequency of 1/4
+        assert_abs_diff_eq!(magnitude.frequencies[4], 0.25);
+        assert_abs_diff_eq!(magnitude.values[4 * 7 + 6], 1., epsilon = 1e-12);
+        assert_abs_diff_eq!(power.values[4 * 7 + 6], 1., epsilon = 1e-12);
+        assert_abs_diff_eq!(decibels.values[4 * 7 + 6], 0., epsilon = 1e-10);
+
+        // The first segment is silent
+        assert_abs_diff_eq!(decibels.values[0], -120., epsilon = 1e-10);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::quantile::{quantile_sorted, QuantileMethod};
+
+/// A streaming estimator of a single quantile with the P² algorithm by Jain
+/// and Chlamtac (1985). It keeps five markers whose heights are adjusted
+/// with piecewise-parabolic interpolation as values arrive, so that the
+/// memory usage is constant regardless of the number of values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct P2Quantile {
+    p: f64,
+    count: usize,
+    /// The marker heights.
+    heights: [f64; 5],
+    /// The actual marker positions (one-based).
+    positions: [f64; 5],
+    /// The desired marker positions.
+    desired: [f64; 5],
+    /// The increments of the desired positions per value.
+    increments: [f64; 5],
+}
+
+impl P2Quantile {
+    /// Creates an estimator of the p-th quantile (with p between 0 and 1).
+    pub fn new(p: f64) -> Self {
+        P2Quantile {
+            p,
+            count: 0,
+            heights: [0.; 5],
+            positions: [1., 2., 3., 4., 5.],
+            desired: [1., 1. + 2. * p, 1. + 4. * p, 3. + 2. * p, 5.],
+            increments: [0., p / 2., p, (1. + p) / 2., 1.],
+        }
+    }
+
+    /// Adds a value to the estimator. NaN values are ignored.
+    pub fn push(&mut self, x: f64) {
+        if x.is_nan() {
+            return;
+        }
+        if self.count < 5 {
+            self.heights[self.count] = x;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_by(f64::total_cmp);
+            }
+            return;
+        }
+        self.count += 1;
+
+        // Find the cell of x and update the extreme markers
+        let k = if x < self.heights[0] {
+            self.heights[0] = x;
+            0
+        } else if x >= self.heights[4] {
+            self.heights[4] = x;
+            3
+        } else {
+            (1..5).find(|&i| x < self.heights[i]).unwrap() - 1
+        };
+
+        for position in &mut self.positions[k + 1..] {
+            *position += 1.;
+        }
+        for (desired, increment) in self.desired.iter_mut().zip(self.increments) {
+            *desired += increment;
+        }
+
+        // Adjust the heights of the middle markers if necessary
+        for i in 1..4 {
+            let d = self.desired[i] - self.positions[i];
+            if (d >= 1. && self.positions[i + 1] - self.positions[i] > 1.)
+                || (d <= -1. && self.positions[i - 1] - self.positions[i] < -1.)
+            {
+                let d = d.signum();
+                let height = self.parabolic(i, d);
+                self.heights[i] = if self.heights[i - 1] < height && height < self.heights[i + 1] {
+                    height
+                } else {
+                    self.linear(i, d)
+                };
+                self.positions[i] += d;
+            }
+        }
+    }
+
+    fn parabolic(&self, i: usize, d: f64) -> f64 {
+        let (q, n) = (&self.heights, &self.positions);
+        q[i] + d / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+
+    fn linear(&self, i: usize, d: f64) -> f64 {
+        let (q, n) = (&self.heights, &self.positions);
+        let j = if d > 0. { i + 1 } else { i - 1 };
+        q[i] + d * (q[j] - q[i]) / (n[j] - n[i])
+    }
+
+    /// Returns the current estimate of the quantile. Up to five values, the
+    /// quantile is computed exactly by linear interpolation. Without any
+    /// values, the estimate is NaN.
+    pub fn estimate(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else if self.count < 5 {
+            let mut values = self.heights[..self.count].to_vec();
+            values.sort_by(f64::total_cmp);
+            quantile_sorted(&values, self.p, QuantileMethod::Linear)
+        } else {
+            self.heights[2]
+        }
+    }
+
+    /// Returns the number of values added so far, not counting NaN values.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_few_values() {
+        let mut estimator = P2Quantile::new(0.5);
+        assert!(estimator.estimate().is_nan());
+        for x in [4., 1., 3.] {
+            estimator.push(x);
+        }
+        assert_eq!(estimator.estimate(), 3.);
+        estimator.push(2.);
+        assert_eq!(estimator.estimate(), 2.5);
+        assert_eq!(estimator.count(), 4);
+    }
+
+    #[test]
+    fn test_uniform() {
+        let mut rng = Rng::new(3);
+        let mut estimators = [0.1, 0.25, 0.5, 0.75, 0.99].map(P2Quantile::new);
+        for _ in 0..100_000 {
+            let x = rng.below(1 << 20) as f64 / (1 << 20) as f64;
+            for estimator in &mut estimators {
+                estimator.push(x);
+            }
+        }
+        for estimator in &estimators {
+            assert_abs_diff_eq!(estimator.estimate(), estimator.p, epsilon = 5e-3);
+        }
+    }
+
+    #[test]
+    fn test_sorted_input() {
+        let mut estimator = P2Quantile::new(0.25);
+        for i in 0..=10_000 {
+            estimator.push(i as f64);
+        }
+        assert_abs_diff_eq!(estimator.estimate(), 2500., epsilon = 25.);
+    }
+
+    #[test]
+    fn test_nan() {
+        let mut estimator = P2Quantile::new(0.5);
+        for x in [1., 2., f64::NAN, 3., 4., 5., 6., 7., f64::NAN, 9.] {
+            estimator.push(x);
+        }
+        assert_eq!(estimator.count(), 8);
+        assert!(estimator.estimate().is_finite());
+    }
+}
@@ -0,0 +1,221 @@
+use crate::boxplot::{boxplot, BoxplotOptions, BoxplotStats};
+use crate::kde::{bandwidth, kde, Bandwidth, KdeOptions, Kernel};
+
+/// A rule for scaling the widths of several violins relative to each other.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ViolinScale {
+    /// All violins have the same area.
+    Area,
+    /// The areas of the violins are proportional to the number of values.
+    Count,
+    /// All violins have the same maximum width.
+    Width,
+}
+
+/// Options for [`violins`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ViolinOptions {
+    pub kernel: Kernel,
+    pub bandwidth: Bandwidth,
+    /// The distance in bandwidths by which the density extends beyond the
+    /// extreme values of a group. A value of zero clips the violin to the
+    /// range of the data.
+    pub cut: f64,
+    /// The number of grid points at which the density is evaluated.
+    pub num_points: usize,
+    /// Whether all groups share a common grid that spans the extents of all
+    /// groups instead of each group using its own grid.
+    pub common_grid: bool,
+    pub scale: ViolinScale,
+    /// Options for the box plot statistics that are drawn inside the violin.
+    pub boxplot: BoxplotOptions,
+}
+
+impl Default for ViolinOptions {
+    fn default() -> Self {
+        ViolinOptions {
+            kernel: Kernel::Gaussian,
+            bandwidth: Bandwidth::Scott,
+            cut: 2.,
+            num_points: 100,
+            common_grid: false,
+            scale: ViolinScale::Area,
+            boxplot: BoxplotOptions::default(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Violin {
+    /// The grid along the value axis at which the density is evaluated.
+    pub grid: Vec<f64>,
+    /// The half width of the violin at each grid point. The widest violin
+    /// has a half width of 1.
+    pub width: Vec<f64>,
+    /// The closed outline of the violin as `(offset, value)` pairs relative
+    /// to its center, going up along the right side and down along the left
+    /// side.
+    pub outline: Vec<(f64, f64)>,
+    /// The bandwidth of the kernel density estimate.
+    pub bandwidth: f64,
+    /// The box plot statistics of the group for drawing the inner quartiles.
+    pub stats: BoxplotStats,
+}
+
+/// Computes the geometry of violin plots for several groups of values. Each
+/// violin is a mirrored kernel density estimate of its group, scaled across
+/// all groups according to [`ViolinOptions::scale`].
+pub fn violins(groups: &[&[f64]], options: &ViolinOptions) -> Vec<Violin> {
+    // Groups without spread degenerate to a line and have no bandwidth
+    let bandwidths = groups
+        .iter()
+        .map(|values| {
+            if values.windows(2).any(|pair| pair[0] != pair[1]) {
+                bandwidth(values, None, options.bandwidth)
+            } else {
+                0.
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let extents = groups
+        .iter()
+        .zip(&bandwidths)
+        .map(|(values, &h)| {
+            let cut = if h > 0. { options.cut * h } else { 0. };
+            let min = values.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+            let max = values.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+            (min - cut, max + cut)
+        })
+        .collect::<Vec<_>>();
+    let common_extent = extents
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |a, b| {
+            (a.0.min(b.0), a.1.max(b.1))
+        });
+
+    let mut violins = groups
+        .iter()
+        .zip(bandwidths)
+        .zip(extents)
+        .map(|((values, h), extent)| {
+            let (min, max) = if options.common_grid {
+                common_extent
+            } else {
+                extent
+            };
+            let n = options.num_points;
+            let grid = (0..n)
+                .map(|i| min + (max - min) * i as f64 / (n - 1) as f64)
+                .collect::<Vec<_>>();
+
+            // Degenerate groups without spread have no density
+            let mut width = if h > 0. {
+                let kde_options = KdeOptions {
+                    kernel: options.kernel,
+                    bandwidth: Bandwidth::Fixed(h),
+                    ..Default::default()
+                };
+                kde(values, &grid, &kde_options)
+            } else {
+                vec![0.; n]
+            };
+            if options.scale == ViolinScale::Count {
+                width.iter_mut().for_each(|w| *w *= values.len() as f64);
+            }
+
+            Violin {
+                grid,
+                width,
+                outline: Vec::new(),
+                bandwidth: h,
+                stats: boxplot(values, &options.boxplot),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let peak = |violin: &Violin| violin.width.iter().fold(0f64, |a, &b| a.max(b));
+    let global_peak = violins.iter().map(peak).fold(0f64, f64::max);
+    for violin in &mut violins {
+        let scale = match options.scale {
+            ViolinScale::Area | ViolinScale::Count => global_peak,
+            ViolinScale::Width => peak(violin),
+        };
+        if scale > 0. {
+            violin.width.iter_mut().for_each(|w| *w /= scale);
+        }
+
+        let right = violin.grid.iter().zip(&violin.width).map(|(&y, &w)| (w, y));
+        let left = violin.grid.iter().zip(&violin.width).rev();
+        violin.outline = right.chain(left.map(|(&y, &w)| (-w, y))).collect();
+    }
+
+    violins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const A: [f64; 6] = [1., 2., 2.5, 3., 4., 7.];
+    const B: [f64; 3] = [10., 11., 13.];
+
+    fn area(violin: &Violin) -> f64 {
+        let dx = violin.grid[1] - violin.grid[0];
+        violin.width.iter().sum::<f64>() * dx
+    }
+
+    #[test]
+    fn test_scales() {
+        let options = |scale| ViolinOptions {
+            scale,
+            // Cover the whole mass of the densities
+            cut: 5.,
+            num_points: 400,
+            ..Default::default()
+        };
+        let result = violins(&[&A, &B], &options(ViolinScale::Area));
+        assert_abs_diff_eq!(area(&result[0]), area(&result[1]), epsilon = 1e-3);
+
+        let result = violins(&[&A, &B], &options(ViolinScale::Count));
+        assert_abs_diff_eq!(area(&result[0]), 2. * area(&result[1]), epsilon = 1e-3);
+
+        let result = violins(&[&A, &B], &options(ViolinScale::Width));
+        for violin in &result {
+            assert_eq!(violin.width.iter().fold(0f64, |a, &b| a.max(b)), 1.);
+        }
+    }
+
+    #[test]
+    fn test_grid() {
+        let result = violins(&[&A, &B], &ViolinOptions::default());
+        let h = result[0].bandwidth;
+        assert_abs_diff_eq!(result[0].grid[0], 1. - 2. * h, epsilon = 1e-12);
+        assert_abs_diff_eq!(result[0].grid[99], 7. + 2. * h, epsilon = 1e-12);
+        assert_eq!(result[0].stats, boxplot(&A, &BoxplotOptions::default()));
+
+        let options = ViolinOptions {
+            cut: 0.,
+            common_grid: true,
+            ..Default::default()
+        };
+        let result = violins(&[&A, &B], &options);
+        assert_eq!(result[0].grid, result[1].grid);
+        assert_eq!((result[0].grid[0], result[0].grid[99]), (1., 13.));
+    }
+
+    #[test]
+    fn test_outline() {
+        let result = violins(&[&A, &[5., 5.]], &ViolinOptions::default());
+        let violin = &result[0];
+        assert_eq!(violin.outline.len(), 200);
+        assert_eq!(violin.outline[0], (violin.width[0], violin.grid[0]));
+        assert_eq!(violin.outline[199], (-violin.width[0], violin.grid[0]));
+        assert_eq!(violin.outline[100], (-violin.width[99], violin.grid[99]));
+
+        // A group without spread degenerates to a line
+        assert!(result[1].width.iter().all(|&w| w == 0.));
+        assert!(result[1].grid.iter().all(|&y| y == 5.));
+    }
+}
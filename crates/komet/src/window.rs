@@ -0,0 +1,303 @@
+use crate::linear_system::thomas_algorithm_bands;
+use std::f64::consts::PI;
+
+/// A window function for spectral analysis.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Window {
+    Rectangular,
+    Hann,
+    Hamming,
+    Blackman,
+    /// The four-term Blackman–Harris window.
+    BlackmanHarris,
+    /// The flat-top window for accurate amplitudes of sinusoids.
+    FlatTop,
+    /// The triangular window with zeros at both ends.
+    Bartlett,
+    /// The tapered cosine window whose parameter is the fraction of the
+    /// window inside the cosine tapers, ranging from a rectangular (0) to a
+    /// Hann window (1).
+    Tukey(f64),
+    /// The Gaussian window with the given standard deviation in samples.
+    Gaussian(f64),
+    /// The Kaiser window with the given shape parameter `β`.
+    Kaiser(f64),
+    /// The first discrete prolate spheroidal (Slepian) sequence with the
+    /// given time-half-bandwidth product `NW`.
+    Dpss(f64),
+}
+
+/// Computes a window function with `len` samples.
+///
+/// Symmetric windows are meant for filter design while periodic windows,
+/// which are computed as a symmetric window of length `len + 1` without its
+/// last sample, are meant for spectral analysis.
+pub fn window(window: Window, len: usize, periodic: bool) -> Vec<f64> {
+    if len <= 1 {
+        return vec![1.; len];
+    }
+    let m = if periodic { len + 1 } else { len };
+    let mut values = symmetric_window(window, m);
+    values.truncate(len);
+    values
+}
+
+fn symmetric_window(window: Window, m: usize) -> Vec<f64> {
+    let span = (m - 1) as f64;
+    let cosine_sum = |coefficients: &[f64]| {
+        (0..m)
+            .map(|k| {
+                coefficients
+                    .iter()
+                    .enumerate()
+                    .map(|(j, a)| {
+                        let sign = if j.is_multiple_of(2) { 1. } else { -1. };
+                        sign * a * (2. * PI * (j * k) as f64 / span).cos()
+                    })
+                    .sum()
+            })
+            .collect()
+    };
+
+    match window {
+        Window::Rectangular => vec![1.; m],
+        Window::Hann => cosine_sum(&[0.5, 0.5]),
+        Window::Hamming => cosine_sum(&[0.54, 0.46]),
+        Window::Blackman => cosine_sum(&[0.42, 0.5, 0.08]),
+        Window::BlackmanHarris => cosine_sum(&[0.35875, 0.48829, 0.14128, 0.01168]),
+        Window::FlatTop => cosine_sum(&[
+            0.21557895,
+            0.41663158,
+            0.277263158,
+            0.083578947,
+            0.006947368,
+        ]),
+        Window::Bartlett => (0..m)
+            .map(|k| 1. - (2. * k as f64 / span - 1.).abs())
+            .collect(),
+        Window::Tukey(alpha) if alpha <= 0. => vec![1.; m],
+        Window::Tukey(alpha) if alpha >= 1. => cosine_sum(&[0.5, 0.5]),
+        Window::Tukey(alpha) => (0..m)
+            .map(|k| {
+                // The distance to the nearer end relative to the taper width
+                let x = (k.min(m - 1 - k) as f64) / (alpha * span / 2.);
+                if x < 1. {
+                    0.5 * (1. - (PI * x).cos())
+                } else {
+                    1.
+                }
+            })
+            .collect(),
+        Window::Gaussian(std) => (0..m)
+            .map(|k| (-0.5 * ((k as f64 - span / 2.) / std).powi(2)).exp())
+            .collect(),
+        Window::Kaiser(beta) => (0..m)
+            .map(|k| {
+                let x = 2. * k as f64 / span - 1.;
+                bessel_i0(beta * (1. - x * x).max(0.).sqrt()) / bessel_i0(beta)
+            })
+            .collect(),
+        Window::Dpss(nw) => dpss(m, nw),
+    }
+}
+
+/// Computes the modified Bessel function of the first kind of order zero
+/// from its power series.
+pub(crate) fn bessel_i0(x: f64) -> f64 {
+    let y = 0.25 * x * x;
+    let mut term = 1.;
+    let mut sum = 1.;
+    for k in 1.. {
+        term *= y / (k * k) as f64;
+        sum += term;
+        if term < sum * 1e-17 {
+            break;
+        }
+    }
+    sum
+}
+
+/// Computes the first discrete prolate spheroidal sequence as the
+/// eigenvector of the largest eigenvalue of the tridiagonal matrix by Slepian
+/// (1978), normalized to a maximum of 1 with the correction for even lengths
+/// used by SciPy.
+fn dpss(m: usize, nw: f64) -> Vec<f64> {
+    let w = nw / m as f64;
+    let diag = (0..m)
+        .map(|k| (0.5 * (m as f64 - 1. - 2. * k as f64)).powi(2) * (2. * PI * w).cos())
+        .collect::<Vec<_>>();
+    let off = (1..m)
+        .map(|k| 0.5 * (k * (m - k)) as f64)
+        .collect::<Vec<_>>();
+
+    // The largest eigenvalue by bisection with Sturm sequence counts
+    let count_below = |x: f64| {
+        let mut count = 0;
+        let mut d = 1.;
+        for k in 0..m {
+            let e2 = if k == 0 { 0. } else { off[k - 1] * off[k - 1] };
+            d = diag[k] - x - e2 / d;
+            if d == 0. {
+                d = f64::EPSILON * (diag[k].abs() + x.abs()).max(f64::MIN_POSITIVE);
+            }
+            if d < 0. {
+                count += 1;
+            }
+        }
+        count
+    };
+    let radius = (0..m)
+        .map(|k| {
+            let left = if k > 0 { off[k - 1] } else { 0. };
+            let right = if k + 1 < m { off[k] } else { 0. };
+            diag[k].abs() + left + right
+        })
+        .fold(0f64, f64::max);
+    let (mut low, mut high) = (-radius, radius);
+    for _ in 0..200 {
+        let mid = 0.5 * (low + high);
+        if mid <= low || mid >= high {
+            break;
+        }
+        if count_below(mid) < m {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    // Inverse iteration with a shift just above the eigenvalue, for which
+    // the shifted matrix is negative definite
+    let shift = high + radius * 1e-12;
+    let shifted = diag.iter().map(|d| d - shift).collect::<Vec<_>>();
+    let mut vector = vec![1.; m];
+    for _ in 0..3 {
+        vector = thomas_algorithm_bands(&off, &shifted, &off, &vector);
+        let norm = vector.iter().map(|x| x * x).sum::<f64>().sqrt();
+        vector.iter_mut().for_each(|x| *x /= norm);
+    }
+
+    let max = vector
+        .iter()
+        .fold(0f64, |a, &b| if b.abs() > a.abs() { b } else { a });
+    let correction = if m.is_multiple_of(2) {
+        (m * m) as f64 / ((m * m) as f64 + nw)
+    } else {
+        1.
+    };
+    vector.iter().map(|x| x / max * correction).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn assert_close(a: &[f64], b: &[f64], epsilon: f64) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert_abs_diff_eq!(x, y, epsilon = epsilon);
+        }
+    }
+
+    #[test]
+    fn test_cosine_windows() {
+        assert_close(
+            &window(Window::Hann, 5, false),
+            &[0., 0.5, 1., 0.5, 0.],
+            1e-15,
+        );
+        assert_close(&window(Window::Hann, 4, true), &[0., 0.5, 1., 0.5], 1e-15);
+        assert_close(
+            &window(Window::Hamming, 5, false),
+            &[0.08, 0.54, 1., 0.54, 0.08],
+            1e-15,
+        );
+        assert_close(
+            &window(Window::Blackman, 5, false),
+            &[0., 0.34, 1., 0.34, 0.],
+            1e-15,
+        );
+        for kind in [Window::BlackmanHarris, Window::FlatTop] {
+            let values = window(kind, 7, false);
+            assert_abs_diff_eq!(values[3], 1., epsilon = 1e-8);
+            assert_abs_diff_eq!(values[0], values[6], epsilon = 1e-15);
+        }
+        assert_abs_diff_eq!(window(Window::BlackmanHarris, 7, false)[0], 6e-5);
+    }
+
+    #[test]
+    fn test_bartlett_and_tukey() {
+        assert_close(
+            &window(Window::Bartlett, 5, false),
+            &[0., 0.5, 1., 0.5, 0.],
+            1e-15,
+        );
+        assert_eq!(window(Window::Tukey(0.), 4, false), [1.; 4]);
+        assert_eq!(
+            window(Window::Tukey(1.), 5, false),
+            window(Window::Hann, 5, false)
+        );
+        assert_close(
+            &window(Window::Tukey(0.5), 9, false),
+            &[0., 0.5, 1., 1., 1., 1., 1., 0.5, 0.],
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn test_gaussian_and_kaiser() {
+        let values = window(Window::Gaussian(1.), 5, false);
+        assert_close(
+            &values,
+            &[
+                (-2f64).exp(),
+                (-0.5f64).exp(),
+                1.,
+                (-0.5f64).exp(),
+                (-2f64).exp(),
+            ],
+            1e-15,
+        );
+
+        assert_abs_diff_eq!(bessel_i0(0.), 1.);
+        assert_abs_diff_eq!(bessel_i0(1.), 1.2660658777520082, epsilon = 1e-15);
+        assert_abs_diff_eq!(bessel_i0(10.), 2815.716628466254, epsilon = 1e-9);
+        let values = window(Window::Kaiser(8.6), 5, false);
+        assert_abs_diff_eq!(values[0], 1. / bessel_i0(8.6), epsilon = 1e-15);
+        assert_abs_diff_eq!(values[2], 1.);
+        assert_abs_diff_eq!(values[1], 0.34039362244, epsilon = 1e-10);
+        assert_eq!(window(Window::Kaiser(0.), 3, false), [1.; 3]);
+    }
+
+    #[test]
+    fn test_dpss() {
+        assert_close(
+            &window(Window::Dpss(2.), 5, false),
+            &[
+                0.18654153925,
+                0.68539378672,
+                1.,
+                0.68539378672,
+                0.18654153925,
+            ],
+            1e-10,
+        );
+        let values = window(Window::Dpss(1.5), 4, false);
+        assert_abs_diff_eq!(values[0], values[3], epsilon = 1e-12);
+        assert_abs_diff_eq!(values[1], 16. / 17.5, epsilon = 1e-12);
+        assert_abs_diff_eq!(values[0], 0.34461490382, epsilon = 1e-10);
+
+        // A large window stays accurate and concentrated in the center
+        let values = window(Window::Dpss(4.), 1000, false);
+        assert!(values.iter().all(|&x| x > 0. && x <= 1.));
+        assert!(values[0] < 1e-3);
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        assert_eq!(window(Window::Hann, 0, false), []);
+        assert_eq!(window(Window::Hann, 1, true), [1.]);
+        assert_eq!(window(Window::Rectangular, 3, true), [1.; 3]);
+    }
+}
@@ -0,0 +1,114 @@
+#import "plugin.typ": komet-plugin
+
+
+/// Computes a statistic of `values` for each bin of `x`, e.g., the average
+/// $y$ value per $x$ bin for a profile plot. The values are sorted into bins
+/// by their corresponding `x` coordinate with the same bin semantics as
+/// `histogram`. Values whose `x` coordinate lies outside the bins are
+/// ignored. 
+/// 
+/// Returns a dictionary `(statistic: array, edges: array)` with the 
+/// statistic for each bin and the bin edges. Empty bins yield `float.nan`,
+/// except for the statistics `"count"` and `"sum"`. 
+/// 
+/// -> dictionary
+#let binned-statistic(
+
+  /// An array of float or integer coordinates used for binning. 
+  /// -> array
+  x,
+
+  /// An array of float or integer values to compute the statistic of. 
+  /// It needs to have the same length as `x`. 
+  /// -> array
+  values,
+
+  /// A number of bins or an explicit array of bin edges. 
+  /// -> int | array
+  bins: 10,
+
+  /// The statistic to compute. Options are `"count"`, `"sum"`, `"mean"`, 
+  /// `"median"`, `"std"` (population standard deviation), `"min"`, and
+  /// `"max"`. Alternatively, a float $q$ between 0 and 100 can be given to 
+  /// compute the $q$-th percentile. 
+  /// -> str | float
+  statistic: "mean",
+
+) = {
+  assert.eq(x.len(), values.len(), message: "x and values need to have the same length")
+  if type(bins) == array {
+    bins = bins.map(float).sorted()
+  }
+  if type(statistic) == int { statistic = float(statistic) }
+
+  let input = cbor.encode((
+    x.map(float),
+    values.map(float),
+    bins,
+    statistic,
+  ))
+
+  cbor(komet-plugin.binned_statistic(input))
+}
+
+
+/// Computes a statistic of `values` for each bin of a two-dimensional grid
+/// of `x` and `y` bins. 
+/// 
+/// Returns a dictionary `(statistic: array, x-edges: array, y-edges: array)`
+/// where `statistic` is a two-dimensional `m×n` array with one row for 
+/// each of the `m` $y$ bins and one column for each of the `n` $x$ bins. 
+/// This is the same layout that `contour` expects for `z`. 
+/// 
+/// -> dictionary
+#let binned-statistic-2d(
+
+  /// An array of float or integer $x$ coordinates used for binning. 
+  /// -> array
+  x,
+
+  /// An array of float or integer $y$ coordinates used for binning. 
+  /// -> array
+  y,
+
+  /// An array of float or integer values to compute the statistic of. 
+  /// It needs to have the same length as `x` and `y`. 
+  /// -> array
+  values,
+
+  /// A number of bins or an explicit array of bin edges along $x$. 
+  /// -> int | array
+  x-bins: 10,
+
+  /// A number of bins or an explicit array of bin edges along $y$. 
+  /// -> int | array
+  y-bins: 10,
+
+  /// The statistic to compute, see `binned-statistic`. 
+  /// -> str | float
+  statistic: "mean",
+
+) = {
+  assert.eq(x.len(), values.len(), message: "x and values need to have the same length")
+  assert.eq(y.len(), values.len(), message: "y and values need to have the same length")
+  if type(x-bins) == array {
+    x-bins = x-bins.map(float).sorted()
+  }
+  if type(y-bins) == array {
+    y-bins = y-bins.map(float).sorted()
+  }
+  if type(statistic) == int { statistic = float(statistic) }
+
+  let input = cbor.encode((
+    x.map(float),
+    y.map(float),
+    values.map(float),
+    x-bins,
+    y-bins,
+    statistic,
+  ))
+
+  let result = cbor(komet-plugin.binned_statistic_2d(input))
+  result.statistic = result.statistic.chunks(result.x-edges.len() - 1)
+  result
+}
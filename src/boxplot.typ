@@ -1,12 +1,26 @@
 #import "plugin.typ": komet-plugin
 
 
+#let convert-whiskers(whiskers) = {
+  if type(whiskers) == array { whiskers.map(float) } else { whiskers }
+}
+
+#let convert-notch(notch) = {
+  if type(notch) == dictionary {
+    let (resamples, confidence, seed) = (resamples: 1000, confidence: 0.95, seed: 0) + notch
+    notch = (resamples, float(confidence), seed)
+  }
+  notch
+}
+
 /// Computes the statistics needed to generate a box plot, including
 /// - `median`
 /// - first and third quartile `q1` and `q3`,
 /// - `min` and `max`,
 /// - lower and upper whisker positions `whisker-low` and `whisker-high`,
-/// - `mean`, and
+/// - `mean`, 
+/// - the confidence interval of the median `notch-low` and `notch-high` 
+///   for drawing notched boxes, and
 /// - an array of `outliers`. 
 /// 
 /// -> dictionary
@@ -16,11 +30,62 @@
   /// -> array
   values,
 
-  /// The position of the whiskers in terms of the inter-quartil distance `q3 - q1`. 
+  /// Optional non-negative weights for the values, e.g., sampling weights,
+  /// of which at least one needs to be positive. The mean, the quantiles,
+  /// and the notches are then weighted, while the medcouple of the 
+  /// `"adjusted"` whiskers ignores the weights. Values with zero weight are
+  /// left out entirely. 
+  /// -> none | array
+  weights: none,
+
+  /// The position of the whiskers in terms of the inter-quartil distance `q3 - q1`
+  /// or, for `whiskers: "std"`, in terms of the standard deviation. 
   /// -> float
   whisker-pos: 1.5,
 
+  /// The definition of the whiskers. Options are
+  /// - `"tukey"`: the most extreme values within `whisker-pos` times the
+  ///   inter-quartil distance from the quartiles, 
+  /// - `"adjusted"`: the adjusted box plot for skewed data by Hubert and
+  ///   Vandervieren which skews Tukey's fences based on the medcouple, 
+  /// - `"std"`: the most extreme values within `whisker-pos` standard 
+  ///   deviations from the mean, 
+  /// - `"range"`: the minimum and maximum, i.e., there are no outliers, 
+  /// - an array `(low, high)` of two percentiles between 0 and 100, e.g.,
+  ///   `(5, 95)`. 
+  /// 
+  /// Values beyond the whiskers are reported as outliers. 
+  /// -> str | array
+  whiskers: "tukey",
+
+  /// The method for computing the median and the quartiles, see `quantiles`
+  /// for the available options. 
+  /// -> str | int
+  quantile-method: "linear",
+
+  /// The method for computing the confidence interval of the median that 
+  /// is displayed by the notches. Options are
+  /// - `"mcgill"`: the interval $"median" ± 1.57 "IQR"\/sqrt(n)$ by McGill et al., 
+  /// - a dictionary `(resamples: int, confidence: float, seed: int)` for a 
+  ///   percentile bootstrap interval with a positive number of resamples and
+  ///   a confidence level between 0 and 1. Missing entries default to 
+  ///   `resamples: 1000`, `confidence: 0.95`, and `seed: 0`. 
+  /// -> str | dictionary
+  notch: "mcgill",
+
+  /// Whether to estimate the quantiles with the streaming P² algorithm 
+  /// instead of computing them exactly. This avoids copying the values 
+  /// which helps with very large inputs at the cost of approximate quartiles
+  /// and whiskers. Ignored for weighted values. 
+  /// -> bool
+  approximate: false,
+
 ) = {
+  notch = convert-notch(notch)
+  if weights != none {
+    assert.eq(weights.len(), values.len(), message: "values and weights need to have the same length")
+  }
+
   // let input = ((whisker-pos,) + values).map(float).map(float.to-bytes.with(size: 8, endian: "big")).join()
 
   // let (
@@ -37,8 +102,115 @@
 
   let input = cbor.encode((
     values.map(float),
-    float(whisker-pos)
+    if weights == none { none } else { weights.map(float) },
+    float(whisker-pos),
+    convert-whiskers(whiskers),
+    quantile-method,
+    notch,
+    approximate,
   ))
 
   cbor(komet-plugin.boxplot(input))
 }
+
+
+/// Computes the box plot statistics of many groups at once, see `boxplot`
+/// for the entries of each statistics dictionary. 
+/// 
+/// The groups can either be given as an array of value arrays, in which case
+/// an array of dictionaries is returned, or as a flat array of values together
+/// with a group label for each value. In the latter case, a dictionary mapping
+/// each label to its statistics is returned, with the groups in order of their
+/// first appearance. 
+/// 
+/// -> array | dictionary
+#let boxplots(
+
+  /// An array of value arrays or, if `labels` are given, a flat array of 
+  /// values. 
+  /// -> array
+  groups,
+
+  /// The group label of each value. 
+  /// -> none | array
+  labels: none,
+
+  /// The position of the whiskers, see `boxplot`. 
+  /// -> float
+  whisker-pos: 1.5,
+
+  /// The definition of the whiskers, see `boxplot`. 
+  /// -> str | array
+  whiskers: "tukey",
+
+  /// The method for computing the median and the quartiles, see `quantiles`
+  /// for the available options. 
+  /// -> str | int
+  quantile-method: "linear",
+
+  /// The method for computing the confidence interval of the median, see 
+  /// `boxplot`. 
+  /// -> str | dictionary
+  notch: "mcgill",
+
+) = {
+  let input = cbor.encode((
+    if labels == none { none } else { labels.map(str) },
+    if labels == none { groups.map(group => group.map(float)) } else { groups.map(float) },
+    float(whisker-pos),
+    convert-whiskers(whiskers),
+    quantile-method,
+    convert-notch(notch),
+  ))
+
+  cbor(komet-plugin.boxplots(input))
+}
+
+
+/// Computes the statistics needed to generate a letter-value plot (also known
+/// as boxen plot) after Hofmann, Wickham, and Kafadar. The letter values
+/// are the median, the fourths, the eighths, and so on, and reveal more of
+/// the tails of large samples than a box plot. 
+/// 
+/// Returns a dictionary with 
+/// - an array of `levels`, each a dictionary with the `depth` and the `lower`
+///   and `upper` letter value, starting with the median (where `lower` and 
+///   `upper` coincide), and
+/// - an array of `outliers` beyond the last level. 
+/// 
+/// -> dictionary
+#let letter-values(
+
+  /// An array of input float or integer values. 
+  /// -> array
+  values,
+
+  /// The rule for choosing the number of letter values. Options are
+  /// - `"tukey"`: $floor(log_2 n) - 3$ levels, 
+  /// - `"proportion"`: as many levels as needed for a proportion 
+  ///   `outlier-prop` of the values to lie beyond the last level, 
+  /// - `"trustworthy"`: only levels whose confidence intervals at the 
+  ///   significance level `trust-alpha` do not overlap, 
+  /// - `"full"`: the maximum of $floor(log_2 n) + 1$ levels,
+  /// - an integer to set the number of levels directly. 
+  /// -> str | int
+  depth: "tukey",
+
+  /// The proportion of outliers for `depth: "proportion"`. 
+  /// -> float
+  outlier-prop: 0.007,
+
+  /// The significance level for `depth: "trustworthy"`. 
+  /// -> float
+  trust-alpha: 0.05,
+
+) = {
+  let input = cbor.encode((
+    values.map(float),
+    float(outlier-prop),
+    float(trust-alpha),
+    depth,
+  ))
+
+  cbor(komet-plugin.letter_values(input))
+}
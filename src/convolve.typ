@@ -0,0 +1,191 @@
+#import "plugin.typ": komet-plugin
+
+
+#let convolve-impl(a, b, mode, method, correlate) = {
+  let input = cbor.encode((
+    a.map(float),
+    b.map(float),
+    mode,
+    method,
+    correlate,
+  ))
+
+  cbor(komet-plugin.convolve(input))
+}
+
+#let convolve-2d-impl(a, b, mode, method, correlate) = {
+  let shape(grid) = (grid.len(), if grid.len() == 0 { 0 } else { grid.at(0).len() })
+  for grid in (a, b) {
+    assert(
+      grid.all(row => row.len() == shape(grid).at(1)),
+      message: "All rows of a grid need to have the same length"
+    )
+  }
+
+  let input = cbor.encode((
+    a.sum(default: ()).map(float),
+    shape(a),
+    b.sum(default: ()).map(float),
+    shape(b),
+    mode,
+    method,
+    correlate,
+  ))
+
+  let result = cbor(komet-plugin.convolve_2d(input))
+  let (rows, cols) = result.shape
+  if rows == 0 or cols == 0 { return range(rows).map(_ => ()) }
+  result.values.chunks(cols)
+}
+
+
+/// Computes the discrete linear convolution $(a * b)[k] = sum_i a[i] b[k - i]$
+/// of two sequences, e.g., for smoothing a signal with a kernel.
+///
+/// Returns an array of floats.
+#let convolve(
+
+  /// The first sequence, e.g., a signal.
+  /// -> array
+  a,
+
+  /// The second sequence, e.g., a kernel.
+  /// -> array
+  b,
+
+  /// Which part of the convolution to return. Options are
+  /// - `"full"`: the full convolution of length $N + M - 1$,
+  /// - `"same"`: the central part with the same length as `a`,
+  /// - `"valid"`: the part where the sequences overlap completely, of length
+  ///   $max(N, M) - min(N, M) + 1$.
+  /// -> "full" | "same" | "valid"
+  mode: "full",
+
+  /// How to compute the convolution. The `"direct"` method computes the
+  /// sums directly while the `"fft"` method multiplies the spectra, which is
+  /// much faster for long sequences but subject to rounding errors relative
+  /// to the largest values. With `"auto"`, the faster method is chosen
+  /// based on the lengths.
+  /// -> "auto" | "direct" | "fft"
+  method: "auto",
+
+) = convolve-impl(a, b, mode, method, false)
+
+
+/// Computes the discrete cross-correlation $sum_i a[i + k] b[i]$ of two
+/// sequences, i.e., the convolution of `a` with the reversed `b`. In the
+/// `"full"` mode, the lags $k$ range from $-(M - 1)$ to $N - 1$.
+///
+/// Returns an array of floats.
+#let correlate(
+
+  /// The first sequence.
+  /// -> array
+  a,
+
+  /// The second sequence, e.g., a template.
+  /// -> array
+  b,
+
+  /// Which part of the correlation to return, see `convolve`.
+  /// -> "full" | "same" | "valid"
+  mode: "full",
+
+  /// How to compute the correlation, see `convolve`.
+  /// -> "auto" | "direct" | "fft"
+  method: "auto",
+
+) = convolve-impl(a, b, mode, method, true)
+
+
+/// Computes the two-dimensional linear convolution of two grids given as
+/// arrays of rows, e.g., for blurring an image with a kernel.
+///
+/// Returns an array of rows of floats.
+#let convolve-2d(
+
+  /// The first grid.
+  /// -> array
+  a,
+
+  /// The second grid, e.g., a kernel.
+  /// -> array
+  b,
+
+  /// Which part of the convolution to return, see `convolve`. The mode
+  /// applies to both axes.
+  /// -> "full" | "same" | "valid"
+  mode: "full",
+
+  /// How to compute the convolution, see `convolve`.
+  /// -> "auto" | "direct" | "fft"
+  method: "auto",
+
+) = convolve-2d-impl(a, b, mode, method, false)
+
+
+/// Computes the two-dimensional cross-correlation of two grids given as
+/// arrays of rows, i.e., the convolution of `a` with `b` reversed along both
+/// axes, e.g., for template matching.
+///
+/// Returns an array of rows of floats.
+#let correlate-2d(
+
+  /// The first grid.
+  /// -> array
+  a,
+
+  /// The second grid, e.g., a template.
+  /// -> array
+  b,
+
+  /// Which part of the correlation to return, see `convolve`.
+  /// -> "full" | "same" | "valid"
+  mode: "full",
+
+  /// How to compute the correlation, see `convolve`.
+  /// -> "auto" | "direct" | "fft"
+  method: "auto",
+
+) = convolve-2d-impl(a, b, mode, method, true)
+
+
+/// Computes the cross-correlation $sum_i x[i + k] y[i]$ of two signals as a
+/// function of the lag $k$. A peak at a positive lag indicates that `x` is
+/// delayed with respect to `y`.
+///
+/// Returns a dictionary with the integer `lags` in ascending order and the
+/// correlation `values` at these lags.
+/// -> dictionary
+#let cross-correlation(
+
+  /// The first signal.
+  /// -> array
+  x,
+
+  /// The second signal.
+  /// -> array
+  y,
+
+  /// The largest lag to return. By default, all lags at which the signals
+  /// overlap are returned.
+  /// -> none | int
+  max-lag: none,
+
+  /// Whether to normalize the correlation. In this case, the means are
+  /// subtracted from the signals and the result is divided by
+  /// $sqrt(sum (x - macron(x))^2 sum (y - macron(y))^2)$, so that the values
+  /// range from $-1$ to $1$.
+  /// -> bool
+  normalized: true,
+
+) = {
+  let input = cbor.encode((
+    x.map(float),
+    y.map(float),
+    max-lag,
+    normalized,
+  ))
+
+  cbor(komet-plugin.cross_correlation(input))
+}
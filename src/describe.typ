@@ -0,0 +1,43 @@
+#import "plugin.typ": komet-plugin
+
+
+/// Computes descriptive statistics of the given values. Returns a dictionary 
+/// with the entries
+/// - `count`: the number of values, 
+/// - `sum`, `mean`, `variance`, `std` (standard deviation), and `sem` 
+///   (standard error of the mean), 
+/// - `skewness` and `kurtosis` (excess kurtosis, both biased), 
+/// - `min`, `max`, `median`, and `mad` (median absolute deviation), 
+/// - `geometric-mean` and `harmonic-mean` (`nan` for negative values), 
+/// - `mode`: the most frequent value (the smallest one in case of ties). 
+/// 
+/// The moments and sums are computed with numerically stable algorithms. 
+/// 
+/// -> dictionary
+#let describe(
+
+  /// An array of float or integer values. 
+  /// -> array
+  values,
+
+  /// The delta degrees of freedom. The variance is computed with the 
+  /// divisor $n - "ddof"$. 
+  /// -> int | float
+  ddof: 1,
+
+  /// How to handle `nan` values: 
+  /// - `"propagate"`: all statistics become `nan`, 
+  /// - `"omit"`: `nan` values are ignored, 
+  /// - `"raise"`: an error is raised. 
+  /// -> str
+  nan-policy: "propagate",
+
+) = {
+  let input = cbor.encode((
+    values.map(float),
+    float(ddof),
+    nan-policy,
+  ))
+
+  cbor(komet-plugin.describe(input))
+}
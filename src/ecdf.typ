@@ -0,0 +1,41 @@
+#import "plugin.typ": komet-plugin
+
+
+/// Computes the empirical cumulative distribution function (ECDF) of the 
+/// given values as a step function. Returns a dictionary with the entries
+/// - `x`: the distinct values in ascending order at which the function jumps, 
+/// - `y`: the value of the function from each jump up to the next one. 
+/// 
+/// `nan` values are ignored. 
+/// 
+/// -> dictionary
+#let ecdf(
+
+  /// An array of float or integer values. 
+  /// -> array
+  values,
+
+  /// Optional non-negative weights for the values. At least one weight needs
+  /// to be positive.
+  /// -> none | array
+  weights: none,
+
+  /// If set to `true`, the complementary cumulative distribution function
+  /// (survival function) $1 - F(x)$, i.e., the proportion of values 
+  /// greater than $x$, is computed instead. 
+  /// -> bool
+  complementary: false,
+
+) = {
+  if weights != none {
+    assert.eq(weights.len(), values.len(), message: "values and weights need to have the same length")
+  }
+
+  let input = cbor.encode((
+    values.map(float),
+    if weights == none { none } else { weights.map(float) },
+    complementary,
+  ))
+
+  cbor(komet-plugin.ecdf(input))
+}
@@ -1,32 +1,57 @@
 #import "plugin.typ": komet-plugin
 
+#let convert-complex(values) = values.map(x => {
+  if type(x) in (int, float) { return (float(x), 0.) }
+  assert(
+    type(x) == array and x.len() == 2,
+    message: "A complex number can consist of one or two floats, got " + repr(x)
+  )
+  x.map(float)
+})
+
+#let convert-norm(norm) = if norm == "backward" { 1 } else if norm == "ortho" { 2 } else { 3 }
+
+// Splits a window given as a name or a name/parameter pair. 
+#let convert-window(window) = {
+  if window == none { return (none, none) }
+  if type(window) == str { return (window, none) }
+  assert(
+    type(window) == array and window.len() == 2 and type(window.at(0)) == str,
+    message: "A window can be a name or a pair of a name and a parameter, got " + repr(window)
+  )
+  (window.at(0), float(window.at(1)))
+}
+
+// A reference power for decibels is either a number or `"max"`. 
+#let convert-reference(reference) = if reference == "max" { reference } else { float(reference) }
+
 #let fft-impl(
-  values, direction: "forward", norm: "backward"
+  values, direction: "forward", norm: "backward", window: none, 
+  output: "complex", reference: 1
 ) = {
-  values = values.map(x => {
-    if type(x) in (int, float) { return (float(x), 0.) }
-    assert(
-      type(x) == array and x.len() == 2,
-      message: "A complex number can consist of one or two floats, got " + repr(x)
-    )
-    x.map(float)
-  })
+  values = convert-complex(values)
 
-  let fft = if direction == "forward" { 
-    komet-plugin.fft 
+  // Only the forward transform takes a window
+  let (fft, window) = if direction == "forward" { 
+    (komet-plugin.fft, convert-window(window))
   } else { 
-    komet-plugin.ifft 
+    (komet-plugin.ifft, ())
   }
 
-  let norm-code = if norm == "backward" { 1 } else if norm == "ortho" { 2 } else { 3 }
-
-  cbor(fft(cbor.encode((norm-code, values))))
+  cbor(fft(cbor.encode((
+    convert-norm(norm),
+    values,
+    ..window,
+    output,
+    convert-reference(reference),
+  ))))
 }
 
 
 /// Computes the discrete Fourier transform (DFT). 
 /// 
-/// Returns an array of complex (i.e., real/imaginary pairs of floats) values. 
+/// Returns an array of complex (i.e., real/imaginary pairs of floats) values
+/// or an array of floats, depending on `output`. 
 #let fft(
 
   /// An array of real (`float`) or complex (real/imaginary pairs of `float`) 
@@ -39,14 +64,41 @@
   /// - `"forward"`: the entire normalization of $1/N$ happens at the forward DFT. 
   /// - `"ortho"`: the normalization is split across DFT and its inverse and to both the factor $1/√N$ is applied. 
   /// -> "backward" | "forward" | "ortho"
-  norm: "backward"
+  norm: "backward",
+
+  /// A window that is applied to the values before the transform, given as
+  /// a name or a pair of a name and a parameter, see `window`. The periodic
+  /// form of the window is used. 
+  /// -> none | str | array
+  window: none,
+
+  /// How to represent the result. Options are:
+  /// - `"complex"`: complex (real/imaginary pairs of floats) values. 
+  /// - `"magnitude"`: the magnitudes $|X|$. 
+  /// - `"power"`: the powers $|X|^2$. 
+  /// - `"db"`: the powers in decibels relative to `reference`, i.e., 
+  ///   $10 log_10 (|X|^2 \/ "reference")$. 
+  /// - `"phase"`: the phase angles in radians in $(-pi, pi]$. 
+  /// - `"unwrapped-phase"`: the phase angles without jumps of more than 
+  ///   $pi$ between consecutive values. 
+  /// -> "complex" | "magnitude" | "power" | "db" | "phase" | "unwrapped-phase"
+  output: "complex",
 
-) = fft-impl(values, direction: "forward", norm: norm)
+  /// The reference power for `output: "db"`, either a number or `"max"` for 
+  /// the largest power, in which case the peak is at 0 dB. 
+  /// -> int | float | "max"
+  reference: 1,
+
+) = fft-impl(
+  values, direction: "forward", norm: norm, window: window, 
+  output: output, reference: reference
+)
 
 
 /// Computes the inverse discrete Fourier transform (DFT). 
 /// 
-/// Returns an array of complex (i.e., real/imaginary pairs of floats) values. 
+/// Returns an array of complex (i.e., real/imaginary pairs of floats) values
+/// or an array of floats, depending on `output`. 
 #let ifft(
 
   /// An array of real (`float`) or complex (real/imaginary pairs of `float`) 
@@ -59,6 +111,603 @@
   /// - `"forward"`: the entire normalization of $1/N$ happens at the forward DFT. 
   /// - `"ortho"`: the normalization is split across DFT and its inverse and to both the factor $1/√N$ is applied. 
   /// -> "backward" | "forward" | "ortho"
+  norm: "backward",
+
+  /// How to represent the result, see `fft`. 
+  /// -> "complex" | "magnitude" | "power" | "db" | "phase" | "unwrapped-phase"
+  output: "complex",
+
+  /// The reference power for `output: "db"`, see `fft`. 
+  /// -> int | float | "max"
+  reference: 1,
+
+) = fft-impl(
+  values, direction: "inverse", norm: norm, 
+  output: output, reference: reference
+)
+
+
+/// Computes a window function for spectral analysis or filter design. 
+/// 
+/// Returns an array of `len` floats. 
+#let window(
+
+  /// The kind of window. Windows with a parameter are given as a pair of the
+  /// name and the parameter. Options are:
+  /// - `"rectangular"`, `"hann"`, `"hamming"`, `"blackman"`, 
+  ///   `"blackman-harris"`, `"flattop"`, and `"bartlett"`.
+  /// - `("tukey", alpha)`: a tapered cosine window with the fraction `alpha`
+  ///   between 0 and 1 of the window inside the tapers. 
+  /// - `("gaussian", std)`: a Gaussian window with the positive standard
+  ///   deviation `std` in samples. 
+  /// - `("kaiser", beta)`: a Kaiser window with the non-negative shape
+  ///   parameter `beta`. 
+  /// - `("dpss", nw)`: the first discrete prolate spheroidal (Slepian) 
+  ///   sequence with the positive time-half-bandwidth product `nw`. 
+  /// -> str | array
+  kind,
+
+  /// The number of samples. 
+  /// -> int
+  len,
+
+  /// Whether to compute the periodic form of the window, i.e., a symmetric
+  /// window of length `len + 1` without its last sample, which is suited for
+  /// spectral analysis. The symmetric form is suited for filter design. 
+  /// -> bool
+  periodic: false
+
+) = cbor(komet-plugin.window(cbor.encode((..convert-window(kind), len, periodic))))
+
+
+/// Computes the discrete Fourier transform (DFT) of real values. Since the 
+/// spectrum of real values is Hermitian-symmetric, only the $N\/2 + 1$ 
+/// non-negative frequency bins are computed, which is about twice as fast as
+/// `fft`. 
+/// 
+/// Returns an array of complex (i.e., real/imaginary pairs of floats) values
+/// or an array of floats, depending on `output`. 
+#let rfft(
+
+  /// An array of real (`int` or `float`) values. 
+  /// -> array
+  values,
+
+  /// How to normalize the output, see `fft`. 
+  /// -> "backward" | "forward" | "ortho"
+  norm: "backward",
+
+  /// How to represent the result, see `fft`. 
+  /// -> "complex" | "magnitude" | "power" | "db" | "phase" | "unwrapped-phase"
+  output: "complex",
+
+  /// The reference power for `output: "db"`, see `fft`. 
+  /// -> int | float | "max"
+  reference: 1,
+
+) = cbor(komet-plugin.rfft(cbor.encode((
+  convert-norm(norm),
+  values.map(float),
+  output,
+  convert-reference(reference),
+))))
+
+
+/// Computes the frequencies of the bins of `fft` for `n` values with the
+/// given sample spacing, i.e., the non-negative frequencies followed by the
+/// negative ones. 
+/// 
+/// Returns an array of `n` floats. 
+#let fftfreq(
+
+  /// The number of values. 
+  /// -> int
+  n,
+
+  /// The spacing between samples, i.e., the inverse sample rate. The 
+  /// frequencies are given in cycles per unit of the spacing. 
+  /// -> int | float
+  spacing: 1,
+
+) = cbor(komet-plugin.fftfreq(cbor.encode((n, float(spacing), false))))
+
+
+/// Computes the frequencies of the bins of `rfft` for `n` values with the
+/// given sample spacing, i.e., the $floor(n\/2) + 1$ non-negative 
+/// frequencies. 
+/// 
+/// Returns an array of floats. 
+#let rfftfreq(
+
+  /// The number of values. 
+  /// -> int
+  n,
+
+  /// The spacing between samples, see `fftfreq`. 
+  /// -> int | float
+  spacing: 1,
+
+) = cbor(komet-plugin.fftfreq(cbor.encode((n, float(spacing), true))))
+
+
+/// Computes the inverse of `rfft`, i.e., the real values whose DFT has the 
+/// given non-negative frequency bins. 
+/// 
+/// Returns an array of floats. 
+#let irfft(
+
+  /// An array of real (`float`) or complex (real/imaginary pairs of `float`) 
+  /// frequency bins. 
+  /// -> array
+  values,
+
+  /// The length of the output. Since both an even and an odd length yield
+  /// the same number of bins, the length cannot be inferred in general. 
+  /// By default, an even length of $2(m - 1)$ for $m$ bins is assumed. 
+  /// Missing bins are treated as zero and superfluous bins are ignored. 
+  /// -> none | int
+  n: none,
+
+  /// How to normalize the output, see `fft`. 
+  /// -> "backward" | "forward" | "ortho"
+  norm: "backward"
+
+) = cbor(komet-plugin.irfft(cbor.encode((convert-norm(norm), n, convert-complex(values)))))
+
+
+#let is-complex(x) = type(x) == array and x.len() == 2 and x.all(v => type(v) in (int, float))
+
+// The shape of a nested array whose innermost entries are numbers or 
+// complex numbers. 
+#let grid-shape(values) = {
+  let shape = ()
+  while type(values) == array and not is-complex(values) {
+    shape.push(values.len())
+    values = values.at(0, default: none)
+  }
+  shape
+}
+
+#let normalize-axes(axes, ndim) = {
+  if axes == auto { return range(ndim) }
+  if type(axes) == int { axes = (axes,) }
+  axes.map(axis => {
+    if axis < 0 { axis += ndim }
+    assert(0 <= axis and axis < ndim, message: "axis out of range for a grid with " + str(ndim) + " dimensions")
+    axis
+  })
+}
+
+#let fftn-impl(values, axes, lengths, inverse, norm) = {
+  let shape = grid-shape(values)
+  axes = normalize-axes(axes, shape.len())
+  if lengths != none {
+    assert.eq(lengths.len(), axes.len(), message: "lengths and axes need to have the same length")
+    lengths = lengths.zip(axes).map(((len, axis)) => if len == none { shape.at(axis) } else { len })
+  }
+
+  for _ in range(shape.len() - 1) { values = values.join() }
+
+  let input = cbor.encode((
+    convert-norm(norm),
+    inverse,
+    shape,
+    convert-complex(values),
+    axes,
+    lengths,
+  ))
+  let (shape, values) = cbor(komet-plugin.fftn(input))
+
+  for len in shape.slice(1).rev() { values = values.chunks(len) }
+  values
+}
+
+
+/// Computes the $N$-dimensional discrete Fourier transform of a grid given 
+/// as nested arrays in row-major order, e.g., an array of rows for a 
+/// two-dimensional grid like for `contour`. 
+/// 
+/// The entries of the grid can be real or complex (real/imaginary pairs of 
+/// floats). Note that an innermost array of two numbers is always interpreted 
+/// as a complex number. 
+/// 
+/// Returns a grid of complex values with the same nesting. 
+/// -> array
+#let fftn(
+
+  /// A grid of real (`float`) or complex (real/imaginary pairs of `float`) 
+  /// values. 
+  /// -> array
+  values,
+
+  /// The axes along which to transform. Negative axes count from the last 
+  /// one. By default, all axes are transformed. 
+  /// -> auto | int | array
+  axes: auto,
+
+  /// The lengths of the transformed axes (one per entry in `axes`). An axis 
+  /// is cropped if its length is larger and zero-padded if it is smaller. 
+  /// An entry of `none` keeps the length of the respective axis. 
+  /// -> none | array
+  lengths: none,
+
+  /// How to normalize the output, see `fft`. The normalization factor is 
+  /// based on the product of the transformed lengths. 
+  /// -> "backward" | "forward" | "ortho"
+  norm: "backward"
+
+) = fftn-impl(values, axes, lengths, false, norm)
+
+
+/// Computes the inverse of `fftn`. 
+/// 
+/// Returns a grid of complex values with the same nesting. 
+/// -> array
+#let ifftn(
+
+  /// A grid of real (`float`) or complex (real/imaginary pairs of `float`) 
+  /// values. 
+  /// -> array
+  values,
+
+  /// The axes along which to transform, see `fftn`. 
+  /// -> auto | int | array
+  axes: auto,
+
+  /// The lengths of the transformed axes, see `fftn`. 
+  /// -> none | array
+  lengths: none,
+
+  /// How to normalize the output, see `fft`. 
+  /// -> "backward" | "forward" | "ortho"
+  norm: "backward"
+
+) = fftn-impl(values, axes, lengths, true, norm)
+
+
+/// Computes the two-dimensional discrete Fourier transform of a grid given as 
+/// an array of rows. This is equivalent to `fftn` with `axes: (0, 1)`. 
+/// 
+/// Returns an array of rows of complex values. 
+/// -> array
+#let fft2(
+
+  /// An array of rows with real (`float`) or complex (real/imaginary pairs 
+  /// of `float`) values. 
+  /// -> array
+  values,
+
+  /// The numbers of rows and columns `(rows, columns)` to crop or zero-pad 
+  /// the grid to before the transform, see `fftn`. 
+  /// -> none | array
+  lengths: none,
+
+  /// How to normalize the output, see `fft`. 
+  /// -> "backward" | "forward" | "ortho"
   norm: "backward"
 
-) = fft-impl(values, direction: "inverse", norm: norm)
+) = fftn-impl(values, (0, 1), lengths, false, norm)
+
+
+/// Computes the inverse of `fft2`. 
+/// 
+/// Returns an array of rows of complex values. 
+/// -> array
+#let ifft2(
+
+  /// An array of rows with real (`float`) or complex (real/imaginary pairs 
+  /// of `float`) values. 
+  /// -> array
+  values,
+
+  /// The numbers of rows and columns `(rows, columns)`, see `fft2`. 
+  /// -> none | array
+  lengths: none,
+
+  /// How to normalize the output, see `fft`. 
+  /// -> "backward" | "forward" | "ortho"
+  norm: "backward"
+
+) = fftn-impl(values, (0, 1), lengths, true, norm)
+
+
+#let shift-grid(values, axes, inverse, depth: 0) = {
+  if depth in axes {
+    let n = values.len()
+    let k = if inverse { calc.quo(n, 2) } else { n - calc.quo(n, 2) }
+    values = values.slice(k) + values.slice(0, k)
+  }
+  if axes.any(axis => axis > depth) {
+    values = values.map(row => shift-grid(row, axes, inverse, depth: depth + 1))
+  }
+  values
+}
+
+
+/// Shifts the zero-frequency component of a spectrum computed with `fft`, 
+/// `fft2`, or `fftn` to the center of the given axes, e.g., for rendering 
+/// a spectrum as a heatmap. 
+/// -> array
+#let fftshift(
+
+  /// A one- or multi-dimensional array of real or complex values. 
+  /// -> array
+  values,
+
+  /// The axes to shift. By default, all axes are shifted. 
+  /// -> auto | int | array
+  axes: auto,
+
+) = shift-grid(values, normalize-axes(axes, grid-shape(values).len()), false)
+
+
+/// Undoes `fftshift`. For odd lengths, the two operations differ by one
+/// position. 
+/// -> array
+#let ifftshift(
+
+  /// A one- or multi-dimensional array of real or complex values. 
+  /// -> array
+  values,
+
+  /// The axes to shift. By default, all axes are shifted. 
+  /// -> auto | int | array
+  axes: auto,
+
+) = shift-grid(values, normalize-axes(axes, grid-shape(values).len()), true)
+
+
+#let psd-impl(
+  values, sample-rate, window, segment-length, overlap, detrend, one-sided, scaling
+) = {
+  cbor(komet-plugin.welch(cbor.encode((
+    values.map(float),
+    float(sample-rate),
+    ..convert-window(window),
+    segment-length,
+    overlap,
+    detrend,
+    one-sided,
+    scaling,
+  ))))
+}
+
+
+/// Estimates the power spectrum of a signal with Welch's method, i.e., by 
+/// averaging the periodograms of overlapping windowed segments, which 
+/// reduces the variance of the estimate at the cost of frequency 
+/// resolution. 
+/// 
+/// Returns a dictionary with the `frequencies` and the `power` at these 
+/// frequencies. 
+/// -> dictionary
+#let welch(
+
+  /// An array of real (`int` or `float`) values sampled at a constant rate. 
+  /// -> array
+  values,
+
+  /// The number of samples per unit of time. The frequencies are given in 
+  /// the inverse of this unit, e.g., in Hz for samples per second. 
+  /// -> int | float
+  sample-rate: 1,
+
+  /// The window applied to each segment, see `window`. 
+  /// -> none | str | array
+  window: "hann",
+
+  /// The number of samples per segment. By default, segments have 256 
+  /// samples or the length of the signal if it is shorter. 
+  /// -> none | int
+  segment-length: none,
+
+  /// The number of samples shared by consecutive segments. By default, 
+  /// segments overlap by half their length. 
+  /// -> none | int
+  overlap: none,
+
+  /// How each segment is detrended before its spectrum is computed. 
+  /// -> "none" | "constant" | "linear"
+  detrend: "constant",
+
+  /// Whether to return only the non-negative frequencies. Otherwise, the
+  /// frequencies are in the order of `fft`. 
+  /// -> bool
+  one-sided: true,
+
+  /// How to scale the power. With `"density"`, the power spectral density 
+  /// (e.g., V²/Hz) is computed whose integral is the mean power of the 
+  /// signal. With `"spectrum"`, the power spectrum (e.g., V²) is computed
+  /// for which a sinusoid of amplitude $A$ has a peak of $A^2\/2$. 
+  /// -> "density" | "spectrum"
+  scaling: "density",
+
+) = psd-impl(values, sample-rate, window, segment-length, overlap, detrend, one-sided, scaling)
+
+
+/// Estimates the power spectrum of a signal from the periodogram of the 
+/// entire signal. 
+/// 
+/// Returns a dictionary with the `frequencies` and the `power` at these 
+/// frequencies. 
+/// -> dictionary
+#let periodogram(
+
+  /// An array of real (`int` or `float`) values sampled at a constant rate. 
+  /// -> array
+  values,
+
+  /// The number of samples per unit of time, see `welch`. 
+  /// -> int | float
+  sample-rate: 1,
+
+  /// The window applied to the signal, see `window`. 
+  /// -> none | str | array
+  window: "rectangular",
+
+  /// How the signal is detrended before its spectrum is computed. 
+  /// -> "none" | "constant" | "linear"
+  detrend: "constant",
+
+  /// Whether to return only the non-negative frequencies. 
+  /// -> bool
+  one-sided: true,
+
+  /// How to scale the power, see `welch`. 
+  /// -> "density" | "spectrum"
+  scaling: "density",
+
+) = psd-impl(values, sample-rate, window, values.len(), 0, detrend, one-sided, scaling)
+
+
+/// Computes the short-time Fourier transform (STFT) of a signal, i.e., the 
+/// spectra of overlapping windowed segments, or a spectrogram showing how 
+/// the spectrum evolves over time. 
+/// 
+/// Returns a dictionary with the `times` of the segment centers, the 
+/// non-negative `frequencies`, and the `values` as an array with one row per
+/// frequency and one column per time. This is the layout of the `z` 
+/// argument of `contour` with the times as `x` and the frequencies as `y`,
+/// so that a spectrogram can be shown directly as a heatmap. The spectra 
+/// are scaled by the inverse sum of the window, i.e., a sinusoid of 
+/// amplitude $A$ at the center of a frequency bin has a magnitude of $A\/2$.
+/// -> dictionary
+#let stft(
+
+  /// An array of real (`int` or `float`) values sampled at a constant rate. 
+  /// -> array
+  values,
+
+  /// The number of samples per unit of time, which determines the units of 
+  /// the times and the frequencies. 
+  /// -> int | float
+  sample-rate: 1,
+
+  /// The window applied to each segment, see `window`. 
+  /// -> none | str | array
+  window: "hann",
+
+  /// The number of samples per segment. 
+  /// -> int
+  segment-length: 256,
+
+  /// The number of samples between the starts of consecutive segments. By 
+  /// default, segments overlap by half their length. 
+  /// -> none | int
+  hop: none,
+
+  /// The length to which each segment is padded with zeros before its 
+  /// transform, which yields a finer frequency grid. 
+  /// -> none | int
+  fft-length: none,
+
+  /// Whether to pad the signal with zeros by half a segment at both ends and
+  /// at the end to a whole number of segments, so that the first segment is
+  /// centered at the first sample and every sample is covered by a segment. 
+  /// -> bool
+  padding: true,
+
+  /// How to represent the spectra. Options are:
+  /// - `"complex"`: complex (real/imaginary pairs of floats) values, 
+  ///   which can be inverted with `istft`. 
+  /// - `"magnitude"`: the magnitudes $|X|$. 
+  /// - `"power"`: the powers $|X|^2$. 
+  /// - `"db"`: the powers in decibels, i.e., $10 log_10 |X|^2$, where 
+  ///   values more than 120 dB below the maximum are clipped. 
+  /// -> "complex" | "magnitude" | "power" | "db"
+  output: "complex",
+
+) = {
+  let result = cbor(komet-plugin.stft(cbor.encode((
+    values.map(float),
+    float(sample-rate),
+    ..convert-window(window),
+    segment-length,
+    hop,
+    fft-length,
+    padding,
+    output,
+  ))))
+  let columns = result.times.len()
+  result.values = if columns == 0 {
+    result.frequencies.map(_ => ())
+  } else {
+    result.values.chunks(columns)
+  }
+  result
+}
+
+
+/// Reconstructs a signal from its short-time Fourier transform by the 
+/// weighted overlap-add method. The options need to be the same as for 
+/// the call of `stft` that computed the transform. 
+/// 
+/// Returns an array of floats. 
+#let istft(
+
+  /// The complex values computed by `stft` with one row per frequency. 
+  /// -> array
+  values,
+
+  /// The window applied to each segment, see `stft`. 
+  /// -> none | str | array
+  window: "hann",
+
+  /// The number of samples per segment, see `stft`. 
+  /// -> int
+  segment-length: 256,
+
+  /// The number of samples between the starts of consecutive segments, see
+  /// `stft`. 
+  /// -> none | int
+  hop: none,
+
+  /// The length to which each segment was padded, see `stft`. 
+  /// -> none | int
+  fft-length: none,
+
+  /// Whether the signal was padded, see `stft`. 
+  /// -> bool
+  padding: true,
+
+  /// The length of the signal. By default, the signal extends to the end of 
+  /// the last segment, excluding the padding. 
+  /// -> none | int
+  len: none,
+
+) = cbor(komet-plugin.istft(cbor.encode((
+  convert-complex(values.sum(default: ())),
+  1.0,
+  ..convert-window(window),
+  segment-length,
+  hop,
+  fft-length,
+  padding,
+  len,
+))))
+
+
+/// Computes the analytic signal $x + i H(x)$ of real values via the FFT, 
+/// whose imaginary part is the Hilbert transform $H(x)$, together with the 
+/// instantaneous amplitude, phase and frequency, e.g., for demodulating an 
+/// amplitude- or frequency-modulated signal. The signal is treated as 
+/// periodic, so the results are distorted near the ends unless the signal 
+/// spans whole periods. 
+/// 
+/// Returns a dictionary with the complex (real/imaginary pairs of floats) 
+/// analytic signal `values`, the `envelope` (the magnitudes of the analytic 
+/// signal), the unwrapped `phase` in radians, and the instantaneous 
+/// `frequency` in cycles per unit of time. 
+/// -> dictionary
+#let hilbert(
+
+  /// An array of real (`int` or `float`) values sampled at a constant rate. 
+  /// -> array
+  values,
+
+  /// The number of samples per unit of time, which determines the unit of 
+  /// the instantaneous frequency. 
+  /// -> int | float
+  sample-rate: 1,
+
+) = cbor(komet-plugin.hilbert(cbor.encode((values.map(float), float(sample-rate)))))
@@ -0,0 +1,187 @@
+#import "plugin.typ": komet-plugin
+#import "fft.typ": convert-window
+
+
+// Splits a design given as a name or a name/parameter pair.
+#let convert-design(design) = {
+  if type(design) == str { return (design, none) }
+  assert(
+    type(design) == array and design.len() == 2 and type(design.at(0)) == str,
+    message: "A design can be a name or a pair of a name and a parameter, got " + repr(design)
+  )
+  (design.at(0), float(design.at(1)))
+}
+
+#let convert-cutoff(cutoff) = if type(cutoff) == array { cutoff.map(float) } else { (float(cutoff),) }
+
+#let filter-impl(coefficients, values, zero-phase, padding) = {
+  values = values.map(float)
+  if type(coefficients) == dictionary {
+    let input = cbor.encode((
+      coefficients.b.map(float),
+      coefficients.at("a", default: (1,)).map(float),
+      values,
+      zero-phase,
+      padding,
+    ))
+    return cbor(komet-plugin.lfilter(input))
+  }
+  if coefficients.len() > 0 and coefficients.all(section => type(section) == array) {
+    assert(
+      coefficients.all(section => section.len() == 6),
+      message: "Each second-order section needs 6 coefficients"
+    )
+    let input = cbor.encode((
+      coefficients.join().map(float),
+      values,
+      zero-phase,
+      padding,
+    ))
+    return cbor(komet-plugin.sosfilt(input))
+  }
+  let input = cbor.encode((coefficients.map(float), (1.0,), values, zero-phase, padding))
+  cbor(komet-plugin.lfilter(input))
+}
+
+
+/// Designs a digital IIR filter, e.g., for removing noise from sensor data
+/// with `filtfilt`. The filter is returned as a cascade of second-order
+/// sections, which is numerically more robust than a single transfer
+/// function of high order.
+///
+/// Returns an array of sections, each an array of the six coefficients
+/// `(b0, b1, b2, 1, a1, a2)` of the transfer function
+/// $(b_0 + b_1 z^(-1) + b_2 z^(-2)) \/ (1 + a_1 z^(-1) + a_2 z^(-2))$.
+/// -> array
+#let iir-filter(
+
+  /// The order of the filter. Band-pass and band-stop filters have twice
+  /// this order.
+  /// -> int
+  order,
+
+  /// The cutoff frequency in the units of `sample-rate` or, for band-pass
+  /// and band-stop filters, a pair of the lower and upper cutoff. Cutoffs
+  /// need to lie between zero and half the sample rate.
+  /// -> int | float | array
+  cutoff,
+
+  /// Which frequencies to pass.
+  /// -> "lowpass" | "highpass" | "bandpass" | "bandstop"
+  band: "lowpass",
+
+  /// The family of the filter. Families with a parameter are given as a
+  /// pair of the name and the parameter. Options are:
+  /// - `"butterworth"`: a maximally flat passband with a gain of $-3$ dB at
+  ///   the cutoff.
+  /// - `("chebyshev1", ripple)`: a steeper roll-off with a passband ripple
+  ///   of `ripple` dB, which is also the attenuation at the cutoff.
+  /// - `("chebyshev2", attenuation)`: a steeper roll-off with a stopband
+  ///   attenuation of at least `attenuation` dB beyond the cutoff.
+  /// - `"bessel"`: a maximally flat group delay, which preserves the
+  ///   waveform in the passband, for orders up to 15.
+  /// -> str | array
+  design: "butterworth",
+
+  /// The number of samples per unit of time.
+  /// -> int | float
+  sample-rate: 1,
+
+) = cbor(komet-plugin.iir_filter(cbor.encode((
+  ..convert-design(design),
+  order,
+  band,
+  convert-cutoff(cutoff),
+  float(sample-rate),
+))))
+
+
+/// Designs a linear-phase FIR filter by the window method, i.e., as the
+/// windowed impulse response of the ideal filter. The coefficients are
+/// scaled to a gain of one at zero frequency, at the Nyquist frequency for
+/// high-pass filters or at the center of the passband for band-pass
+/// filters.
+///
+/// Returns an array of `taps` floats.
+#let fir-filter(
+
+  /// The number of coefficients. High-pass and band-stop filters need an
+  /// odd number.
+  /// -> int
+  taps,
+
+  /// The cutoff frequency or frequencies, see `iir-filter`.
+  /// -> int | float | array
+  cutoff,
+
+  /// Which frequencies to pass.
+  /// -> "lowpass" | "highpass" | "bandpass" | "bandstop"
+  band: "lowpass",
+
+  /// The window applied to the impulse response, see `window`.
+  /// -> none | str | array
+  window: "hamming",
+
+  /// The number of samples per unit of time.
+  /// -> int | float
+  sample-rate: 1,
+
+) = cbor(komet-plugin.fir_filter(cbor.encode((
+  taps,
+  band,
+  convert-cutoff(cutoff),
+  float(sample-rate),
+  ..convert-window(window),
+))))
+
+
+/// Filters a signal once in forward direction. The filter starts at rest,
+/// so the output is delayed with respect to the input and begins with a
+/// transient.
+///
+/// Returns an array of floats.
+#let lfilter(
+
+  /// The filter, given as
+  /// - an array of second-order sections computed with `iir-filter`,
+  /// - an array of FIR coefficients computed with `fir-filter`, or
+  /// - a dictionary with the numerator `b` and the denominator `a` of a
+  ///   transfer function $(b_0 + b_1 z^(-1) + ...) \/ (a_0 + a_1 z^(-1) + ...)$.
+  /// -> array | dictionary
+  coefficients,
+
+  /// An array of real (`int` or `float`) values sampled at a constant rate.
+  /// -> array
+  values,
+
+) = filter-impl(coefficients, values, false, none)
+
+
+/// Filters a signal forward and backward, which results in zero phase
+/// shift, i.e., features of the signal stay in place, and in the squared
+/// magnitude response of the filter. Both passes start in the steady state
+/// of the first value to suppress transients.
+///
+/// Returns an array of floats.
+#let filtfilt(
+
+  /// The filter, see `lfilter`.
+  /// -> array | dictionary
+  coefficients,
+
+  /// An array of real (`int` or `float`) values sampled at a constant rate.
+  /// -> array
+  values,
+
+  /// How to extend the signal at both ends before filtering to reduce edge
+  /// effects. The signal is extended by three times the number of
+  /// coefficients, but by less than its length. Options are:
+  /// - `"odd"`: a point reflection at the end points, which preserves
+  ///   the slope.
+  /// - `"even"`: a mirror image at the end points.
+  /// - `"constant"`: repetitions of the end points.
+  /// - `none`: no extension.
+  /// -> "odd" | "even" | "constant" | none
+  padding: "odd",
+
+) = filter-impl(coefficients, values, true, padding)
@@ -4,8 +4,10 @@
 /// Computes a histogram by counting values that fall into a set of intervals,
 /// the so-called bins. 
 /// 
-/// Returns a dictionary `(counts: array, edges: array)` of histogram counts 
-/// and bin edges. 
+/// Returns a dictionary `(counts: array, edges: array, underflow: int, 
+/// overflow: int, nan: int)` of histogram counts and bin edges, along with 
+/// the number of values that fell below the first edge, above the last edge, 
+/// or that were NaN. 
 /// 
 /// -> dictionary
 #let histogram(
@@ -20,6 +22,12 @@
   /// -> int | array
   bins: 10,
 
+  /// Whether to count values below the first edge in the first bin and 
+  /// values above the last edge in the last bin. These values are reported 
+  /// in `underflow` and `overflow` either way. 
+  /// -> bool
+  fold: false,
+
 ) = {
   if type(bins) == array {
     bins = bins.map(float).sorted()
@@ -28,8 +36,51 @@
   let input = cbor.encode((
     values.map(float),
     bins,
+    fold,
   ))
 
   cbor(komet-plugin.histogram(input))
 }
 
+
+
+/// Determines the index of the bin each value falls into, for example, to 
+/// color points by bin or to build stacked histograms. The bins follow the 
+/// same semantics as for `histogram`. 
+/// 
+/// Returns an array of bin indices where, if $n$ bins are given, values below
+/// the first edge are assigned the index $-1$, values above the last edge the
+/// index $n$, and NaN values the index $n+1$. 
+/// 
+/// -> array
+#let digitize(
+
+  /// An array of float or integer values to assign bins to. 
+  /// -> array
+  values,
+
+  /// A number of bins or an explicit array of bin edges. 
+  /// -> int | array
+  bins: 10,
+
+  /// If set to `true`, bins include their upper edge instead of their lower 
+  /// edge, i.e., $(e_i, e_(i+1)]$ for $i=2,...,n$ and $[e_1,e_2]$. 
+  /// -> bool
+  right: false,
+
+) = {
+  if type(bins) == array {
+    bins = bins.map(float).sorted()
+  }
+
+  let input = cbor.encode((
+    values.map(float),
+    bins,
+    right,
+  ))
+
+  array(komet-plugin.digitize(input))
+    .chunks(4)
+    .map(bytes)
+    .map(int.from-bytes.with(endian: "big", signed: true))
+}
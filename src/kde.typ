@@ -0,0 +1,182 @@
+#import "plugin.typ": komet-plugin
+
+
+/// Computes a kernel density estimate (KDE) of the given values, for example,
+/// to draw a smooth density curve or a violin plot. The density is normalized
+/// to integrate to one. 
+/// 
+/// Returns a dictionary `(x: array, density: array, bandwidth: float)` 
+/// containing the grid points, the density evaluated at the grid points, 
+/// and the bandwidth that was used. 
+/// 
+/// -> dictionary
+#let kde(
+
+  /// A non-empty array of float or integer values. NaN values are ignored
+  /// together with their weights. 
+  /// -> array
+  values,
+
+  /// The points at which to evaluate the density. If an integer $n ≥ 2$ is 
+  /// given, the density is evaluated at $n$ evenly spaced points extending
+  /// three bandwidths beyond the range of the values (but not beyond 
+  /// `lower-bound` and `upper-bound`). 
+  /// -> int | array
+  grid: 200,
+
+  /// The kernel function. Options are `"gaussian"`, `"epanechnikov"`, 
+  /// `"uniform"`, `"triangular"`, `"biweight"`, `"triweight"`, and `"cosine"`. 
+  /// -> str
+  kernel: "gaussian",
+
+  /// The bandwidth, i.e., the standard deviation of the kernel. Either a fixed 
+  /// positive number or a rule to select it from the data:
+  /// - `"scott"`: Scott's rule $1.06 σ n^(-1\/5)$, 
+  /// - `"silverman"`: Silverman's rule $0.9 min(σ, "IQR"\/1.34) n^(-1\/5)$, 
+  /// - `"isj"`: the Improved Sheather–Jones algorithm which does not assume
+  ///   normally distributed data and works well for multimodal data. 
+  /// For values without spread, e.g., a single value, the rules use the
+  /// magnitude of the values in place of $σ$. 
+  /// -> str | float
+  bandwidth: "scott",
+
+  /// Optional non-negative weights for the values, of which at least one
+  /// needs to be positive. 
+  /// -> none | array
+  weights: none,
+
+  /// A lower boundary of the data (e.g., 0 for positive quantities). The 
+  /// density is reflected at the boundary and zero below it. 
+  /// -> none | float
+  lower-bound: none,
+
+  /// An upper boundary of the data. The density is reflected at the boundary 
+  /// and zero above it. 
+  /// -> none | float
+  upper-bound: none,
+
+) = {
+  if weights != none {
+    assert.eq(weights.len(), values.len(), message: "values and weights need to have the same length")
+    weights = weights.map(float)
+  }
+  if type(grid) == array { grid = grid.map(float) }
+  if type(bandwidth) == int { bandwidth = float(bandwidth) }
+  if lower-bound != none { lower-bound = float(lower-bound) }
+  if upper-bound != none { upper-bound = float(upper-bound) }
+
+  let input = cbor.encode((
+    values.map(float),
+    weights,
+    grid,
+    kernel,
+    bandwidth,
+    lower-bound,
+    upper-bound,
+  ))
+
+  cbor(komet-plugin.kde(input))
+}
+
+
+/// Computes a two-dimensional Gaussian kernel density estimate of a set of
+/// points $(x_i, y_i)$ and evaluates it on a regular grid, for example, to 
+/// draw density contours over scatter data. 
+/// 
+/// Returns a dictionary `(x: array, y: array, z: array, bandwidth: array)`
+/// with the grid coordinates, the density as a two-dimensional `m×n` array
+/// (one row for each of the `m` $y$ values), and the $2×2$ bandwidth matrix 
+/// that was used. The result can be passed directly to `contour`. 
+/// 
+/// -> dictionary
+#let kde-2d(
+
+  /// An array of float or integer $x$ coordinates. 
+  /// -> array
+  x,
+
+  /// An array of float or integer $y$ coordinates. 
+  /// -> array
+  y,
+
+  /// The $x$ coordinates of the grid. Either a number of points or an array
+  /// of at least two ascending, evenly spaced coordinates. If an integer is given, the grid extends 
+  /// three kernel standard deviations beyond the range of the data. 
+  /// -> int | array
+  x-grid: 100,
+
+  /// The $y$ coordinates of the grid, see `x-grid`. 
+  /// -> int | array
+  y-grid: 100,
+
+  /// The bandwidth matrix, i.e., the covariance matrix of the kernel. Either 
+  /// - a rule to select it from the sample covariance: `"scott"` or 
+  ///   `"silverman"` (which coincide in two dimensions), 
+  /// - a number $h$ for an isotropic kernel with standard deviation $h$, 
+  /// - or an explicit $2×2$ matrix. 
+  /// The matrix needs to be positive definite, so the rules require at least
+  /// three points that do not lie on a line. 
+  /// -> str | float | array
+  bandwidth: "scott",
+
+  /// Optional weights for the points. 
+  /// -> none | array
+  weights: none,
+
+) = {
+  assert.eq(x.len(), y.len(), message: "x and y need to have the same length")
+  if weights != none {
+    assert.eq(weights.len(), x.len(), message: "x, y, and weights need to have the same length")
+    weights = weights.map(float)
+  }
+  if type(x-grid) == array { x-grid = x-grid.map(float) }
+  if type(y-grid) == array { y-grid = y-grid.map(float) }
+  if type(bandwidth) == int { bandwidth = float(bandwidth) }
+  if type(bandwidth) == array { bandwidth = bandwidth.map(row => row.map(float)) }
+
+  let input = cbor.encode((
+    x.map(float),
+    y.map(float),
+    weights,
+    x-grid,
+    y-grid,
+    bandwidth,
+  ))
+
+  let result = cbor(komet-plugin.kde_2d(input))
+  result.z = result.z.chunks(result.x.len())
+  result
+}
+
+
+/// Converts probability mass fractions into density levels, e.g., to draw 
+/// the contours enclosing 68% and 95% of the probability mass of a 
+/// density computed with `kde-2d`. For each fraction $p$, the level is 
+/// the density such that the region where the density exceeds the level 
+/// contains the fraction $p$ of the total mass. 
+/// 
+/// Returns a level for each fraction. 
+/// 
+/// -> float | array
+#let density-levels(
+
+  /// A two-dimensional array of densities evaluated on a regular grid. 
+  /// -> array
+  z,
+
+  /// One or more probability mass fractions between 0 and 1. 
+  /// -> float | array
+  fractions,
+
+) = {
+  let single = type(fractions) in (int, float)
+  if single { fractions = (fractions,) }
+
+  let input = cbor.encode((
+    z.flatten().map(float),
+    fractions.map(float),
+  ))
+
+  let levels = cbor(komet-plugin.density_levels(input))
+  if single { levels.first() } else { levels }
+}
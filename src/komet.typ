@@ -1,5 +1,14 @@
-#import "fft.typ": fft, ifft
+#import "fft.typ": fft, ifft, window, rfft, irfft, fftfreq, rfftfreq, fft2, ifft2, fftn, ifftn, fftshift, ifftshift, welch, periodogram, stft, istft, hilbert
 #import "contour.typ": contour
-#import "histogram.typ": histogram
-#import "boxplot.typ": boxplot
+#import "convolve.typ": convolve, correlate, convolve-2d, correlate-2d, cross-correlation
+#import "filter.typ": iir-filter, fir-filter, lfilter, filtfilt
+#import "histogram.typ": histogram, digitize
+#import "binned_statistic.typ": binned-statistic, binned-statistic-2d
+#import "boxplot.typ": boxplot, boxplots, letter-values
+#import "violin.typ": violins
+#import "describe.typ": describe
+#import "ecdf.typ": ecdf
+#import "qq.typ": qq
+#import "quantile.typ": quantiles
+#import "kde.typ": kde, kde-2d, density-levels
 #import "linear_system.typ": thomas-algorithm
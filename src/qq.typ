@@ -0,0 +1,59 @@
+#import "plugin.typ": komet-plugin
+
+
+/// Computes the data for a quantile-quantile (Q-Q) plot of the given values
+/// against a theoretical distribution. Returns a dictionary with the entries
+/// - `theoretical`: the theoretical quantiles in ascending order, 
+/// - `sample`: the sorted values, 
+/// - `slope` and `intercept` of the reference line 
+///   $"sample" = "slope" dot "theoretical" + "intercept"$, 
+/// - `r`: the correlation coefficient between the theoretical and the sample 
+///   quantiles. 
+/// 
+/// The distributions are used in their standard form (location 0 and 
+/// scale 1) since location and scale of the sample are captured by the 
+/// reference line. 
+/// 
+/// -> dictionary
+#let qq(
+
+  /// A non-empty array of float or integer values. NaN values are ignored. 
+  /// -> array
+  values,
+
+  /// The theoretical distribution. Options are `"normal"`, `"exponential"`,
+  /// `"uniform"`, `"lognormal"`, `"logistic"`, `"laplace"`, `"gumbel"`, 
+  /// `"cauchy"`, and `"weibull"`. 
+  /// -> str
+  distribution: "normal",
+
+  /// The shape parameter of the `"lognormal"` distribution (the standard
+  /// deviation of the logarithm) and of the `"weibull"` distribution. 
+  /// -> int | float
+  shape: 1,
+
+  /// The offset $a$ of the plotting positions $(i - a)\/(n + 1 - 2a)$ 
+  /// at which the theoretical quantiles are evaluated, e.g., `0.5` (Hazen), 
+  /// `0.375` (Blom), or `0` (Weibull). The offset needs to lie in $[0, 1)$. 
+  /// -> float
+  plotting-position: 0.5,
+
+  /// How to fit the reference line. Options are 
+  /// - `"least-squares"`: the least-squares fit of the sample on the 
+  ///   theoretical quantiles, 
+  /// - `"quartiles"`: the line through the first and third quartiles 
+  ///   which is robust against outliers. 
+  /// -> str
+  line: "least-squares",
+
+) = {
+  let input = cbor.encode((
+    values.map(float),
+    distribution,
+    float(shape),
+    float(plotting-position),
+    line,
+  ))
+
+  cbor(komet-plugin.qq(input))
+}
@@ -0,0 +1,51 @@
+#import "plugin.typ": komet-plugin
+
+
+/// Computes one or more percentiles of the given values. 
+/// 
+/// Returns the value of the percentile, or an array of values if an 
+/// array of percentiles is given. 
+/// 
+/// -> float | array
+#let quantiles(
+
+  /// A non-empty array of float or integer values. 
+  /// -> array
+  values,
+
+  /// One or more percentiles between 0 and 100. 
+  /// -> float | array
+  percentiles,
+
+  /// The method for computing the quantiles. The nine definitions by 
+  /// Hyndman and Fan can be given either by their type number (1 to 9) or 
+  /// by their NumPy name:
+  /// 1. `"inverted-cdf"`,
+  /// 2. `"averaged-inverted-cdf"`,
+  /// 3. `"closest-observation"`,
+  /// 4. `"interpolated-inverted-cdf"`,
+  /// 5. `"hazen"`,
+  /// 6. `"weibull"`,
+  /// 7. `"linear"`,
+  /// 8. `"median-unbiased"`,
+  /// 9. `"normal-unbiased"`. 
+  /// 
+  /// In addition, `"lower"`, `"higher"`, `"nearest"`, and `"midpoint"` 
+  /// choose the lower or higher of the two values that `"linear"` 
+  /// interpolates between, the nearer one, or their mean. 
+  /// -> str | int
+  method: "linear",
+
+) = {
+  let single = type(percentiles) in (int, float)
+  if single { percentiles = (percentiles,) }
+
+  let input = cbor.encode((
+    values.map(float),
+    percentiles.map(float),
+    method,
+  ))
+
+  let result = cbor(komet-plugin.quantiles(input))
+  if single { result.first() } else { result }
+}
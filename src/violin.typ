@@ -0,0 +1,88 @@
+#import "plugin.typ": komet-plugin
+#import "boxplot.typ": convert-whiskers, convert-notch
+
+
+/// Computes the geometry of violin plots for one or more groups of values. 
+/// Each violin is a mirrored kernel density estimate of its group, combined 
+/// with the box plot statistics for drawing the inner quartiles. 
+/// 
+/// Returns an array with one dictionary per group containing
+/// - the `grid` along the value axis at which the density is evaluated, 
+/// - the half `width` of the violin at each grid point, scaled so that the
+///   widest violin has a half width of 1, 
+/// - the closed `outline` of the violin as an array of `(offset, value)` 
+///   pairs relative to its center, 
+/// - the `bandwidth` of the kernel density estimate, and 
+/// - the box plot `stats`, see `boxplot`. 
+/// 
+/// -> array
+#let violins(
+
+  /// An array of value arrays, one per group. 
+  /// -> array
+  groups,
+
+  /// The kernel function, see `kde`. 
+  /// -> str
+  kernel: "gaussian",
+
+  /// The bandwidth or a rule to select it for each group, see `kde`. 
+  /// -> str | float
+  bandwidth: "scott",
+
+  /// The non-negative distance in bandwidths by which the violins extend beyond the 
+  /// extreme values of each group. With `cut: 0`, the violins are clipped
+  /// to the range of the data. 
+  /// -> float
+  cut: 2,
+
+  /// The number of grid points per violin. 
+  /// -> int
+  num-points: 100,
+
+  /// Whether all violins share a common grid spanning all groups. 
+  /// -> bool
+  common-grid: false,
+
+  /// How the widths of the violins are scaled relative to each other:
+  /// - `"area"`: all violins have the same area, 
+  /// - `"count"`: the areas are proportional to the number of values, 
+  /// - `"width"`: all violins have the same maximum width. 
+  /// -> str
+  scale: "area",
+
+  /// The position of the whiskers of the inner box plots, see `boxplot`. 
+  /// -> float
+  whisker-pos: 1.5,
+
+  /// The definition of the whiskers of the inner box plots, see `boxplot`. 
+  /// -> str | array
+  whiskers: "tukey",
+
+  /// The method for computing the median and the quartiles, see `quantiles`. 
+  /// -> str | int
+  quantile-method: "linear",
+
+  /// The confidence interval of the median, see `boxplot`. 
+  /// -> str | dictionary
+  notch: "mcgill",
+
+) = {
+  if type(bandwidth) == int { bandwidth = float(bandwidth) }
+
+  let input = cbor.encode((
+    groups.map(group => group.map(float)),
+    kernel,
+    bandwidth,
+    float(cut),
+    num-points,
+    common-grid,
+    scale,
+    float(whisker-pos),
+    convert-whiskers(whiskers),
+    quantile-method,
+    convert-notch(notch),
+  ))
+
+  cbor(komet-plugin.violins(input))
+}
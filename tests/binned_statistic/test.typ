@@ -0,0 +1,42 @@
+#import "/src/komet.typ": binned-statistic, binned-statistic-2d
+
+#assert.eq(
+  binned-statistic(
+    (0.5, 1.5, 1.2, 0.1, 2.5, 3, 4),
+    (1, 4, 2, 3, 10, 20, 100),
+    bins: (0, 1, 2, 3),
+  ),
+  (statistic: (2, 3, 15), edges: (0, 1, 2, 3)),
+)
+
+#assert.eq(
+  binned-statistic(
+    (0.5, 1.5, 1.2, 0.1, 2.5, 3, 4),
+    (1, 4, 2, 3, 10, 20, 100),
+    bins: (0, 1, 2, 3),
+    statistic: "max",
+  ).statistic,
+  (3, 4, 20),
+)
+
+#assert.eq(
+  binned-statistic(
+    (0.5, 1.5, 1.2, 0.1, 2.5, 3, 4),
+    (1, 4, 2, 3, 10, 20, 100),
+    bins: (0, 1, 2, 3),
+    statistic: 25,
+  ).statistic,
+  (1.5, 2.5, 12.5),
+)
+
+#assert.eq(
+  binned-statistic-2d(
+    (0.5, 1.5, 1.5, 0.2, 5),
+    (0.5, 0.5, 1.5, 0.1, 0.5),
+    (1, 2, 3, 4, 5),
+    x-bins: (0, 1, 2),
+    y-bins: (0, 1, 2),
+    statistic: "sum",
+  ),
+  (statistic: ((5, 2), (0, 3)), x-edges: (0, 1, 2), y-edges: (0, 1, 2)),
+)
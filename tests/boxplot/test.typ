@@ -1,6 +1,10 @@
-#import "/src/komet.typ": boxplot
+#import "/src/komet.typ": boxplot, boxplots, letter-values
+#import "/src/assertations.typ": approx
 
-#assert.eq(boxplot((1, 2, 3, 4, -2, 100)), (
+#let stats = boxplot((1, 2, 3, 4, -2, 100))
+#approx(stats.remove("notch-low"), 2.5 - 1.57 * 2.5 / calc.sqrt(6), eps: 1e-12)
+#approx(stats.remove("notch-high"), 2.5 + 1.57 * 2.5 / calc.sqrt(6), eps: 1e-12)
+#assert.eq(stats, (
   mean: 18.0,
   median: 2.5,
   q1: 1.25,
@@ -12,7 +16,10 @@
   outliers: (100.,),
 ))
 
-#assert.eq(boxplot((1, 2, 3, 4)), (
+#let stats = boxplot((1, 2, 3, 4))
+#approx(stats.remove("notch-low"), 2.5 - 1.57 * 1.5 / 2, eps: 1e-12)
+#approx(stats.remove("notch-high"), 2.5 + 1.57 * 1.5 / 2, eps: 1e-12)
+#assert.eq(stats, (
   mean: 2.5,
   median: 2.5,
   q1: 1.75,
@@ -23,3 +30,73 @@
   whisker-high: 4.,
   outliers: (),
 ))
+
+#let stats = boxplot((1, 2, 3, 4), quantile-method: "weibull")
+#assert.eq((stats.q1, stats.median, stats.q3), (1.25, 2.5, 3.75))
+
+#let stats = boxplot(range(101), notch: (resamples: 500, seed: 3))
+#assert(stats.notch-low < 50 and stats.notch-high > 50)
+#assert.eq(stats, boxplot(range(101), notch: (resamples: 500, seed: 3)))
+
+#let groups = ((1, 2, 3, 4, -2, 100), (1, 2, 3, 4), range(101))
+#assert.eq(boxplots(groups), groups.map(boxplot))
+#assert.eq(
+  boxplots(groups, quantile-method: "weibull", notch: (resamples: 200)),
+  groups.map(boxplot.with(quantile-method: "weibull", notch: (resamples: 200))),
+)
+
+#let stats = boxplots((3, 10, 1, 20, 2, 4, 30), labels: ("a", "b", "a", "b", "a", "a", "b"))
+#assert.eq(stats.keys(), ("a", "b"))
+#assert.eq(stats.a, boxplot((3, 1, 2, 4)))
+#assert.eq(stats.b, boxplot((10, 20, 30)))
+
+#let stats = boxplots((1, 2, 3), labels: (2, 1, 2))
+#assert.eq(stats.keys(), ("2", "1"))
+
+#let values = (-2, 1, 2, 3, 4, 5, 6, 7, 8, 100)
+#let whiskers(..args) = {
+  let stats = boxplot(values, ..args)
+  (stats.whisker-low, stats.whisker-high, stats.outliers)
+}
+#assert.eq(whiskers(), (-2., 8., (100.,)))
+#assert.eq(whiskers(whisker-pos: 0.5), (1., 8., (-2., 100.)))
+#assert.eq(whiskers(whiskers: "range"), (-2., 100., ()))
+#assert.eq(whiskers(whiskers: "std", whisker-pos: 1), (-2., 8., (100.,)))
+#assert.eq(whiskers(whiskers: (10, 90)).at(2), (-2., 100.))
+#approx(whiskers(whiskers: (10, 90)).slice(0, 2), (0.7, 17.2), eps: 1e-12)
+
+#let skewed = range(1, 100).map(i => -calc.ln(1 - i / 100))
+#assert(boxplot(skewed).outliers.len() > 0)
+#assert.eq(boxplot(skewed, whiskers: "adjusted").outliers, ())
+#assert.eq(
+  boxplots((values, skewed), whiskers: "adjusted"),
+  (values, skewed).map(boxplot.with(whiskers: "adjusted")),
+)
+
+#let lv = letter-values(range(1, 101), depth: "full")
+#assert.eq(lv.levels.map(level => level.depth), (50.5, 25.5, 13., 7., 4., 2.5, 1.5))
+#assert.eq(lv.levels.at(1), (depth: 25.5, lower: 25.5, upper: 75.5))
+#assert.eq(lv.outliers, (1., 100.))
+
+#let values = range(1000).map(i => calc.rem(i * 37, 1000))
+#assert.eq(letter-values(values).levels.len(), 6)
+#assert.eq(letter-values(values, depth: "proportion").levels.len(), 8)
+#assert.eq(letter-values(values, depth: "trustworthy").levels.len(), 8)
+#assert.eq(letter-values(values, depth: 3).levels.len(), 3)
+#assert.eq(letter-values(values).outliers.len(), 32)
+
+// Weighted box plots
+#let values = (1, 2, 3, 4, -2, 100)
+#assert.eq(boxplot(values, weights: (2,) * 6), boxplot(values))
+#let stats = boxplot(values, weights: (1, 1, 1, 1, 1, 5))
+#approx(stats.mean, 50.8, eps: 1e-12)
+#assert.eq((stats.q3, stats.whisker-high, stats.outliers), (100., 100., ()))
+
+// Approximate box plots
+#let values = range(1000).map(i => calc.rem(i * 37, 1000)) + (-5000, 6000)
+#let exact = boxplot(values)
+#let stats = boxplot(values, approximate: true)
+#assert.eq((stats.min, stats.max, stats.outliers), (-5000., 6000., (-5000., 6000.)))
+#approx(stats.median, exact.median, eps: 10)
+#approx(stats.q1, exact.q1, eps: 10)
+#approx(stats.q3, exact.q3, eps: 10)
@@ -0,0 +1,39 @@
+#import "/src/komet.typ": convolve, correlate, convolve-2d, correlate-2d, cross-correlation
+#import "/src/assertations.typ": approx
+
+
+#let a = (1, 2, 3, 4, 5)
+#let b = (1, 0, -1)
+#for method in ("direct", "fft", "auto") {
+  approx(convolve(a, b, method: method), (1, 2, 2, 2, 2, -4, -5), eps: 1e-10)
+  approx(convolve(a, b, mode: "same", method: method), (2, 2, 2, 2, -4), eps: 1e-10)
+  approx(convolve(a, b, mode: "valid", method: method), (2, 2, 2), eps: 1e-10)
+}
+#assert.eq(convolve(a, ()), ())
+
+#approx(correlate((1, 2, 3), (0, 1, 0.5)), (0.5, 2, 3.5, 3, 0), eps: 1e-12)
+#approx(correlate((1, 2, 3), (0, 1, 0.5), mode: "same"), (2, 3.5, 3), eps: 1e-12)
+
+
+// Two-dimensional
+#let grid = ((1, 2, 3), (4, 5, 6))
+#let kernel = ((1, 1), (1, 1))
+#approx(
+  convolve-2d(grid, kernel).flatten(), 
+  (1, 3, 5, 3, 5, 12, 16, 9, 4, 9, 11, 6), 
+  eps: 1e-10
+)
+#assert.eq(convolve-2d(grid, kernel, mode: "valid", method: "direct"), ((12., 16.),))
+#assert.eq(convolve-2d(grid, kernel, mode: "same").len(), 2)
+#assert.eq(correlate-2d(grid, ((0, 1),), method: "direct"), ((1., 2., 3., 0.), (4., 5., 6., 0.)))
+
+
+// Cross-correlation
+#let y = (0, 1, 3, 1, 0, 0, 0, 0)
+#let x = (0, 0, 0, 1, 3, 1, 0, 0)
+#let result = cross-correlation(x, y, normalized: false)
+#assert.eq(result.lags, range(-7, 8))
+#assert.eq(result.values.at(result.lags.position(lag => lag == 2)), 11.)
+#let result = cross-correlation(x, y, max-lag: 3)
+#assert.eq(result.lags, (-3, -2, -1, 0, 1, 2, 3))
+#assert(result.values.all(v => calc.abs(v) <= 1))
@@ -0,0 +1,21 @@
+#import "/src/komet.typ": describe
+#import "/src/assertations.typ": approx
+
+#let d = describe((2, 8, 4, 1, 4))
+#assert.eq(d.count, 5)
+#assert.eq((d.sum, d.min, d.max, d.median, d.mad, d.mode), (19., 1., 8., 4., 2., 4.))
+#approx(d.mean, 3.8, eps: 1e-12)
+#approx(d.variance, 7.2, eps: 1e-12)
+#approx(d.std, calc.sqrt(7.2), eps: 1e-12)
+#approx(d.sem, calc.sqrt(7.2 / 5), eps: 1e-12)
+#approx(d.skewness, 9.264 / calc.pow(5.76, 1.5), eps: 1e-12)
+#approx(d.kurtosis, 76.6272 / 5.76 / 5.76 - 3, eps: 1e-12)
+#approx(d.geometric-mean, calc.pow(256, 0.2), eps: 1e-12)
+#approx(d.harmonic-mean, 5 / 2.125, eps: 1e-12)
+
+#approx(describe((2, 8, 4, 1, 4), ddof: 0).variance, 5.76, eps: 1e-12)
+
+#let values = (1, float.nan, 3)
+#assert(describe(values).mean.is-nan())
+#assert.eq(describe(values, nan-policy: "omit").mean, 2.)
+#assert.eq(describe(values, nan-policy: "omit").count, 2)
@@ -0,0 +1,10 @@
+#import "/src/komet.typ": ecdf
+
+
+#assert.eq(ecdf((3, 1, 2, 2)), (x: (1., 2., 3.), y: (0.25, 0.75, 1.)))
+#assert.eq(ecdf((3, 1, 2, 2), complementary: true).y, (0.75, 0.25, 0.))
+#assert.eq(ecdf((3, 1, 2, float.nan)).x, (1., 2., 3.))
+
+// Weighted
+#assert.eq(ecdf((3, 1, 2), weights: (2, 1, 1)).y, (0.25, 0.5, 1.))
+#assert.eq(ecdf((3, 1, 2), weights: (2, 1, 1), complementary: true).y, (0.75, 0.5, 0.))
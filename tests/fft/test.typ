@@ -1,4 +1,5 @@
-#import "/src/komet.typ": fft, ifft
+#import "/src/komet.typ": fft, ifft, window, rfft, irfft, fftfreq, rfftfreq, fft2, ifft2, fftn, ifftn, fftshift, ifftshift, welch, periodogram, stft, istft, hilbert
+#import "/src/assertations.typ": approx
 
 #assert.eq(
   fft((1, 2, 3, 4)),
@@ -19,4 +20,114 @@
 
 #test-inverse(((1, 0), (2, 9), (-233, -2), (4, 0)))
 
-#ifft((1,2,3,4,5,6,7), norm: "ortho")
\ No newline at end of file
+#ifft((1,2,3,4,5,6,7), norm: "ortho")
+
+// Real-input FFT
+#approx(rfft((1, 2, 3, 4)).flatten(), (10, 0, -2, 2, -2, 0), eps: 1e-12)
+#approx(rfft((1, 2, 3)).flatten(), (6, 0, -1.5, calc.sqrt(3) / 2), eps: 1e-12)
+#approx(irfft(((4, 0), (1, -1), (0, 0))), (1.5, 1.5, 0.5, 0.5), eps: 1e-12)
+#approx(irfft(rfft((1, 2, 3, 4))), (1, 2, 3, 4), eps: 1e-12)
+
+#let values = (1, -2, 3.5, 0, 7)
+#approx(irfft(rfft(values, norm: "ortho"), n: 5, norm: "ortho"), values, eps: 1e-12)
+#assert.eq(irfft(rfft(values)).len(), 4)
+
+
+// Spectrum outputs
+#approx(fft((1, 2, 0, -1), output: "magnitude"), (2, calc.sqrt(10), 0, calc.sqrt(10)), eps: 1e-12)
+#approx(fft((1, 2, 0, -1), output: "power"), (4, 10, 0, 10), eps: 1e-12)
+#approx(rfft((1, 2, 0, -1), output: "db", reference: 10).slice(0, 2), (10 * calc.log(0.4), 0), eps: 1e-12)
+#approx(rfft((1, 2, 0, -1), output: "db", reference: "max").slice(0, 2), (10 * calc.log(0.4), 0), eps: 1e-12)
+#approx(fft((1, 2, 0, -1), output: "phase"), (0, -calc.atan(3).rad(), 0, calc.atan(3).rad()), eps: 1e-12)
+#approx(
+  fft((0, 1, 0, 0, 0, 0, 0, 0), output: "unwrapped-phase"),
+  range(8).map(k => -k * calc.pi / 4),
+  eps: 1e-12,
+)
+
+// Frequencies of the bins
+#approx(fftfreq(5, spacing: 0.1), (0, 2, 4, -4, -2), eps: 1e-12)
+#approx(fftfreq(4), (0, 0.25, -0.5, -0.25), eps: 1e-12)
+#approx(rfftfreq(4, spacing: 0.1), (0, 2.5, 5), eps: 1e-12)
+#assert.eq(rfftfreq(5).len(), 3)
+
+
+// Multi-dimensional FFT
+#let grid = ((1, 2, 3), (4, 5, 6))
+#let spectrum = fft2(grid)
+#assert.eq(spectrum.len(), 2)
+#assert.eq(spectrum.at(0).len(), 3)
+#approx(spectrum.at(0).at(0), (21, 0), eps: 1e-12)
+#approx(spectrum.at(1).at(0), (-9, 0), eps: 1e-12)
+#approx(spectrum.at(0).at(1), (-3, calc.sqrt(3)), eps: 1e-12)
+#approx(ifft2(spectrum).flatten(), grid.flatten().map(x => (x, 0)).flatten(), eps: 1e-12)
+#approx(fftn(grid).flatten(), spectrum.flatten(), eps: 1e-12)
+
+// Transforming a single axis with zero-padding
+#let rows = fftn(grid, axes: -1, lengths: (4,))
+#approx(rows.at(0).flatten(), (6, 0, -2, -2, 2, 0, -2, 2), eps: 1e-12)
+#approx(fft2(grid, lengths: (1, none)).flatten(), fft((1, 2, 3)).flatten(), eps: 1e-12)
+
+// Shifting
+#assert.eq(fftshift((0, 1, 2, 3, 4)), (3, 4, 0, 1, 2))
+#assert.eq(ifftshift((0, 1, 2, 3, 4)), (2, 3, 4, 0, 1))
+#assert.eq(ifftshift(fftshift((0, 1, 2, 3, 4, 5))), (0, 1, 2, 3, 4, 5))
+#assert.eq(fftshift(((0, 1, 2), (3, 4, 5))), ((5, 3, 4), (2, 0, 1)))
+#assert.eq(fftshift(((0, 1, 2), (3, 4, 5)), axes: 1), ((2, 0, 1), (5, 3, 4)))
+#assert.eq(fftshift(((0, 0), (1, 0), (2, 0))), ((2, 0), (0, 0), (1, 0)))
+
+
+// Windows
+#approx(window("hann", 5), (0, 0.5, 1, 0.5, 0), eps: 1e-12)
+#approx(window("hann", 4, periodic: true), (0, 0.5, 1, 0.5), eps: 1e-12)
+#approx(window(("tukey", 0.5), 9), (0, 0.5, 1, 1, 1, 1, 1, 0.5, 0), eps: 1e-12)
+#assert.eq(window(("kaiser", 0), 3), (1.0, 1.0, 1.0))
+#assert.eq(window("rectangular", 0), ())
+#approx(
+  fft((1, 1, 1, 1), window: "hann").flatten(),
+  fft((0, 0.5, 1, 0.5)).flatten(),
+  eps: 1e-12
+)
+
+
+// Power spectra
+#let sine = range(100).map(k => 2 * calc.sin(2 * calc.pi * 10 * k / 100))
+#let psd = periodogram(sine, sample-rate: 100, scaling: "spectrum")
+#assert.eq(psd.frequencies.len(), 51)
+#approx((psd.frequencies.at(10), psd.power.at(10)), (10, 2), eps: 1e-10)
+#approx((psd.power.sum(),), (2,), eps: 1e-10)
+
+#let psd = periodogram(sine, sample-rate: 100, one-sided: false)
+#assert.eq(psd.frequencies.len(), 100)
+#approx((psd.frequencies.at(50), psd.power.at(90)), (-50, 1), eps: 1e-10)
+
+#let psd = welch(sine, sample-rate: 100, segment-length: 20, overlap: 10)
+#assert.eq(psd.frequencies.len(), 11)
+#assert.eq(psd.power.len(), 11)
+#approx((psd.frequencies.at(2),), (10,), eps: 1e-12)
+#assert(psd.power.at(2) == calc.max(..psd.power))
+
+
+// Short-time Fourier transform
+#let signal = range(256).map(k => 3 * calc.cos(2 * calc.pi * k / 8))
+#let result = stft(signal, sample-rate: 8, segment-length: 32, padding: false, output: "magnitude")
+#assert.eq(result.times.len(), 15)
+#assert.eq(result.frequencies.len(), 17)
+#assert.eq(result.values.len(), 17)
+#assert.eq(result.values.at(0).len(), 15)
+#approx((result.times.at(0), result.frequencies.at(4)), (2, 1), eps: 1e-12)
+#approx(result.values.at(4), (1.5,) * 15, eps: 1e-12)
+
+#let signal = range(100).map(k => calc.sin(k * 0.3) + 0.01 * k * k)
+#let result = stft(signal, segment-length: 16, hop: 4)
+#assert.eq(result.times.at(0), 0.0)
+#approx(istft(result.values, segment-length: 16, hop: 4, len: 100), signal, eps: 1e-10)
+
+
+// Hilbert transform
+#let signal = range(8).map(k => calc.cos(2 * calc.pi * k / 4))
+#let result = hilbert(signal, sample-rate: 4)
+#approx(result.values.flatten(), range(8).map(k => (calc.cos(calc.pi * k / 2), calc.sin(calc.pi * k / 2))).flatten(), eps: 1e-12)
+#approx(result.envelope, (1,) * 8, eps: 1e-12)
+#approx(result.phase, range(8).map(k => calc.pi * k / 2), eps: 1e-12)
+#approx(result.frequency, (1,) * 8, eps: 1e-12)
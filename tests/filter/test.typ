@@ -0,0 +1,40 @@
+#import "/src/komet.typ": iir-filter, fir-filter, lfilter, filtfilt
+#import "/src/assertations.typ": approx
+
+
+// IIR design
+#let sos = iir-filter(2, 25, sample-rate: 100)
+#assert.eq(sos.len(), 1)
+#approx(sos.at(0), (0.29289321881, 0.58578643763, 0.29289321881, 1, 0, 0.17157287525), eps: 1e-10)
+
+#assert.eq(iir-filter(5, 0.1).len(), 3)
+#assert.eq(iir-filter(3, (10, 20), band: "bandpass", sample-rate: 100).len(), 3)
+#assert.eq(iir-filter(2, (10, 20), band: "bandstop", design: ("chebyshev1", 1), sample-rate: 100).len(), 2)
+#assert.eq(iir-filter(4, 0.2, band: "highpass", design: ("chebyshev2", 40)).len(), 2)
+#assert.eq(iir-filter(4, 0.2, design: "bessel").len(), 2)
+
+
+// FIR design
+#let taps = fir-filter(5, 10, sample-rate: 100)
+#approx(taps, (0.02840647, 0.23700821, 0.46917063, 0.23700821, 0.02840647), eps: 1e-8)
+#approx((taps.sum(),), (1,), eps: 1e-12)
+#assert.eq(fir-filter(31, 0.2, band: "highpass", window: ("kaiser", 5)).len(), 31)
+
+
+// Filtering
+#approx(lfilter((b: (1, 1), a: (2,)), (2, 4, 6, 8)), (1, 3, 5, 7), eps: 1e-12)
+#approx(lfilter((b: (1,), a: (1, -0.5)), (1, 0, 0)), (1, 0.5, 0.25), eps: 1e-12)
+#approx(lfilter((0.5, 0.5), (2, 4, 6, 8)), (1, 3, 5, 7), eps: 1e-12)
+#approx(filtfilt((0.5, 0.5), (2, 4, 6, 8), padding: none), (2.5, 4, 6, 7), eps: 1e-12)
+
+// A constant signal passes without transients
+#for padding in ("odd", "even", "constant", none) {
+  approx(filtfilt(iir-filter(4, 0.1), (3,) * 20, padding: padding), (3,) * 20, eps: 1e-10)
+}
+
+// A slow sinusoid passes without phase shift while a fast one is removed
+#let signal = range(400).map(k => calc.sin(2 * calc.pi * 0.01 * k) + 0.5 * calc.sin(2 * calc.pi * 0.3 * k))
+#let expected = range(400).map(k => calc.sin(2 * calc.pi * 0.01 * k))
+#approx(filtfilt(iir-filter(4, 0.1), signal).slice(50, 350), expected.slice(50, 350), eps: 1e-2)
+#approx(filtfilt(fir-filter(31, 0.1), signal).slice(50, 350), expected.slice(50, 350), eps: 1e-2)
+#assert.eq(filtfilt(iir-filter(4, 0.1), ()), ())
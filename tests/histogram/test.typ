@@ -1,18 +1,42 @@
-#import "/src/komet.typ": histogram
+#import "/src/komet.typ": histogram, digitize
 
 #assert.eq(
   histogram((1, 3, 5, 7), bins: (0, 2, 8)),
-  (counts: (1, 3), edges: (0, 2, 8)),
+  (counts: (1, 3), edges: (0, 2, 8), underflow: 0, overflow: 0, nan: 0),
 )
 
 
 #assert.eq(
   histogram(range(10), bins: 3),
-  (counts: (3, 3, 4), edges: (0, 3, 6, 9)),
+  (counts: (3, 3, 4), edges: (0, 3, 6, 9), underflow: 0, overflow: 0, nan: 0),
 )
 
 
 #assert.eq(
   histogram((0, 2, 4), bins: (0, 2, 4)),
-  (counts: (1, 2), edges: (0, 2, 4)),
+  (counts: (1, 2), edges: (0, 2, 4), underflow: 0, overflow: 0, nan: 0),
+)
+
+
+#assert.eq(
+  histogram((-1, 0, float.nan, 2, 5, 6, -3), bins: (0, 2, 4)),
+  (counts: (1, 1), edges: (0, 2, 4), underflow: 2, overflow: 2, nan: 1),
+)
+
+
+#assert.eq(
+  histogram((-1, 0, 2, 5, 6, -3), bins: (0, 2, 4), fold: true),
+  (counts: (3, 3), edges: (0, 2, 4), underflow: 2, overflow: 2, nan: 0),
+)
+
+
+#assert.eq(
+  digitize((-1, 0, 0.5, 1, 2, 2.5, float.nan), bins: (0, 1, 2)),
+  (-1, 0, 0, 1, 1, 2, 3),
+)
+
+
+#assert.eq(
+  digitize((-1, 0, 0.5, 1, 2, 2.5), bins: (0, 1, 2), right: true),
+  (-1, 0, 0, 0, 1, 2),
 )
@@ -0,0 +1,41 @@
+#import "/src/komet.typ": kde, kde-2d, density-levels
+#import "/src/assertations.typ": approx
+
+#let result = kde((0,), grid: (0, 1), bandwidth: 1)
+#approx(result.density, (1 / calc.sqrt(2 * calc.pi), calc.exp(-0.5) / calc.sqrt(2 * calc.pi)))
+#assert.eq(result.bandwidth, 1.0)
+
+// Reflection at a lower boundary doubles the density at the boundary
+#let result = kde((0,), grid: (-1, 0), bandwidth: 1, lower-bound: 0)
+#approx(result.density, (0, 2 / calc.sqrt(2 * calc.pi)))
+
+// Weights act like repeated values
+#approx(
+  kde((0, 1), grid: (0.5, 2), bandwidth: 1, weights: (1, 3)).density,
+  kde((0, 1, 1, 1), grid: (0.5, 2), bandwidth: 1).density,
+)
+
+#let result = kde((1, 2, 3, 4, 5, 6, 7, 8, 9, 10), grid: 50, kernel: "epanechnikov", bandwidth: "silverman")
+#assert.eq(result.x.len(), 50)
+#assert.eq(result.density.len(), 50)
+#approx(result.x.first(), 1 - 3 * result.bandwidth, eps: 1e-12)
+
+// Values without spread fall back to a positive bandwidth
+#let result = kde((5, 5), grid: (5,))
+#approx((result.bandwidth,), (1.06 * 5 * calc.pow(2, -0.2),), eps: 1e-12)
+#assert(result.density.at(0) > 0)
+
+#let result = kde-2d((0,), (0,), x-grid: (-1, 0, 1), y-grid: (0, 1), bandwidth: 1)
+#assert.eq(result.x, (-1, 0, 1))
+#assert.eq(result.y, (0, 1))
+#assert.eq(result.bandwidth, ((1, 0), (0, 1)))
+#let gauss(x, y) = calc.exp(-(x * x + y * y) / 2) / (2 * calc.pi)
+#approx(result.z.at(0), (-1, 0, 1).map(x => gauss(x, 0)))
+#approx(result.z.at(1), (-1, 0, 1).map(x => gauss(x, 1)))
+
+#let result = kde-2d((0, 1, 2), (0, 1, 3), x-grid: 20, y-grid: 30)
+#assert.eq(result.z.len(), 30)
+#assert.eq(result.z.first().len(), 20)
+
+#assert.eq(density-levels(((1, 4), (3, 2)), (0.4, 0.7)), (4, 3))
+#assert.eq(density-levels(((1, 4), (3, 2)), 0.4), 4)
@@ -0,0 +1,20 @@
+#import "/src/komet.typ": qq
+#import "/src/assertations.typ": approx
+
+
+#let result = qq((4, 1, 3, 2), distribution: "uniform", plotting-position: 0)
+#assert.eq(result.theoretical, (0.2, 0.4, 0.6, 0.8))
+#assert.eq(result.sample, (1., 2., 3., 4.))
+#approx(result.slope, 5, eps: 1e-12)
+#approx(result.intercept, 0, eps: 1e-12)
+#approx(result.r, 1, eps: 1e-12)
+
+#let result = qq((4, 1, 3, 2), distribution: "uniform", plotting-position: 0, line: "quartiles")
+#approx(result.slope, 3, eps: 1e-12)
+#approx(result.intercept, 1, eps: 1e-12)
+
+// Values at the normal plotting positions lie on the line
+#let result = qq((-1.15035, -0.31864, 0.31864, 1.15035).map(x => 2 * x + 1))
+#approx(result.slope, 2, eps: 1e-4)
+#approx(result.intercept, 1, eps: 1e-4)
+#approx(result.theoretical.at(3), 1.15035, eps: 1e-4)
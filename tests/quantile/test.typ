@@ -0,0 +1,18 @@
+#import "/src/komet.typ": quantiles
+#import "/src/assertations.typ": approx
+
+#assert.eq(quantiles((4, 1, 3, 2), (0, 25, 50, 100)), (1, 1.75, 2.5, 4))
+#assert.eq(quantiles((4, 1, 3, 2), 50), 2.5)
+
+#let values = (1, 2, 4, 7, 11)
+#approx(
+  range(1, 10).map(i => quantiles(values, 30, method: i)),
+  (2, 2, 2, 1.5, 2, 1.8, 2.4, 1 + 14 / 15, 1.95),
+  eps: 1e-12,
+)
+#assert.eq(quantiles(values, 30, method: "weibull"), 1.8)
+#assert.eq(quantiles(values, 30, method: "median_unbiased"), quantiles(values, 30, method: 8))
+#assert.eq(quantiles(values, 30, method: "lower"), 2)
+#assert.eq(quantiles(values, 30, method: "higher"), 4)
+#assert.eq(quantiles(values, 30, method: "nearest"), 2)
+#assert.eq(quantiles(values, 30, method: "midpoint"), 3)
@@ -0,0 +1,24 @@
+#import "/src/komet.typ": violins, boxplot
+#import "/src/assertations.typ": approx
+
+#let groups = ((1, 2, 2.5, 3, 4, 7), (10, 11, 13))
+
+#let result = violins(groups)
+#assert.eq(result.len(), 2)
+#assert.eq(result.at(0).grid.len(), 100)
+#assert.eq(result.at(0).outline.len(), 200)
+#assert.eq(result.at(0).stats, boxplot(groups.at(0)))
+#approx(result.at(0).grid.first(), 1 - 2 * result.at(0).bandwidth, eps: 1e-12)
+#assert.eq(calc.max(..result.map(violin => calc.max(..violin.width))), 1.0)
+
+// The outline goes up the right side and down the left side
+#let violin = result.at(1)
+#assert.eq(violin.outline.first(), (violin.width.first(), violin.grid.first()))
+#assert.eq(violin.outline.last(), (-violin.width.first(), violin.grid.first()))
+
+#let result = violins(groups, scale: "width", cut: 0, common-grid: true, num-points: 50)
+#assert.eq(result.at(0).grid, result.at(1).grid)
+#assert.eq((result.at(0).grid.first(), result.at(0).grid.last()), (1.0, 13.0))
+#for violin in result {
+  assert.eq(calc.max(..violin.width), 1.0)
+}